@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::builder::ExecutableInfo;
+use crate::executor::{
+    execute_program, MetricHistory, ProcessEnv, ProgramOptions, RuntimeMetric, StepUpdate,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricStats {
+    pub metric: String,
+    pub samples: usize,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    pub p95: f64,
+}
+
+impl MetricStats {
+    fn from_history(metric: String, history: &MetricHistory) -> Option<Self> {
+        Some(Self {
+            metric,
+            samples: history.values.len(),
+            min: history.min()?,
+            avg: history.average()?,
+            max: history.max()?,
+            p95: history.percentile(95.0)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub program: String,
+    pub duration: f64,
+    pub exit_code: Option<i32>,
+    pub failure_reason: Option<String>,
+    pub metrics: Vec<MetricStats>,
+}
+
+impl BenchReport {
+    pub fn success(&self) -> bool {
+        self.failure_reason.is_none()
+    }
+
+    pub fn render_table(&self) -> String {
+        let width = self
+            .metrics
+            .iter()
+            .map(|stats| stats.metric.len())
+            .max()
+            .unwrap_or(0)
+            .max("metric".len());
+
+        let mut out = format!(
+            "{:<width$}  {:>8}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+            "metric",
+            "samples",
+            "min",
+            "avg",
+            "max",
+            "p95",
+            width = width
+        );
+        for stats in &self.metrics {
+            out.push_str(&format!(
+                "{:<width$}  {:>8}  {:>10.2}  {:>10.2}  {:>10.2}  {:>10.2}\n",
+                stats.metric,
+                stats.samples,
+                stats.min,
+                stats.avg,
+                stats.max,
+                stats.p95,
+                width = width
+            ));
+        }
+
+        out
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub fn record_metric(metrics: &mut BTreeMap<String, MetricHistory>, metric: &RuntimeMetric) {
+    let Some(value) = metric.parse_numeric_value() else {
+        return;
+    };
+
+    let history = metrics.entry(metric.series_key()).or_insert_with(|| {
+        let mut history = MetricHistory::from_metric(metric);
+        history.max_history = usize::MAX;
+        history
+    });
+    history.add_value(value, metric.timestamp);
+}
+
+pub fn summarize(metrics: &BTreeMap<String, MetricHistory>) -> Vec<MetricStats> {
+    metrics
+        .iter()
+        .filter_map(|(key, history)| MetricStats::from_history(key.clone(), history))
+        .collect()
+}
+
+pub async fn run_bench(
+    exec_info: ExecutableInfo,
+    duration: Duration,
+    env: &ProcessEnv,
+) -> anyhow::Result<BenchReport> {
+    let (log_tx, _log_rx) = mpsc::unbounded_channel();
+    let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+    let program = exec_info.name.clone();
+
+    let options = ProgramOptions::default().with_timeout(Some(duration));
+    let result = execute_program(exec_info, log_tx, step_tx, env, options).await?;
+
+    let mut metrics = BTreeMap::new();
+    while let Ok(update) = step_rx.try_recv() {
+        if let StepUpdate::Metric(metric) = update {
+            record_metric(&mut metrics, &metric);
+        }
+    }
+
+    Ok(BenchReport {
+        program,
+        duration: result.duration,
+        exit_code: result.exit_code,
+        failure_reason: if result.timed_out {
+            None
+        } else {
+            result.failure_reason
+        },
+        metrics: summarize(&metrics),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parser::MetricParser;
+
+    #[test]
+    fn test_record_metric_keeps_every_sample() {
+        let mut metrics = BTreeMap::new();
+        for i in 0..120 {
+            let line = format!("[IGNIS_METRIC] perf:frame_ms={}", i);
+            record_metric(
+                &mut metrics,
+                &MetricParser::parse_metric_line(&line).unwrap(),
+            );
+        }
+        let text = MetricParser::parse_metric_line("[IGNIS_METRIC] scene:name=intro").unwrap();
+        record_metric(&mut metrics, &text);
+
+        let stats = summarize(&metrics);
+        assert_eq!(
+            stats,
+            vec![MetricStats {
+                metric: "perf:frame_ms".to_string(),
+                samples: 120,
+                min: 0.0,
+                avg: 59.5,
+                max: 119.0,
+                p95: 113.0,
+            }]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_bench_stops_program_after_duration() {
+        let exec_info = ExecutableInfo {
+            path: "/bin/sh".into(),
+            name: "sh".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec![
+                "-c".to_string(),
+                concat!(
+                    "echo '[IGNIS_METRIC] perf:fps=60'; ",
+                    "echo '[IGNIS_METRIC] perf:fps=30'; ",
+                    "exec sleep 30"
+                )
+                .to_string(),
+            ],
+        };
+
+        let report = run_bench(
+            exec_info,
+            Duration::from_millis(300),
+            &ProcessEnv::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(report.success());
+        assert!(report.duration < 10.0);
+        assert_eq!(report.metrics.len(), 1);
+        assert_eq!(report.metrics[0].samples, 2);
+        assert_eq!(report.metrics[0].avg, 45.0);
+        assert!(report.render_table().contains("perf:fps"));
+    }
+}
@@ -1,7 +1,14 @@
+use serde::Serialize;
 use std::path::{Path, PathBuf};
 
 use crate::{
-    command::CMakeCommands,
+    command::{CMakeCommands, Generator},
+    config::Config,
+    dependency_graph::GraphError,
+    executor::ProcessEnv,
+    plan::BuildPlan,
+    presets,
+    stage::Stage,
     target::{Target, TargetKind},
 };
 
@@ -12,6 +19,17 @@ pub struct ExecutableInfo {
     pub project_dir: PathBuf,
     pub build_dir: PathBuf,
     pub install_dir: PathBuf,
+    pub args: Vec<String>,
+}
+
+impl ExecutableInfo {
+    pub fn args_display(&self) -> String {
+        if self.args.is_empty() {
+            "(none)".to_string()
+        } else {
+            self.args.join(" ")
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -20,12 +38,17 @@ pub struct Builder {
     root: Target,
     targets: Vec<Target>,
     cmake: CMakeCommands,
+    generator_override: Option<Generator>,
+    dry_run: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BuildStep {
     pub description: String,
     pub commands: Vec<String>,
+    pub working_dir: Option<PathBuf>,
+    pub shell: bool,
+    pub target: Option<String>,
 }
 
 impl BuildStep {
@@ -33,10 +56,76 @@ impl BuildStep {
         Self {
             description,
             commands,
+            working_dir: None,
+            shell: false,
+            target: None,
+        }
+    }
+
+    pub fn with_working_dir(mut self, working_dir: PathBuf) -> Self {
+        self.working_dir = Some(working_dir);
+        self
+    }
+
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn with_shell(mut self, shell: bool) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    pub fn argv(&self) -> Vec<String> {
+        if !self.shell {
+            return self.commands.clone();
+        }
+
+        let line = self.commands.join(" ");
+        if cfg!(windows) {
+            vec!["cmd".to_string(), "/C".to_string(), line]
+        } else {
+            vec!["sh".to_string(), "-c".to_string(), line]
+        }
+    }
+
+    pub fn command_line(&self) -> String {
+        shell_join(&self.argv())
+    }
+
+    /// The command line prefixed with a `cd` into its working directory.
+    pub fn invocation(&self) -> String {
+        match &self.working_dir {
+            Some(dir) => format!(
+                "cd {} && {}",
+                shell_quote(&dir.display().to_string()),
+                self.command_line()
+            ),
+            None => self.command_line(),
         }
     }
 }
 
+pub fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 impl std::fmt::Display for BuildStep {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{}", self.description)?;
@@ -49,17 +138,45 @@ impl std::fmt::Display for BuildStep {
 
 impl Builder {
     pub fn new(root: Target, preset: String) -> Self {
-        let cmake = CMakeCommands::new(preset.clone(), true);
+        let generator =
+            Generator::from_name(root.config.generator_for(&preset)).unwrap_or(Generator::Ninja);
+        let cmake = cmake_commands(&root, &preset, generator);
         Self {
             root,
             preset,
             targets: Vec::new(),
             cmake,
+            generator_override: None,
+            dry_run: false,
         }
     }
 
-    pub fn with_ninja(mut self, ninja: bool) -> Self {
-        self.cmake = CMakeCommands::new(self.preset.clone(), ninja);
+    pub fn with_generator(mut self, generator: Generator) -> Self {
+        self.cmake = cmake_commands(&self.root, &self.preset, generator);
+        self.generator_override = Some(generator);
+        self
+    }
+
+    pub fn with_ninja(self, ninja: bool) -> Self {
+        self.with_generator(if ninja {
+            Generator::Ninja
+        } else {
+            Generator::Default
+        })
+    }
+
+    pub fn with_preset(mut self, preset: String) -> Self {
+        let config = &self.root.config;
+        let generator = config
+            .presets
+            .get(&preset)
+            .and_then(|section| section.generator.as_deref())
+            .and_then(Generator::from_name)
+            .or(self.generator_override)
+            .or_else(|| Generator::from_name(config.generator_for(&preset)))
+            .unwrap_or(Generator::Ninja);
+        self.cmake = cmake_commands(&self.root, &preset, generator);
+        self.preset = preset;
         self
     }
 
@@ -68,6 +185,28 @@ impl Builder {
         self
     }
 
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Resolves the stage order and the steps each stage would run, without executing anything.
+    pub fn plan(&self, stages: &[Stage]) -> Result<BuildPlan, GraphError> {
+        BuildPlan::resolve(self, stages, None)
+    }
+
+    pub fn plan_for(
+        &self,
+        stages: &[Stage],
+        exec_info: &ExecutableInfo,
+    ) -> Result<BuildPlan, GraphError> {
+        BuildPlan::resolve(self, stages, Some(exec_info))
+    }
+
     pub fn preset(&self) -> &str {
         &self.preset
     }
@@ -84,6 +223,10 @@ impl Builder {
         &self.cmake
     }
 
+    pub fn build_env(&self) -> ProcessEnv {
+        self.root.config.build_env_for(&self.preset)
+    }
+
     pub fn generate_build_all(&self) -> Vec<BuildStep> {
         let mut steps: Vec<BuildStep> = Vec::new();
 
@@ -93,12 +236,7 @@ impl Builder {
             let build_dir_str = build_dir.display().to_string();
             let install_dir_str = install_dir.display().to_string();
 
-            let source_dir = target.path.display().to_string();
-
-            steps.push(
-                self.cmake
-                    .configure_step("CMake", source_dir, build_dir_str.clone()),
-            );
+            steps.push(self.configure_target_step("CMake", &target.path, build_dir_str.clone()));
 
             let target_name = target.config.build.name.as_deref().unwrap_or("project");
 
@@ -123,7 +261,7 @@ impl Builder {
     }
 
     fn find_executables_in_target(&self, target_path: &Path) -> Vec<ExecutableInfo> {
-        let build_dir = target_path.join("builds").join(&self.preset);
+        let build_dir = self.build_dir_for(target_path, &self.preset);
 
         if !build_dir.exists() {
             return Vec::new();
@@ -134,14 +272,19 @@ impl Builder {
             .into_iter()
             .flat_map(|entries| entries.flatten())
             .filter(|entry| {
+                // Cross-compiled binaries usually lack the host's executable bit, so
+                // build.skip_exec_check trusts every regular file in the build dir.
+                // It is opt-in: a toolchain alone does not imply it.
                 entry.file_type().ok().map(|t| t.is_file()).unwrap_or(false)
-                    && is_executable(&entry.path())
+                    && (self.root.config.build.skip_exec_check || is_executable(&entry.path()))
             })
             .map(|entry| {
                 let install_dir = target_path.join("install");
+                let name = entry.file_name().to_string_lossy().to_string();
                 ExecutableInfo {
                     path: entry.path(),
-                    name: entry.file_name().to_string_lossy().to_string(),
+                    args: self.configured_args(&name),
+                    name,
                     project_dir: target_path.to_path_buf(),
                     build_dir: build_dir.clone(),
                     install_dir,
@@ -150,38 +293,145 @@ impl Builder {
             .collect()
     }
 
+    fn build_dir_for(&self, target_path: &Path, preset: &str) -> PathBuf {
+        presets::find_preset(target_path, preset)
+            .and_then(|p| p.binary_dir_in(target_path))
+            .unwrap_or_else(|| target_path.join("builds").join(preset))
+    }
+
+    pub fn configure_target_step(
+        &self,
+        target_name: &str,
+        target_path: &Path,
+        build_dir: String,
+    ) -> BuildStep {
+        let source_dir = target_path.display().to_string();
+
+        if let Some(preset) = presets::find_preset(target_path, &self.preset) {
+            // Without a preset binaryDir CMake would pick its own build tree.
+            let build_dir = preset.binary_dir.is_none().then_some(build_dir);
+            self.cmake
+                .preset_configure_step(target_name, source_dir, build_dir)
+        } else {
+            self.cmake
+                .configure_step(target_name, source_dir, build_dir)
+        }
+    }
+
+    fn configured_args(&self, executable: &str) -> Vec<String> {
+        self.root
+            .config
+            .exec
+            .args
+            .get(executable)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn has_cmake_cache(&self) -> bool {
+        !self.targets.is_empty()
+            && self.targets.iter().all(|target| {
+                self.build_dir_for(&target.path, &self.preset)
+                    .join("CMakeCache.txt")
+                    .exists()
+            })
+    }
+
     pub fn get_root_dirs(&self) -> (PathBuf, PathBuf) {
-        let build_dir = self.root.path.join("builds").join(&self.preset);
+        let build_dir = self.build_dir_for(&self.root.path, &self.preset);
         let install_dir = self.root.path.join("install");
 
         (build_dir, install_dir)
     }
 
     pub fn get_dirs(&self, target_path: PathBuf, preset: &str) -> (PathBuf, PathBuf) {
-        let build_dir = target_path.join("builds").join(preset);
+        let build_dir = self.build_dir_for(&target_path, preset);
         let install_dir = self.root.path.join("install");
 
         (build_dir, install_dir)
     }
 
     pub fn generate_build_target_steps(&self, exec_info: &ExecutableInfo) -> Vec<BuildStep> {
-        let source_dir = exec_info.project_dir.display().to_string();
+        [Stage::Configure, Stage::Build, Stage::Install]
+            .into_iter()
+            .filter_map(|stage| self.generate_scoped_step(stage, exec_info))
+            .collect()
+    }
+
+    // Clean, Test and PostBuild are not scoped; they always cover every target.
+    pub fn generate_scoped_step(
+        &self,
+        stage: Stage,
+        exec_info: &ExecutableInfo,
+    ) -> Option<BuildStep> {
+        let name = &exec_info.name;
         let build_dir = exec_info.build_dir.display().to_string();
-        let install_dir = exec_info.install_dir.display().to_string();
 
-        vec![
-            self.cmake
-                .configure_step(&exec_info.name, source_dir, build_dir.clone()),
-            self.cmake
-                .build_target_step(&exec_info.name, build_dir.clone(), &exec_info.name),
-            self.cmake
-                .install_step(&exec_info.name, build_dir, install_dir),
-        ]
+        match stage {
+            Stage::Configure => {
+                Some(self.configure_target_step(name, &exec_info.project_dir, build_dir))
+            }
+            Stage::Build => Some(self.cmake.build_target_step(name, build_dir, name)),
+            Stage::Install => {
+                let install_dir = exec_info.install_dir.display().to_string();
+                Some(self.cmake.install_step(name, build_dir, install_dir))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn resolve_build_target(&self, name: &str) -> anyhow::Result<ExecutableInfo> {
+        if let Some(exec_info) = self
+            .find_executables()
+            .into_iter()
+            .find(|exec_info| exec_info.name == name)
+        {
+            return Ok(exec_info);
+        }
+
+        if let Some(target) = self
+            .targets
+            .iter()
+            .find(|target| target.config.build.name.as_deref() == Some(name))
+        {
+            let (build_dir, install_dir) = self.get_dirs(target.path.clone(), &self.preset);
+            return Ok(ExecutableInfo {
+                path: build_dir.join(name),
+                name: name.to_string(),
+                project_dir: target.path.clone(),
+                build_dir,
+                install_dir,
+                args: self.configured_args(name),
+            });
+        }
+
+        let mut available: Vec<String> = self
+            .find_executables()
+            .into_iter()
+            .map(|exec_info| exec_info.name)
+            .chain(
+                self.targets
+                    .iter()
+                    .filter_map(|target| target.config.build.name.clone()),
+            )
+            .collect();
+        available.sort();
+        available.dedup();
+
+        anyhow::bail!(
+            "Unknown target '{}' (available: {})",
+            name,
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        )
     }
 
     pub fn generate_clean_target_command(&self, exec_info: &ExecutableInfo) -> Vec<String> {
         self.cmake
-            .build_target(exec_info.build_dir.display().to_string(), "clean")
+            .clean_build(exec_info.build_dir.display().to_string())
     }
 
     pub fn generate_clean_all(&self) -> Vec<BuildStep> {
@@ -204,11 +454,61 @@ impl Builder {
         steps
     }
 
-    pub fn generate_clean_command(&self, build_dir: String, install_dir: String) -> Vec<String> {
-        self.cmake.clean(&[build_dir, install_dir])
+    pub fn generate_test_all(&self) -> Vec<BuildStep> {
+        let mut steps: Vec<BuildStep> = Vec::new();
+
+        for target in &self.targets {
+            let (build_dir, _install_dir) = self.get_dirs(target.path.clone(), &self.preset);
+
+            if !build_dir.join("CTestTestfile.cmake").exists() {
+                continue;
+            }
+
+            let target_name = target.config.build.name.as_deref().unwrap_or("project");
+
+            steps.push(
+                self.cmake
+                    .test_step(target_name, build_dir.display().to_string()),
+            );
+        }
+
+        steps
+    }
+
+    pub fn generate_postbuild_all(&self) -> Vec<BuildStep> {
+        let mut steps: Vec<BuildStep> = Vec::new();
+
+        for target in &self.targets {
+            let (build_dir, _install_dir) = self.get_dirs(target.path.clone(), &self.preset);
+            let target_name = target.config.build.name.as_deref().unwrap_or("project");
+
+            for command in &target.config.stages.post_build.commands {
+                if command.is_empty() {
+                    continue;
+                }
+
+                steps.push(
+                    BuildStep::new(
+                        format!("Post-build {}: {}", target_name, command.join(" ")),
+                        command.clone(),
+                    )
+                    .with_working_dir(build_dir.clone())
+                    .with_shell(target.config.stages.post_build.shell),
+                );
+            }
+        }
+
+        steps
     }
 }
 
+fn cmake_commands(root: &Target, preset: &str, generator: Generator) -> CMakeCommands {
+    CMakeCommands::new(preset.to_string(), generator)
+        .with_jobs(root.config.jobs_for(preset))
+        .with_cache_flags(root.config.cmake_flags_for(preset))
+        .with_toolchain_file(root.config.build.toolchain_path())
+}
+
 #[cfg(unix)]
 fn is_executable(path: &Path) -> bool {
     use std::os::unix::fs::PermissionsExt;
@@ -223,6 +523,505 @@ fn is_executable(_path: &Path) -> bool {
     true
 }
 
-pub fn detect_available_presets(_source_dir: &Path) -> Vec<String> {
-    vec!["debug".to_string(), "release".to_string()]
+pub fn detect_available_presets(source_dir: &Path) -> Vec<String> {
+    let presets = presets::load_presets(source_dir);
+    if presets.is_empty() {
+        return vec!["debug".to_string(), "release".to_string()];
+    }
+
+    presets.into_iter().map(|preset| preset.name).collect()
+}
+
+pub fn available_presets(config: &Config, source_dir: &Path) -> Vec<String> {
+    let names = config.preset_names();
+    if !names.is_empty() {
+        return names;
+    }
+
+    detect_available_presets(source_dir)
+}
+
+pub fn resolve_preset(name: &str, available: &[String]) -> anyhow::Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Preset name is required");
+    }
+
+    available
+        .iter()
+        .find(|preset| preset.eq_ignore_ascii_case(name))
+        .cloned()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown preset '{}' (available: {})",
+                name,
+                available.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn available() -> Vec<String> {
+        vec!["debug".to_string(), "release".to_string()]
+    }
+
+    #[test]
+    fn test_command_line_quotes_arguments() {
+        let step = BuildStep::new(
+            "Configuring".to_string(),
+            vec!["cmake", "-S", "my project", "-DNAME=it's"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        assert_eq!(
+            step.command_line(),
+            r"cmake -S 'my project' '-DNAME=it'\''s'"
+        );
+
+        let step = BuildStep::new("Tests".to_string(), vec!["ctest".to_string()]).with_shell(true);
+        if cfg!(unix) {
+            assert_eq!(step.command_line(), "sh -c ctest");
+        }
+
+        let step = step.with_working_dir(PathBuf::from("/tmp/my build"));
+        assert!(step.invocation().starts_with("cd '/tmp/my build' && "));
+    }
+
+    #[test]
+    fn test_resolve_preset() {
+        assert_eq!(resolve_preset("release", &available()).unwrap(), "release");
+        assert_eq!(resolve_preset(" Debug ", &available()).unwrap(), "debug");
+    }
+
+    #[test]
+    fn test_resolve_preset_rejects_unknown() {
+        let err = resolve_preset("profile", &available()).unwrap_err();
+        assert!(err.to_string().contains("debug, release"));
+        assert!(resolve_preset("  ", &available()).is_err());
+    }
+
+    #[test]
+    fn test_with_preset_keeps_ninja() {
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config: crate::Config::default(),
+        };
+        let builder = Builder::new(root, "debug".to_string())
+            .with_ninja(false)
+            .with_preset("release".to_string());
+
+        assert_eq!(builder.preset(), "release");
+        assert!(!builder.cmake().ninja());
+        assert_eq!(builder.cmake().generator(), Generator::Default);
+    }
+
+    #[test]
+    fn test_build_jobs_flag() {
+        let mut config = crate::Config::default();
+        config.build.jobs = Some(4);
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+        let builder = Builder::new(root, "debug".to_string()).with_preset("release".to_string());
+
+        let step = builder
+            .cmake()
+            .build_step("engine", "/tmp/test/builds/release".to_string());
+        assert_eq!(
+            step.commands,
+            vec![
+                "cmake",
+                "--build",
+                "/tmp/test/builds/release",
+                "--parallel",
+                "4"
+            ]
+        );
+        assert!(builder
+            .cmake()
+            .build_target("/tmp/build".to_string(), "editor")
+            .ends_with(&["--parallel".to_string(), "4".to_string()]));
+    }
+
+    fn builder_with_generator(generator: &str) -> Builder {
+        let mut config = crate::Config::default();
+        config.build.generator = generator.to_string();
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+        Builder::new(root, "debug".to_string())
+    }
+
+    #[test]
+    fn test_configure_command_per_generator() {
+        for (name, flag) in [
+            ("ninja", "-GNinja"),
+            ("make", "-GUnix Makefiles"),
+            ("xcode", "-GXcode"),
+        ] {
+            let builder = builder_with_generator(name);
+            let step = builder.cmake().configure_step(
+                "engine",
+                "/tmp/test".to_string(),
+                "/tmp/test/builds/debug".to_string(),
+            );
+            assert_eq!(
+                step.commands,
+                vec![
+                    "cmake",
+                    "--preset=debug",
+                    "-S",
+                    "/tmp/test",
+                    "-B",
+                    "/tmp/test/builds/debug",
+                    flag,
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_clean_command_per_generator() {
+        assert_eq!(
+            builder_with_generator("ninja")
+                .cmake()
+                .clean_build("/tmp/build".to_string()),
+            vec!["ninja", "-C", "/tmp/build", "-t", "clean"]
+        );
+        assert_eq!(
+            builder_with_generator("make")
+                .cmake()
+                .clean_build("/tmp/build".to_string()),
+            vec!["make", "-C", "/tmp/build", "clean"]
+        );
+        assert_eq!(
+            builder_with_generator("Unix Makefiles").cmake().generator(),
+            Generator::Make
+        );
+    }
+
+    #[test]
+    fn test_cmake_presets_file_drives_configure() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        std::fs::write(
+            dir.join("CMakePresets.json"),
+            r#"{"version": 3, "configurePresets": [
+                {"name": "dev", "binaryDir": "${sourceDir}/out/${presetName}"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(detect_available_presets(&dir), vec!["dev".to_string()]);
+
+        let root = Target {
+            path: dir.clone(),
+            kind: TargetKind::Root,
+            config: crate::Config::default(),
+        };
+        let builder = Builder::new(root, "dev".to_string());
+
+        let (build_dir, _) = builder.get_root_dirs();
+        assert_eq!(build_dir, dir.join("out").join("dev"));
+
+        let step = builder.configure_target_step("CMake", &dir, build_dir.display().to_string());
+        assert_eq!(
+            step.commands,
+            vec![
+                "cmake".to_string(),
+                "--preset".to_string(),
+                "dev".to_string(),
+                "-S".to_string(),
+                dir.display().to_string(),
+            ]
+        );
+
+        std::fs::write(
+            dir.join("CMakePresets.json"),
+            r#"{"version": 3, "configurePresets": [{"name": "dev"}]}"#,
+        )
+        .unwrap();
+        let (build_dir, _) = builder.get_root_dirs();
+        assert_eq!(build_dir, dir.join("builds").join("dev"));
+        let step = builder.configure_target_step("CMake", &dir, build_dir.display().to_string());
+        assert_eq!(
+            step.commands[5..],
+            ["-B".to_string(), build_dir.display().to_string()]
+        );
+    }
+
+    #[test]
+    fn test_presets_fall_back_without_presets_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            detect_available_presets(dir.path()),
+            vec!["debug".to_string(), "release".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_configure_includes_cmake_flags_in_stable_order() {
+        std::env::set_var("IGNIS_TEST_TOOLCHAIN", "/opt/toolchain.cmake");
+        let mut config = crate::Config::default();
+        config
+            .build
+            .cmake_flags
+            .insert("ZETA".to_string(), "1".to_string());
+        config.build.cmake_flags.insert(
+            "CMAKE_EXPORT_COMPILE_COMMANDS".to_string(),
+            "ON".to_string(),
+        );
+        config.build.cmake_flags.insert(
+            "CMAKE_TOOLCHAIN_FILE".to_string(),
+            "${IGNIS_TEST_TOOLCHAIN}".to_string(),
+        );
+        let mut release = crate::config::BuildPresetConfig::default();
+        release
+            .cmake_flags
+            .insert("ZETA".to_string(), "2".to_string());
+        config.build.presets.insert("release".to_string(), release);
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+
+        let builder = Builder::new(root, "debug".to_string());
+        let step = builder.cmake().configure_step(
+            "engine",
+            "/tmp/test".to_string(),
+            "/tmp/build".to_string(),
+        );
+        assert_eq!(
+            &step.commands[7..],
+            &[
+                "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON",
+                "-DCMAKE_TOOLCHAIN_FILE=/opt/toolchain.cmake",
+                "-DZETA=1",
+            ]
+        );
+
+        let builder = builder.with_preset("release".to_string());
+        let step = builder.cmake().configure_step(
+            "engine",
+            "/tmp/test".to_string(),
+            "/tmp/build".to_string(),
+        );
+        assert_eq!(step.commands.last().map(String::as_str), Some("-DZETA=2"));
+    }
+
+    #[test]
+    fn test_named_preset_section_drives_builder() {
+        let mut config = crate::Config::default();
+        config.build.jobs = Some(2);
+        config.build.env.insert("CC".to_string(), "gcc".to_string());
+        let perf = crate::config::PresetConfig {
+            flags: vec!["-O3".to_string(), "-march=native".to_string()],
+            generator: Some("make".to_string()),
+            jobs: Some(16),
+            env: std::collections::HashMap::from([("CC".to_string(), "clang".to_string())]),
+            ..Default::default()
+        };
+        config.presets.insert("perf".to_string(), perf);
+        config
+            .presets
+            .insert("debug".to_string(), Default::default());
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+
+        let builder = Builder::new(root, "debug".to_string());
+        assert!(builder.cmake().ninja());
+        assert_eq!(builder.cmake().jobs(), Some(2));
+        assert_eq!(builder.build_env().vars["CC"], "gcc");
+
+        let builder = builder.with_preset("perf".to_string());
+        assert_eq!(builder.cmake().generator(), Generator::Make);
+        assert_eq!(builder.cmake().jobs(), Some(16));
+        assert_eq!(builder.build_env().vars["CC"], "clang");
+
+        let builder = builder.with_preset("debug".to_string());
+        assert!(builder.cmake().ninja());
+        assert_eq!(builder.build_env().vars["CC"], "gcc");
+        let builder = builder.with_preset("perf".to_string());
+
+        let step = builder.cmake().configure_step(
+            "engine",
+            "/tmp/test".to_string(),
+            "/tmp/build".to_string(),
+        );
+        assert!(step
+            .commands
+            .contains(&"-DCMAKE_CXX_FLAGS=-O3 -march=native".to_string()));
+        assert!(step
+            .commands
+            .contains(&"-DCMAKE_C_FLAGS=-O3 -march=native".to_string()));
+    }
+
+    #[test]
+    fn test_toolchain_file_is_forwarded_to_configure() {
+        let mut config = crate::Config::default();
+        config.build.toolchain_file = Some("/opt/arm/toolchain.cmake".to_string());
+        config
+            .build
+            .cmake_flags
+            .insert("CMAKE_BUILD_TYPE".to_string(), "MinSizeRel".to_string());
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+        let builder = Builder::new(root, "debug".to_string()).with_preset("release".to_string());
+
+        assert_eq!(
+            builder.cmake().toolchain_file(),
+            Some(&PathBuf::from("/opt/arm/toolchain.cmake"))
+        );
+        let step = builder.cmake().configure_step(
+            "engine",
+            "/tmp/test".to_string(),
+            "/tmp/build".to_string(),
+        );
+        assert_eq!(
+            &step.commands[7..],
+            &[
+                "-DCMAKE_TOOLCHAIN_FILE=/opt/arm/toolchain.cmake",
+                "-DCMAKE_BUILD_TYPE=MinSizeRel",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_skip_exec_check_finds_non_executable_binaries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let build_dir = dir.join("builds").join("debug");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        std::fs::write(build_dir.join("firmware.elf"), "").unwrap();
+
+        let target = Target {
+            path: dir.clone(),
+            kind: TargetKind::Executable,
+            config: crate::Config::default(),
+        };
+        let mut config = crate::Config::default();
+        let root = Target {
+            path: dir.clone(),
+            kind: TargetKind::Root,
+            config: config.clone(),
+        };
+        let builder = Builder::new(root, "debug".to_string()).with_targets(vec![target.clone()]);
+        assert!(builder.find_executables().is_empty());
+
+        config.build.skip_exec_check = true;
+        let root = Target {
+            path: dir.clone(),
+            kind: TargetKind::Root,
+            config,
+        };
+        let builder = Builder::new(root, "debug".to_string()).with_targets(vec![target]);
+        let names: Vec<String> = builder
+            .find_executables()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, vec!["firmware.elf".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_build_target() {
+        let mut config = crate::Config::default();
+        config.build.name = Some("editor".to_string());
+        let target = Target {
+            path: PathBuf::from("/tmp/test/editor"),
+            kind: TargetKind::Executable,
+            config,
+        };
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config: crate::Config::default(),
+        };
+        let builder = Builder::new(root, "debug".to_string()).with_targets(vec![target]);
+
+        let exec_info = builder.resolve_build_target("editor").unwrap();
+        assert_eq!(exec_info.project_dir, PathBuf::from("/tmp/test/editor"));
+        assert_eq!(
+            exec_info.build_dir,
+            PathBuf::from("/tmp/test/editor/builds/debug")
+        );
+
+        let err = builder.resolve_build_target("game").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown target 'game' (available: editor)");
+    }
+
+    #[test]
+    fn test_with_ninja_maps_to_generator() {
+        let builder = builder_with_generator("xcode");
+        assert_eq!(builder.cmake().generator(), Generator::Xcode);
+        assert_eq!(
+            builder.with_ninja(true).cmake().generator(),
+            Generator::Ninja
+        );
+    }
+
+    #[test]
+    fn test_configure_without_ninja_omits_generator_flag() {
+        let builder = builder_with_generator("ninja").with_ninja(false);
+        let step = builder.cmake().configure_step(
+            "engine",
+            "/tmp/test".to_string(),
+            "/tmp/test/builds/debug".to_string(),
+        );
+        assert_eq!(
+            step.commands,
+            vec![
+                "cmake",
+                "--preset=debug",
+                "-S",
+                "/tmp/test",
+                "-B",
+                "/tmp/test/builds/debug",
+            ]
+        );
+        assert_eq!(
+            builder.cmake().clean_build("/tmp/build".to_string()),
+            vec![
+                "cmake",
+                "--build",
+                "/tmp/build",
+                "--target",
+                "clean",
+                "--parallel"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_without_jobs_keeps_generator_default() {
+        let root = Target {
+            path: PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config: crate::Config::default(),
+        };
+        let builder = Builder::new(root, "debug".to_string());
+
+        let step = builder
+            .cmake()
+            .build_step("engine", "/tmp/build".to_string());
+        assert_eq!(step.commands.last().map(String::as_str), Some("--parallel"));
+    }
 }
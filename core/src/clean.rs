@@ -0,0 +1,119 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanEntry {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+impl CleanEntry {
+    pub fn describe(&self) -> String {
+        format!("{} ({})", self.path.display(), format_size(self.bytes))
+    }
+}
+
+pub fn dir_size(path: &Path) -> io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += dir_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+pub fn plan(paths: &[PathBuf]) -> Result<Vec<CleanEntry>> {
+    paths
+        .iter()
+        .filter(|path| path.symlink_metadata().is_ok())
+        .map(|path| {
+            let bytes =
+                dir_size(path).with_context(|| format!("Failed to measure {}", path.display()))?;
+            Ok(CleanEntry {
+                path: path.clone(),
+                bytes,
+            })
+        })
+        .collect()
+}
+
+pub fn total_bytes(entries: &[CleanEntry]) -> u64 {
+    entries.iter().map(|entry| entry.bytes).sum()
+}
+
+pub fn needs_confirmation(entries: &[CleanEntry], threshold_mb: Option<u64>) -> bool {
+    threshold_mb.is_some_and(|mb| total_bytes(entries) > mb.saturating_mul(BYTES_PER_MB))
+}
+
+pub fn remove(entries: &[CleanEntry]) -> Result<()> {
+    for entry in entries {
+        std::fs::remove_dir_all(&entry.path)
+            .with_context(|| format!("Failed to remove {}", entry.path.display()))?;
+    }
+    Ok(())
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_measures_existing_dirs_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let build_dir = dir.path().join("builds").join("debug");
+        std::fs::create_dir_all(build_dir.join("CMakeFiles")).unwrap();
+        std::fs::write(build_dir.join("app"), vec![0u8; 3000]).unwrap();
+        std::fs::write(build_dir.join("CMakeFiles").join("obj.o"), vec![0u8; 1000]).unwrap();
+
+        let missing = dir.path().join("install");
+        let entries = plan(&[build_dir.clone(), missing]).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![CleanEntry {
+                path: build_dir,
+                bytes: 4000
+            }]
+        );
+        assert_eq!(total_bytes(&entries), 4000);
+        assert!(!needs_confirmation(&entries, None));
+        assert!(!needs_confirmation(&entries, Some(1)));
+        assert!(needs_confirmation(&entries, Some(0)));
+
+        remove(&entries).unwrap();
+        assert!(plan(&[entries[0].path.clone()]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -1,5 +1,6 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "ignis")]
@@ -19,13 +20,67 @@ pub struct Cli {
     #[arg(short, long, help = "Skip TUI and use simple logger")]
     pub no_tui: bool,
 
+    #[arg(
+        long,
+        global = true,
+        help = "Print the planned commands without running them"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "text",
+        help = "Output format without the TUI (json implies --no-tui)"
+    )]
+    pub format: OutputFormat,
+
     #[arg(long, help = "Configuration file path")]
     pub config: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "CMake toolchain file (overrides build.toolchain_file)"
+    )]
+    pub toolchain: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PORT",
+        help = "Serve runtime metrics in Prometheus format (requires the metrics-server feature)"
+    )]
+    pub metrics_port: Option<u16>,
+
+    #[arg(long, value_name = "NAME", help = "Only build the named target")]
+    pub target: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write step timings as a Chrome trace"
+    )]
+    pub trace: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        help = "Abort if ignis runs longer than this many seconds"
+    )]
+    pub timeout: Option<u64>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum HistoryType {
     Build,
@@ -39,6 +94,19 @@ pub enum HistoryCommands {
     Show {
         #[arg(short, long, help = "Number of entries to show")]
         count: Option<usize>,
+
+        #[arg(long, help = "Only show builds for this preset")]
+        preset: Option<String>,
+
+        #[arg(long, help = "Only show failed builds")]
+        failed_only: bool,
+
+        #[arg(
+            long,
+            value_name = "YYYY-MM-DD",
+            help = "Only show builds on or after this date"
+        )]
+        since: Option<chrono::NaiveDate>,
     },
 
     #[command(about = "Clear history")]
@@ -61,6 +129,12 @@ pub enum Commands {
         preset: String,
     },
 
+    #[command(about = "Run CTest for built targets")]
+    Test {
+        #[arg(help = "Build preset")]
+        preset: Option<String>,
+    },
+
     #[command(about = "List available presets")]
     Presets,
 
@@ -76,6 +150,26 @@ pub enum Commands {
         preset: String,
     },
 
+    #[command(about = "Check the build environment and configuration")]
+    Doctor,
+
+    #[command(about = "Run an executable headless and summarize its runtime metrics")]
+    Bench {
+        #[arg(help = "Executable to run")]
+        target: String,
+
+        #[arg(
+            long,
+            default_value = "30s",
+            value_parser = parse_duration,
+            help = "How long to run before stopping the program (e.g. 500ms, 30s, 2m)"
+        )]
+        duration: Duration,
+    },
+
+    #[command(about = "Rebuild automatically when sources change")]
+    Watch,
+
     #[command(about = "Initialize a new ignis.toml configuration")]
     Init {
         #[arg(long, help = "Project name")]
@@ -83,6 +177,32 @@ pub enum Commands {
     },
 }
 
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", text))?;
+    let secs = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => {
+            return Err(format!(
+                "unknown duration unit '{}' (use ms, s, m or h)",
+                unit
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("duration '{}' is out of range", text))
+}
+
 impl Cli {
     pub fn source_directory(&self) -> PathBuf {
         self.source_dir
@@ -90,3 +210,21 @@ impl Cli {
             .unwrap_or_else(|| std::env::current_dir().unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5400)));
+        assert!(parse_duration("10d").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration(&format!("{}h", "9".repeat(400))).is_err());
+        assert!(parse_duration("1e400").is_err());
+    }
+}
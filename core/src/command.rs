@@ -1,14 +1,116 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
 use crate::builder::BuildStep;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Generator {
+    /// Lets CMake pick its platform default; no `-G` is passed.
+    Default,
+    Ninja,
+    Make,
+    Xcode,
+}
+
+impl Generator {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ninja" => Some(Generator::Ninja),
+            "make" | "makefiles" | "unix makefiles" => Some(Generator::Make),
+            "xcode" => Some(Generator::Xcode),
+            _ => None,
+        }
+    }
+
+    pub fn cmake_name(&self) -> Option<&'static str> {
+        match self {
+            Generator::Default => None,
+            Generator::Ninja => Some("Ninja"),
+            Generator::Make => Some("Unix Makefiles"),
+            Generator::Xcode => Some("Xcode"),
+        }
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Generator::Default => "cmake",
+            Generator::Ninja => "ninja",
+            Generator::Make => "make",
+            Generator::Xcode => "xcodebuild",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CMakeCommands {
     preset: String,
-    ninja: bool,
+    generator: Generator,
+    jobs: Option<usize>,
+    cache_flags: BTreeMap<String, String>,
+    toolchain_file: Option<PathBuf>,
 }
 
 impl CMakeCommands {
-    pub fn new(preset: String, ninja: bool) -> Self {
-        Self { preset, ninja }
+    pub fn new(preset: String, generator: Generator) -> Self {
+        Self {
+            preset,
+            generator,
+            jobs: None,
+            cache_flags: BTreeMap::new(),
+            toolchain_file: None,
+        }
+    }
+
+    pub fn with_toolchain_file(mut self, toolchain_file: Option<PathBuf>) -> Self {
+        self.toolchain_file = toolchain_file;
+        self
+    }
+
+    pub fn toolchain_file(&self) -> Option<&PathBuf> {
+        self.toolchain_file.as_ref()
+    }
+
+    pub fn with_cache_flags(mut self, cache_flags: BTreeMap<String, String>) -> Self {
+        self.cache_flags = cache_flags;
+        self
+    }
+
+    fn cache_args(&self) -> impl Iterator<Item = String> + '_ {
+        // The toolchain define goes first so an explicit CMAKE_TOOLCHAIN_FILE in
+        // build.cmake_flags still wins, since CMake keeps the last -D for a key.
+        self.toolchain_file
+            .iter()
+            .map(|path| format!("-DCMAKE_TOOLCHAIN_FILE={}", path.display()))
+            .chain(
+                self.cache_flags
+                    .iter()
+                    .map(|(key, value)| format!("-D{}={}", key, value)),
+            )
+    }
+
+    pub fn with_jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn generator(&self) -> Generator {
+        self.generator
+    }
+
+    pub fn ninja(&self) -> bool {
+        self.generator == Generator::Ninja
+    }
+
+    pub fn jobs(&self) -> Option<usize> {
+        self.jobs
+    }
+
+    fn parallel_args(&self) -> Vec<String> {
+        let mut args = vec!["--parallel".to_string()];
+        if let Some(jobs) = self.jobs {
+            args.push(jobs.to_string());
+        }
+        args
     }
 
     pub fn configure_step(
@@ -23,6 +125,28 @@ impl CMakeCommands {
         )
     }
 
+    pub fn preset_configure_step(
+        &self,
+        target_name: &str,
+        source_dir: String,
+        build_dir: Option<String>,
+    ) -> BuildStep {
+        BuildStep::new(
+            format!("Configuring {}", target_name),
+            vec![
+                "cmake".to_string(),
+                "--preset".to_string(),
+                self.preset.clone(),
+                "-S".to_string(),
+                source_dir,
+            ]
+            .into_iter()
+            .chain(build_dir.into_iter().flat_map(|dir| ["-B".to_string(), dir]))
+            .chain(self.cache_args())
+            .collect(),
+        )
+    }
+
     pub fn configure_step_with_prefix(
         &self,
         target_name: &str,
@@ -64,6 +188,10 @@ impl CMakeCommands {
         )
     }
 
+    pub fn test_step(&self, target_name: &str, build_dir: String) -> BuildStep {
+        BuildStep::new(format!("Testing {}", target_name), self.test(build_dir))
+    }
+
     pub fn clean_step(&self, target_name: &str, paths: &[String]) -> BuildStep {
         BuildStep::new(
             format!("Cleaning {}", target_name),
@@ -90,9 +218,10 @@ impl CMakeCommands {
             build_dir,
         ];
 
-        if self.ninja {
-            cmd.push("-GNinja".to_string());
+        if let Some(name) = self.generator.cmake_name() {
+            cmd.push(format!("-G{}", name));
         }
+        cmd.extend(self.cache_args());
 
         if let Some(prefix) = prefix_path {
             cmd.push(format!("-DCMAKE_PREFIX_PATH={}", prefix));
@@ -102,23 +231,49 @@ impl CMakeCommands {
     }
 
     fn build(&self, build_dir: String) -> Vec<String> {
+        let mut cmd = vec!["cmake".to_string(), "--build".to_string(), build_dir];
+        cmd.extend(self.parallel_args());
+        cmd
+    }
+
+    fn test(&self, build_dir: String) -> Vec<String> {
         vec![
-            "cmake".to_string(),
-            "--build".to_string(),
+            "ctest".to_string(),
+            "--test-dir".to_string(),
             build_dir,
-            "--parallel".to_string(),
+            "--output-on-failure".to_string(),
         ]
     }
 
     pub fn build_target(&self, build_dir: String, target: &str) -> Vec<String> {
-        vec![
+        let mut cmd = vec![
             "cmake".to_string(),
             "--build".to_string(),
             build_dir,
             "--target".to_string(),
             target.to_string(),
-            "--parallel".to_string(),
-        ]
+        ];
+        cmd.extend(self.parallel_args());
+        cmd
+    }
+
+    pub fn clean_build(&self, build_dir: String) -> Vec<String> {
+        match self.generator {
+            Generator::Ninja => vec![
+                "ninja".to_string(),
+                "-C".to_string(),
+                build_dir,
+                "-t".to_string(),
+                "clean".to_string(),
+            ],
+            Generator::Make => vec![
+                "make".to_string(),
+                "-C".to_string(),
+                build_dir,
+                "clean".to_string(),
+            ],
+            Generator::Default | Generator::Xcode => self.build_target(build_dir, "clean"),
+        }
     }
 
     fn install(&self, build_dir: String, install_dir: String) -> Vec<String> {
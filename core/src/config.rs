@@ -1,11 +1,48 @@
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use crate::command::Generator;
+use crate::executor::{ProcessEnv, RetryPolicy};
+use crate::logger::LogFormat;
+use crate::stage::Stage;
 use crate::target::{Target, TargetKind};
+use crate::tui::keybinding_manager::KeyPress;
 use crate::Cli;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn validation_error(errors: Vec<ConfigError>) -> anyhow::Error {
+    let lines: Vec<String> = errors
+        .iter()
+        .map(|error| format!("  - {}", error))
+        .collect();
+    anyhow::anyhow!("{} problem(s) found:\n{}", errors.len(), lines.join("\n"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
@@ -22,6 +59,14 @@ pub struct Config {
     pub build: BuildConfig,
     #[serde(default)]
     pub stages: StagesConfig,
+    #[serde(default)]
+    pub monitor: MonitorConfig,
+    #[serde(default)]
+    pub exec: ExecConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
+    #[serde(default)]
+    pub presets: BTreeMap<String, PresetConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +75,10 @@ pub struct HistoryConfig {
     pub max_builds: usize,
     #[serde(default = "default_storage_path")]
     pub storage_path: String,
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    #[serde(default = "default_regression_threshold_pct")]
+    pub regression_threshold_pct: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +87,12 @@ pub struct LogsConfig {
     pub auto_save: bool,
     #[serde(default = "default_save_directory")]
     pub save_directory: String,
+    #[serde(default)]
+    pub auto_save_perf: bool,
+    #[serde(default = "default_max_log_file_kb")]
+    pub max_file_size_kb: u64,
+    #[serde(default = "default_log_format")]
+    pub format: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +111,32 @@ pub struct DisplayConfig {
     pub show_timestamps: bool,
     #[serde(default = "default_theme")]
     pub theme: String,
+    #[serde(default)]
+    pub keep_previous_logs: bool,
+    #[serde(default)]
+    pub relative_numbers: bool,
+    #[serde(default = "default_slowest_steps")]
+    pub slowest_steps: usize,
+    #[serde(default = "default_trend_builds")]
+    pub trend_builds: usize,
+    #[serde(default = "default_tabs")]
+    pub tabs: Vec<String>,
+    #[serde(default = "default_true")]
+    pub highlight_stderr: bool,
+    #[serde(default)]
+    pub collapse_duplicates: bool,
+    #[serde(default = "default_slow_step_secs")]
+    pub slow_step_secs: u64,
+    #[serde(default = "default_refresh_ms")]
+    pub refresh_ms: u64,
+    #[serde(default = "default_true")]
+    pub adaptive_refresh: bool,
+    #[serde(default)]
+    pub notify_on_complete: bool,
+    #[serde(default = "default_true")]
+    pub confirm_quit: bool,
+    #[serde(default)]
+    pub show_minimap: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +159,58 @@ pub struct BuildConfig {
     pub kind: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
+    #[serde(default)]
+    pub parallel_targets: bool,
+    #[serde(default = "default_max_parallel_targets")]
+    pub max_parallel_targets: usize,
+    #[serde(default)]
+    pub jobs: Option<usize>,
+    #[serde(default = "default_generator")]
+    pub generator: String,
+    #[serde(default)]
+    pub cmake_flags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub presets: HashMap<String, BuildPresetConfig>,
+    #[serde(default)]
+    pub toolchain_file: Option<String>,
+    #[serde(default)]
+    pub skip_exec_check: bool,
+    #[serde(default)]
+    pub step_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub env_clear: bool,
+    #[serde(default)]
+    pub retries: usize,
+    #[serde(default)]
+    pub retry_on_patterns: Vec<String>,
+    #[serde(default)]
+    pub smart_configure: bool,
+    #[serde(default)]
+    pub confirm_clean_over_mb: Option<u64>,
+}
+
+/// `[build.presets.<name>]` only carries cache flags; everything else about a
+/// preset lives in the top-level `[presets.<name>]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildPresetConfig {
+    #[serde(default)]
+    pub cmake_flags: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PresetConfig {
+    #[serde(default)]
+    pub cmake_flags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub generator: Option<String>,
+    #[serde(default)]
+    pub jobs: Option<usize>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +221,60 @@ pub struct StagesConfig {
     pub auto_build_on_start: bool,
     #[serde(default)]
     pub stage_dependencies: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub post_build: PostBuildConfig,
+    #[serde(default = "default_min_cmake_version")]
+    pub min_cmake_version: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostBuildConfig {
+    #[serde(default)]
+    pub commands: Vec<Vec<String>>,
+    #[serde(default)]
+    pub shell: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecConfig {
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub env_clear: bool,
+    #[serde(default)]
+    pub args: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub metric_groups: Vec<String>,
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorConfig {
+    #[serde(default)]
+    pub memory_cap_mb: Option<f64>,
+    #[serde(default = "default_single_thread_min_secs")]
+    pub single_thread_min_secs: f64,
+    #[serde(default)]
+    pub alert_memory_mb: Option<f64>,
+    #[serde(default)]
+    pub alert_cpu_pct: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchConfig {
+    #[serde(default = "default_watch_debounce_ms")]
+    pub debounce_ms: u64,
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+impl WatchConfig {
+    pub fn debounce(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.debounce_ms)
+    }
 }
 
 impl Default for Config {
@@ -100,6 +287,30 @@ impl Default for Config {
             keybindings: KeybindingsConfig::default(),
             build: BuildConfig::default(),
             stages: StagesConfig::default(),
+            monitor: MonitorConfig::default(),
+            exec: ExecConfig::default(),
+            watch: WatchConfig::default(),
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: default_watch_debounce_ms(),
+            ignore: Vec::new(),
+        }
+    }
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            memory_cap_mb: None,
+            single_thread_min_secs: default_single_thread_min_secs(),
+            alert_memory_mb: None,
+            alert_cpu_pct: None,
         }
     }
 }
@@ -109,6 +320,8 @@ impl Default for HistoryConfig {
         Self {
             max_builds: default_max_builds(),
             storage_path: default_storage_path(),
+            max_age_days: None,
+            regression_threshold_pct: default_regression_threshold_pct(),
         }
     }
 }
@@ -118,6 +331,9 @@ impl Default for LogsConfig {
         Self {
             auto_save: false,
             save_directory: default_save_directory(),
+            auto_save_perf: false,
+            max_file_size_kb: default_max_log_file_kb(),
+            format: default_log_format(),
         }
     }
 }
@@ -137,6 +353,19 @@ impl Default for DisplayConfig {
             max_log_lines: default_max_log_lines(),
             show_timestamps: true,
             theme: default_theme(),
+            keep_previous_logs: false,
+            relative_numbers: false,
+            slowest_steps: default_slowest_steps(),
+            trend_builds: default_trend_builds(),
+            tabs: default_tabs(),
+            highlight_stderr: true,
+            collapse_duplicates: false,
+            slow_step_secs: default_slow_step_secs(),
+            refresh_ms: default_refresh_ms(),
+            adaptive_refresh: true,
+            notify_on_complete: false,
+            confirm_quit: true,
+            show_minimap: false,
         }
     }
 }
@@ -158,6 +387,21 @@ impl Default for BuildConfig {
         Self {
             kind: None,
             name: None,
+            parallel_targets: false,
+            max_parallel_targets: default_max_parallel_targets(),
+            jobs: None,
+            generator: default_generator(),
+            cmake_flags: BTreeMap::new(),
+            presets: HashMap::new(),
+            toolchain_file: None,
+            skip_exec_check: false,
+            step_timeout_secs: None,
+            env: HashMap::new(),
+            env_clear: false,
+            retries: 0,
+            retry_on_patterns: Vec::new(),
+            smart_configure: false,
+            confirm_clean_over_mb: None,
         }
     }
 }
@@ -168,20 +412,128 @@ impl Default for StagesConfig {
             enabled_stages: default_enabled_stages(),
             auto_build_on_start: default_auto_build_on_start(),
             stage_dependencies: HashMap::new(),
+            post_build: PostBuildConfig::default(),
+            min_cmake_version: default_min_cmake_version(),
+        }
+    }
+}
+
+impl StagesConfig {
+    pub fn is_enabled(&self, stage: Stage) -> bool {
+        if !stage.metadata().is_optional {
+            return true;
+        }
+
+        let name = stage.to_string();
+        self.enabled_stages
+            .iter()
+            .any(|enabled| enabled.eq_ignore_ascii_case(&name))
+    }
+
+    pub fn filter_enabled(&self, stages: Vec<Stage>) -> Vec<Stage> {
+        stages
+            .into_iter()
+            .filter(|stage| self.is_enabled(*stage))
+            .collect()
+    }
+}
+
+impl ExecConfig {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn process_env(&self) -> ProcessEnv {
+        ProcessEnv::new(&self.env, self.env_clear)
+    }
+}
+
+impl LogsConfig {
+    pub fn log_format(&self) -> Result<LogFormat, ConfigError> {
+        match self.format.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "jsonl" => Ok(LogFormat::Jsonl),
+            _ => Err(ConfigError::new(
+                "logs.format",
+                format!(
+                    "unrecognized format '{}' (expected text or jsonl)",
+                    self.format
+                ),
+            )),
         }
     }
 }
 
 impl BuildConfig {
-    pub fn target_kind(&self) -> TargetKind {
-        match self.kind.as_deref() {
-            Some("installer") => TargetKind::Installer,
-            Some("root") => TargetKind::Root,
-            Some("executable") => TargetKind::Executable,
-            Some(v) => panic!("Unrecognized build kind: {}", v),
-            _ => panic!("Missing build kind: \"installer\"|\"root\"|\"executable\""),
+    pub fn target_kind(&self) -> Result<TargetKind, ConfigError> {
+        match self.kind.as_deref().map(str::to_lowercase).as_deref() {
+            Some("installer") => Ok(TargetKind::Installer),
+            Some("root") => Ok(TargetKind::Root),
+            Some("executable") => Ok(TargetKind::Executable),
+            Some(_) => Err(ConfigError::new(
+                "build.kind",
+                format!(
+                    "unrecognized kind '{}' (expected root, executable or installer)",
+                    self.kind.as_deref().unwrap_or_default()
+                ),
+            )),
+            None => Err(ConfigError::new(
+                "build.kind",
+                "missing (expected root, executable or installer)",
+            )),
         }
     }
+
+    pub fn step_timeout(&self) -> Option<Duration> {
+        self.step_timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn process_env(&self) -> ProcessEnv {
+        ProcessEnv::new(&self.env, self.env_clear)
+    }
+
+    pub fn retry_policy(&self) -> anyhow::Result<RetryPolicy> {
+        RetryPolicy::new(self.retries, &self.retry_on_patterns)
+    }
+
+    pub fn toolchain_path(&self) -> Option<PathBuf> {
+        self.toolchain_file.as_deref().map(Config::expand_path)
+    }
+
+    pub fn cmake_flags_for(&self, preset: &str) -> BTreeMap<String, String> {
+        let mut flags = self.cmake_flags.clone();
+        if let Some(overrides) = self.presets.get(preset) {
+            flags.extend(overrides.cmake_flags.clone());
+        }
+
+        expand_flags(flags)
+    }
+}
+
+// A single shellexpand pass, so a variable whose value itself contains `$VAR`
+// is not expanded again. Unset or empty results keep the original text.
+fn resolve_env_in(value: &mut String) {
+    if !value.contains('$') {
+        return;
+    }
+
+    if let Ok(expanded) = shellexpand::env(value) {
+        if !expanded.trim().is_empty() {
+            *value = expanded.into_owned();
+        }
+    }
+}
+
+fn expand_flags(flags: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    flags
+        .into_iter()
+        .map(|(key, value)| {
+            let expanded = shellexpand::full(&value)
+                .map(|v| v.to_string())
+                .unwrap_or(value);
+            (key, expanded)
+        })
+        .collect()
 }
 
 fn default_max_builds() -> usize {
@@ -196,6 +548,14 @@ fn default_save_directory() -> String {
     "~/.cache/astralix/logs".to_string()
 }
 
+fn default_max_log_file_kb() -> u64 {
+    1024
+}
+
+fn default_log_format() -> String {
+    "text".to_string()
+}
+
 fn default_editor_command() -> String {
     "${EDITOR}".to_string()
 }
@@ -204,6 +564,34 @@ fn default_max_log_lines() -> usize {
     10000
 }
 
+fn default_slow_step_secs() -> u64 {
+    60
+}
+
+fn default_refresh_ms() -> u64 {
+    100
+}
+
+fn default_slowest_steps() -> usize {
+    10
+}
+
+fn default_regression_threshold_pct() -> f64 {
+    20.0
+}
+
+fn default_single_thread_min_secs() -> f64 {
+    10.0
+}
+
+fn default_watch_debounce_ms() -> u64 {
+    500
+}
+
+fn default_trend_builds() -> usize {
+    30
+}
+
 fn default_theme() -> String {
     "dark".to_string()
 }
@@ -229,10 +617,35 @@ fn default_enabled_stages() -> Vec<String> {
         "PreValidation".to_string(),
         "Configure".to_string(),
         "Build".to_string(),
+        "Test".to_string(),
         "Install".to_string(),
+        "PostBuild".to_string(),
+    ]
+}
+
+fn default_max_parallel_targets() -> usize {
+    2
+}
+
+fn default_generator() -> String {
+    "ninja".to_string()
+}
+
+fn default_tabs() -> Vec<String> {
+    vec![
+        "console".to_string(),
+        "summary".to_string(),
+        "performance".to_string(),
+        "warnings".to_string(),
+        "history".to_string(),
+        "errors".to_string(),
     ]
 }
 
+fn default_min_cmake_version() -> String {
+    "3.19".to_string()
+}
+
 fn default_auto_build_on_start() -> bool {
     false
 }
@@ -240,19 +653,28 @@ fn default_auto_build_on_start() -> bool {
 impl Config {
     pub fn load_from_cli(cli: &Cli) -> Result<(Target, Vec<Target>), anyhow::Error> {
         let source_dir = cli.source_directory();
-        let root = Config::find_config(source_dir.clone());
+        let mut root = Config::find_config(source_dir.clone())?;
         let mut targets = Vec::new();
 
-        if root.config.build.target_kind() != TargetKind::Root {
+        if let Some(toolchain) = &cli.toolchain {
+            root.config.build.toolchain_file = Some(toolchain.display().to_string());
+        }
+
+        if cli.metrics_port.is_some() {
+            root.config.exec.metrics_port = cli.metrics_port;
+        }
+
+        let root_kind = root.config.build.target_kind()?;
+        if root_kind != TargetKind::Root {
             let root_as_target = Target {
                 path: root.path.clone(),
-                kind: root.config.build.target_kind(),
+                kind: root_kind,
                 config: root.config.clone(),
             };
             targets.push(root_as_target);
         }
 
-        targets.extend(Config::find_targets_configs(&source_dir));
+        targets.extend(Config::find_targets_configs(&source_dir)?);
 
         targets.sort_by_key(|target| match target.kind {
             TargetKind::Executable => 0,
@@ -274,12 +696,240 @@ impl Config {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-        let config: Config = toml::from_str(&content)
+        let mut config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        config.resolve_env();
 
         Ok(config)
     }
 
+    /// Names from `[presets]` and `[build.presets]`, sorted. Empty when no
+    /// `[presets]` section exists, since cache-flag overrides alone do not
+    /// restrict which presets can be selected.
+    pub fn preset_names(&self) -> Vec<String> {
+        if self.presets.is_empty() {
+            return Vec::new();
+        }
+
+        self.presets
+            .keys()
+            .chain(self.build.presets.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn default_preset(&self) -> String {
+        let names = self.preset_names();
+        if names.is_empty() || names.iter().any(|name| name == "debug") {
+            return "debug".to_string();
+        }
+
+        names.into_iter().next().unwrap_or_default()
+    }
+
+    pub fn check_preset(&self, name: &str) -> anyhow::Result<()> {
+        let names = self.preset_names();
+        if names.is_empty() || names.iter().any(|configured| configured == name) {
+            return Ok(());
+        }
+
+        let configured: Vec<&str> = names.iter().map(String::as_str).collect();
+        anyhow::bail!(
+            "Unknown preset '{}' (configured: {})",
+            name,
+            configured.join(", ")
+        )
+    }
+
+    pub fn cmake_flags_for(&self, preset: &str) -> BTreeMap<String, String> {
+        let mut flags = self.build.cmake_flags_for(preset);
+        let Some(section) = self.presets.get(preset) else {
+            return flags;
+        };
+
+        if !section.flags.is_empty() {
+            let joined = section.flags.join(" ");
+            flags.insert("CMAKE_C_FLAGS".to_string(), joined.clone());
+            flags.insert("CMAKE_CXX_FLAGS".to_string(), joined);
+        }
+        flags.extend(expand_flags(section.cmake_flags.clone()));
+
+        flags
+    }
+
+    pub fn generator_for(&self, preset: &str) -> &str {
+        self.presets
+            .get(preset)
+            .and_then(|section| section.generator.as_deref())
+            .unwrap_or(&self.build.generator)
+    }
+
+    pub fn jobs_for(&self, preset: &str) -> Option<usize> {
+        self.presets
+            .get(preset)
+            .and_then(|section| section.jobs)
+            .or(self.build.jobs)
+    }
+
+    pub fn build_env_for(&self, preset: &str) -> ProcessEnv {
+        let mut env = self.build.env.clone();
+        if let Some(section) = self.presets.get(preset) {
+            env.extend(section.env.clone());
+        }
+
+        ProcessEnv::new(&env, self.build.env_clear)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        if let Err(e) = self.build.target_kind() {
+            errors.push(e);
+        }
+        if let Err(e) = self.logs.log_format() {
+            errors.push(e);
+        }
+
+        for (i, name) in self.stages.enabled_stages.iter().enumerate() {
+            if Stage::from_name(name).is_none() {
+                errors.push(ConfigError::new(
+                    &format!("stages.enabled_stages[{}]", i),
+                    format!("unknown stage '{}'", name),
+                ));
+            }
+        }
+
+        let mut dependencies: Vec<_> = self.stages.stage_dependencies.iter().collect();
+        dependencies.sort_by(|a, b| a.0.cmp(b.0));
+        for (stage, deps) in dependencies {
+            for name in std::iter::once(stage).chain(deps) {
+                if Stage::from_name(name).is_none() {
+                    errors.push(ConfigError::new(
+                        &format!("stages.stage_dependencies.{}", stage),
+                        format!("unknown stage '{}'", name),
+                    ));
+                }
+            }
+        }
+
+        if KeyPress::from_string(&self.keybindings.leader_key).is_none() {
+            errors.push(ConfigError::new(
+                "keybindings.leader_key",
+                format!(
+                    "cannot parse '{}' (use a single character, Space, Backslash, Enter or Esc)",
+                    self.keybindings.leader_key
+                ),
+            ));
+        }
+
+        let positive = [
+            ("history.max_builds", Some(self.history.max_builds as u64)),
+            (
+                "display.max_log_lines",
+                Some(self.display.max_log_lines as u64),
+            ),
+            (
+                "display.trend_builds",
+                Some(self.display.trend_builds as u64),
+            ),
+            ("display.slow_step_secs", Some(self.display.slow_step_secs)),
+            ("display.refresh_ms", Some(self.display.refresh_ms)),
+            (
+                "keybindings.sequence_timeout_ms",
+                Some(self.keybindings.sequence_timeout_ms),
+            ),
+            (
+                "build.max_parallel_targets",
+                Some(self.build.max_parallel_targets as u64),
+            ),
+            ("build.jobs", self.build.jobs.map(|jobs| jobs as u64)),
+            ("build.step_timeout_secs", self.build.step_timeout_secs),
+            ("exec.timeout_secs", self.exec.timeout_secs),
+            ("logs.max_file_size_kb", Some(self.logs.max_file_size_kb)),
+        ];
+        for (field, value) in positive {
+            if value == Some(0) {
+                errors.push(ConfigError::new(field, "must be greater than 0"));
+            }
+        }
+
+        if Generator::from_name(&self.build.generator).is_none() {
+            errors.push(ConfigError::new(
+                "build.generator",
+                format!(
+                    "unknown generator '{}' (expected ninja, make or xcode)",
+                    self.build.generator
+                ),
+            ));
+        }
+
+        for (name, section) in &self.presets {
+            if let Some(generator) = &section.generator {
+                if Generator::from_name(generator).is_none() {
+                    errors.push(ConfigError::new(
+                        &format!("presets.{}.generator", name),
+                        format!(
+                            "unknown generator '{}' (expected ninja, make or xcode)",
+                            generator
+                        ),
+                    ));
+                }
+            }
+            if section.jobs == Some(0) {
+                errors.push(ConfigError::new(
+                    &format!("presets.{}.jobs", name),
+                    "must be greater than 0",
+                ));
+            }
+        }
+
+        for (i, pattern) in self.build.retry_on_patterns.iter().enumerate() {
+            if regex::Regex::new(pattern).is_err() {
+                errors.push(ConfigError::new(
+                    &format!("build.retry_on_patterns[{}]", i),
+                    format!("invalid regex '{}'", pattern),
+                ));
+            }
+        }
+
+        for (i, pattern) in self.watch.ignore.iter().enumerate() {
+            if globset::Glob::new(pattern).is_err() {
+                errors.push(ConfigError::new(
+                    &format!("watch.ignore[{}]", i),
+                    format!("invalid glob '{}'", pattern),
+                ));
+            }
+        }
+
+        let threshold = self.history.regression_threshold_pct;
+        if threshold.is_nan() || threshold <= 0.0 {
+            errors.push(ConfigError::new(
+                "history.regression_threshold_pct",
+                "must be greater than 0",
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn resolve_env(&mut self) {
+        resolve_env_in(&mut self.history.storage_path);
+        resolve_env_in(&mut self.logs.save_directory);
+        resolve_env_in(&mut self.editor.command);
+        if let Some(toolchain) = self.build.toolchain_file.as_mut() {
+            resolve_env_in(toolchain);
+        }
+        for command in &mut self.stages.post_build.commands {
+            command.iter_mut().for_each(resolve_env_in);
+        }
+    }
+
     pub fn merge_with(mut self, other: Config) -> Self {
         if !other.keybindings.leader_bindings.is_empty() {
             self.keybindings
@@ -312,10 +962,14 @@ impl Config {
         Self::expand_path(&self.logs.save_directory)
     }
 
-    pub fn find_targets_configs(root_dir: &Path) -> Vec<Target> {
+    pub fn find_targets_configs(root_dir: &Path) -> anyhow::Result<Vec<Target>> {
         let mut targets: Vec<Target> = Vec::new();
 
-        fn search_recursive(dir: &Path, targets: &mut Vec<Target>, root_config: &Path) {
+        fn search_recursive(
+            dir: &Path,
+            targets: &mut Vec<Target>,
+            root_config: &Path,
+        ) -> anyhow::Result<()> {
             if let Ok(entries) = std::fs::read_dir(dir) {
                 for entry in entries.flatten() {
                     let path = entry.path();
@@ -323,23 +977,33 @@ impl Config {
                         let config_path = path.join("ignis.toml");
 
                         if config_path.exists() && config_path != root_config {
-                            let config = Config::load_from_file(&config_path).unwrap_or_default();
+                            let config = Config::load_validated(&config_path)?;
                             targets.push(Target {
                                 path: path.clone(),
-                                kind: config.build.target_kind(),
+                                kind: config.build.target_kind()?,
                                 config,
                             });
                         }
 
-                        search_recursive(&path, targets, root_config);
+                        search_recursive(&path, targets, root_config)?;
                     }
                 }
             }
+            Ok(())
         }
 
         let root_config = root_dir.join("ignis.toml");
-        search_recursive(root_dir, &mut targets, &root_config);
-        targets
+        search_recursive(root_dir, &mut targets, &root_config)?;
+        Ok(targets)
+    }
+
+    fn load_validated(path: &Path) -> anyhow::Result<Config> {
+        let config = Config::load_from_file(path)?;
+        config
+            .validate()
+            .map_err(validation_error)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+        Ok(config)
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
@@ -351,22 +1015,243 @@ impl Config {
         Ok(())
     }
 
-    fn find_config(root_path: std::path::PathBuf) -> Target {
-        let root_config =
-            Config::load_from_file(root_path.join("ignis.toml")).unwrap_or_else(|_| {
-                panic!(
-                    "No ignis.toml found in {} or any parent directory.\n\
-                Run 'ignis init' to create a new configuration.",
-                    root_path.display()
-                )
-            });
+    fn find_config(root_path: std::path::PathBuf) -> anyhow::Result<Target> {
+        let config_path = root_path.join("ignis.toml");
+        if !config_path.exists() {
+            anyhow::bail!(
+                "No ignis.toml found in {}.\nRun 'ignis init' to create a new configuration.",
+                root_path.display()
+            );
+        }
 
+        let root_config = Config::load_validated(&config_path)?;
         let global_config = Config::load_from_file(Config::global_path()).unwrap_or_default();
 
-        Target {
+        Ok(Target {
             path: root_path,
             kind: TargetKind::Root,
             config: root_config.merge_with(global_config),
-        }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> Config {
+        let mut config = Config::default();
+        config.build.kind = Some("root".to_string());
+        config
+    }
+
+    fn fields(config: &Config) -> Vec<String> {
+        config
+            .validate()
+            .unwrap_err()
+            .into_iter()
+            .map(|error| error.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_default_config_with_kind_is_valid() {
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_build_kind_is_parsed_case_insensitively() {
+        let mut config = valid_config();
+        config.build.kind = Some("Executable".to_string());
+        assert_eq!(config.build.target_kind(), Ok(TargetKind::Executable));
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_missing_build_kind() {
+        let config = Config::default();
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "build.kind");
+        assert!(errors[0].message.starts_with("missing"));
+    }
+
+    #[test]
+    fn test_unrecognized_build_kind() {
+        let mut config = valid_config();
+        config.build.kind = Some("library".to_string());
+        let errors = config.validate().unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "build.kind: unrecognized kind 'library' \
+            (expected root, executable or installer)"
+        );
+    }
+
+    #[test]
+    fn test_unknown_stage_names() {
+        let mut config = valid_config();
+        config.stages.enabled_stages = vec!["Build".to_string(), "Deploy".to_string()];
+        config
+            .stages
+            .stage_dependencies
+            .insert("Test".to_string(), vec!["Lint".to_string()]);
+
+        assert_eq!(
+            fields(&config),
+            vec!["stages.enabled_stages[1]", "stages.stage_dependencies.Test"]
+        );
+    }
+
+    #[test]
+    fn test_unparseable_leader_key() {
+        let mut config = valid_config();
+        config.keybindings.leader_key = "Ctrl+Space".to_string();
+        assert_eq!(fields(&config), vec!["keybindings.leader_key"]);
+    }
+
+    #[test]
+    fn test_non_positive_numeric_fields() {
+        let mut config = valid_config();
+        config.history.max_builds = 0;
+        config.display.max_log_lines = 0;
+        config.build.jobs = Some(0);
+        config.build.step_timeout_secs = Some(0);
+        config.history.regression_threshold_pct = -5.0;
+
+        assert_eq!(
+            fields(&config),
+            vec![
+                "history.max_builds",
+                "display.max_log_lines",
+                "build.jobs",
+                "build.step_timeout_secs",
+                "history.regression_threshold_pct",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_env_expands_paths_and_commands() {
+        std::env::set_var("IGNIS_TEST_DATA_HOME", "/home/dev/.local/share");
+        std::env::set_var("IGNIS_TEST_NESTED", "$IGNIS_TEST_DATA_HOME");
+        std::env::set_var("IGNIS_TEST_EMPTY", "");
+
+        let mut config = valid_config();
+        config.history.storage_path = "$IGNIS_TEST_DATA_HOME/ignis/history.json".to_string();
+        config.logs.save_directory = "${IGNIS_TEST_NESTED}/logs".to_string();
+        config.editor.command = "${IGNIS_TEST_EMPTY}".to_string();
+        config.build.toolchain_file = Some("${IGNIS_TEST_UNSET_VAR}/arm.cmake".to_string());
+        config.stages.post_build.commands = vec![vec![
+            "cp".to_string(),
+            "${IGNIS_TEST_DATA_HOME}/a".to_string(),
+        ]];
+
+        config.resolve_env();
+
+        assert_eq!(
+            config.history.storage_path,
+            "/home/dev/.local/share/ignis/history.json"
+        );
+        assert_eq!(config.logs.save_directory, "$IGNIS_TEST_DATA_HOME/logs");
+        assert_eq!(config.editor.command, "${IGNIS_TEST_EMPTY}");
+        assert_eq!(
+            config.build.toolchain_file.as_deref(),
+            Some("${IGNIS_TEST_UNSET_VAR}/arm.cmake")
+        );
+        assert_eq!(
+            config.stages.post_build.commands[0][1],
+            "/home/dev/.local/share/a"
+        );
+        assert_eq!(
+            config.storage_path(),
+            PathBuf::from("/home/dev/.local/share/ignis/history.json")
+        );
+    }
+
+    #[test]
+    fn test_invalid_preset_section() {
+        let mut config = valid_config();
+        let perf = PresetConfig {
+            generator: Some("bazel".to_string()),
+            jobs: Some(0),
+            ..Default::default()
+        };
+        config.presets.insert("perf".to_string(), perf);
+
+        assert_eq!(
+            fields(&config),
+            vec!["presets.perf.generator", "presets.perf.jobs"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_preset_lists_configured_ones() {
+        let mut config = valid_config();
+        assert!(config.check_preset("anything").is_ok());
+        assert_eq!(config.default_preset(), "debug");
+
+        config
+            .presets
+            .insert("release".to_string(), PresetConfig::default());
+        config
+            .presets
+            .insert("perf".to_string(), PresetConfig::default());
+        assert!(config.check_preset("perf").is_ok());
+        assert_eq!(config.default_preset(), "perf");
+        assert_eq!(
+            config.check_preset("debug").unwrap_err().to_string(),
+            "Unknown preset 'debug' (configured: perf, release)"
+        );
+    }
+
+    #[test]
+    fn test_build_presets_count_as_configured() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [build.presets.ci.cmake_flags]
+            WARNINGS_AS_ERRORS = "ON"
+
+            [presets.perf]
+            jobs = 16
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.check_preset("ci").is_ok());
+        assert_eq!(config.preset_names(), vec!["ci", "perf"]);
+        assert_eq!(config.cmake_flags_for("ci")["WARNINGS_AS_ERRORS"], "ON");
+        assert_eq!(
+            config.check_preset("debug").unwrap_err().to_string(),
+            "Unknown preset 'debug' (configured: ci, perf)"
+        );
+
+        config.presets.clear();
+        assert!(config.preset_names().is_empty());
+        assert!(config.check_preset("debug").is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem_at_once() {
+        let mut config = Config::default();
+        config.stages.enabled_stages.push("Deploy".to_string());
+        config.keybindings.leader_key = "".to_string();
+        config.exec.timeout_secs = Some(0);
+        config.build.retry_on_patterns = vec!["timed out".to_string(), "(".to_string()];
+        config.build.generator = "bazel".to_string();
+        config.watch.ignore = vec!["build/**".to_string(), "src/[".to_string()];
+
+        assert_eq!(
+            fields(&config),
+            vec![
+                "build.kind",
+                "stages.enabled_stages[6]",
+                "keybindings.leader_key",
+                "exec.timeout_secs",
+                "build.generator",
+                "build.retry_on_patterns[1]",
+                "watch.ignore[1]",
+            ]
+        );
     }
 }
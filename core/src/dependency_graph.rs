@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use crate::config::StagesConfig;
 use crate::stage::Stage;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GraphError {
     CircularDependency(Vec<Stage>),
-    StageNotFound(Stage),
+    StageNotFound(String),
 }
 
 impl std::fmt::Display for GraphError {
@@ -62,6 +63,31 @@ impl StageDependencyGraph {
         graph
     }
 
+    pub fn from_config(stages: Vec<Stage>, config: &StagesConfig) -> Result<Self, GraphError> {
+        let mut graph = Self::from_stages(stages);
+
+        let parse = |name: &str| {
+            Stage::from_name(name).ok_or_else(|| GraphError::StageNotFound(name.to_string()))
+        };
+
+        for (stage, deps) in &config.stage_dependencies {
+            let stage = parse(stage)?;
+            for dep in deps {
+                let dep = parse(dep)?;
+
+                if !graph.stages.contains(&stage) || !graph.stages.contains(&dep) {
+                    continue;
+                }
+                if graph.adjacency.get(&dep).is_some_and(|to| to.contains(&stage)) {
+                    continue;
+                }
+                graph.add_dependency(dep, stage);
+            }
+        }
+
+        Ok(graph)
+    }
+
     pub fn add_stage(&mut self, stage: Stage) {
         self.stages.insert(stage);
         self.adjacency.entry(stage).or_insert_with(Vec::new);
@@ -117,17 +143,62 @@ impl StageDependencyGraph {
         }
 
         if processed != self.stages.len() {
-            let remaining: Vec<Stage> = self.stages
+            let remaining: HashSet<Stage> = self.stages
                 .iter()
-                .filter(|s| in_degree.get(s).map_or(false, |&d| d > 0))
+                .filter(|s| in_degree.get(s).is_some_and(|&d| d > 0))
                 .copied()
                 .collect();
-            return Err(GraphError::CircularDependency(remaining));
+            return Err(GraphError::CircularDependency(self.find_cycle(&remaining)));
         }
 
         Ok(layers)
     }
 
+    fn find_cycle(&self, remaining: &HashSet<Stage>) -> Vec<Stage> {
+        let mut visited = HashSet::new();
+
+        for start in Stage::all().into_iter().filter(|s| remaining.contains(s)) {
+            let mut path = Vec::new();
+            if let Some(cycle) = self.cycle_from(start, remaining, &mut visited, &mut path) {
+                return cycle;
+            }
+        }
+
+        Stage::all()
+            .into_iter()
+            .filter(|s| remaining.contains(s))
+            .collect()
+    }
+
+    fn cycle_from(
+        &self,
+        stage: Stage,
+        remaining: &HashSet<Stage>,
+        visited: &mut HashSet<Stage>,
+        path: &mut Vec<Stage>,
+    ) -> Option<Vec<Stage>> {
+        if let Some(pos) = path.iter().position(|s| *s == stage) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(stage);
+            return Some(cycle);
+        }
+        if !visited.insert(stage) {
+            return None;
+        }
+
+        path.push(stage);
+        for &next in self.adjacency.get(&stage).into_iter().flatten() {
+            if remaining.contains(&next) {
+                if let Some(cycle) = self.cycle_from(next, remaining, visited, path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+
+        None
+    }
+
     pub fn stages(&self) -> Vec<Stage> {
         self.stages.iter().copied().collect()
     }
@@ -186,6 +257,60 @@ mod tests {
         assert!(layers[1].contains(&Stage::PostBuild));
     }
 
+    fn stages_config(deps: &[(&str, &[&str])]) -> StagesConfig {
+        StagesConfig {
+            stage_dependencies: deps
+                .iter()
+                .map(|(stage, deps)| {
+                    (
+                        stage.to_string(),
+                        deps.iter().map(|d| d.to_string()).collect(),
+                    )
+                })
+                .collect(),
+            ..StagesConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_from_config_adds_edges() {
+        let stages = vec![Stage::Build, Stage::Test, Stage::Install];
+
+        let layers = StageDependencyGraph::from_stages(stages.clone())
+            .topological_sort()
+            .unwrap();
+        assert_eq!(layers.len(), 2);
+
+        let config = stages_config(&[("Install", &["test", "Exec"])]);
+        let graph = StageDependencyGraph::from_config(stages, &config).unwrap();
+        let layers = graph.topological_sort().unwrap();
+        assert_eq!(
+            layers,
+            vec![vec![Stage::Build], vec![Stage::Test], vec![Stage::Install]]
+        );
+    }
+
+    #[test]
+    fn test_from_config_rejects_unknown_stage() {
+        let config = stages_config(&[("Install", &["Lint"])]);
+        let result = StageDependencyGraph::from_config(vec![Stage::Install], &config);
+        assert_eq!(
+            result.err(),
+            Some(GraphError::StageNotFound("Lint".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_config_detects_cycles() {
+        let config = stages_config(&[("Build", &["Install"])]);
+        let graph =
+            StageDependencyGraph::from_config(vec![Stage::Build, Stage::Install], &config).unwrap();
+        assert!(matches!(
+            graph.topological_sort(),
+            Err(GraphError::CircularDependency(_))
+        ));
+    }
+
     #[test]
     fn test_circular_dependency() {
         let mut graph = StageDependencyGraph::new();
@@ -198,12 +323,39 @@ mod tests {
         let result = graph.topological_sort();
         assert!(result.is_err());
 
-        if let Err(GraphError::CircularDependency(_)) = result {
+        if let Err(GraphError::CircularDependency(cycle)) = result {
+            assert_eq!(cycle, vec![Stage::Configure, Stage::Build, Stage::Configure]);
+            assert_eq!(
+                GraphError::CircularDependency(cycle).to_string(),
+                "Circular dependency detected: Configure -> Build -> Configure"
+            );
         } else {
             panic!("Expected CircularDependency error");
         }
     }
 
+    #[test]
+    fn test_cycle_excludes_downstream_stages() {
+        let mut graph = StageDependencyGraph::new();
+        graph.add_dependency(Stage::PreValidation, Stage::Configure);
+        graph.add_dependency(Stage::Configure, Stage::Build);
+        graph.add_dependency(Stage::Build, Stage::Test);
+        graph.add_dependency(Stage::Test, Stage::Configure);
+        graph.add_dependency(Stage::Build, Stage::Install);
+
+        match graph.topological_sort() {
+            Err(GraphError::CircularDependency(cycle)) => {
+                assert_eq!(cycle.first(), cycle.last());
+                assert_eq!(cycle.len(), 4);
+                for pair in cycle.windows(2) {
+                    assert!(graph.adjacency[&pair[0]].contains(&pair[1]));
+                }
+                assert!(!cycle.contains(&Stage::Install));
+            }
+            other => panic!("Expected CircularDependency error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_empty_graph() {
         let graph = StageDependencyGraph::new();
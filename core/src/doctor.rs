@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::command::Generator;
+use crate::config::Config;
+use crate::editor::resolve_editor_command;
+use crate::prevalidation::{find_in_path, parse_version, version_at_least};
+
+const COMPILERS: [&str; 4] = ["c++", "g++", "clang++", "cl"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub passed: bool,
+    pub critical: bool,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            passed: true,
+            critical: true,
+            detail,
+            hint: None,
+        }
+    }
+
+    fn fail(name: &str, detail: String, hint: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            passed: false,
+            critical: true,
+            detail,
+            hint: Some(hint.to_string()),
+        }
+    }
+
+    fn optional(mut self) -> Self {
+        self.critical = false;
+        self
+    }
+}
+
+fn tool_version(program: &Path) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = parse_version(stdout.lines().next()?)?;
+
+    Some(
+        version
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+fn describe(program: &Path) -> String {
+    match tool_version(program) {
+        Some(version) => format!("{} ({})", version, program.display()),
+        None => program.display().to_string(),
+    }
+}
+
+fn check_cmake(min_version: &str) -> DoctorCheck {
+    let Some(path) = find_in_path("cmake") else {
+        return DoctorCheck::fail(
+            "cmake",
+            "not found on PATH".to_string(),
+            "Install CMake from your package manager or https://cmake.org/download",
+        );
+    };
+
+    let too_old = tool_version(&path)
+        .and_then(|version| parse_version(&version))
+        .zip(parse_version(min_version))
+        .is_some_and(|(actual, minimum)| !version_at_least(&actual, &minimum));
+
+    if too_old {
+        DoctorCheck::fail(
+            "cmake",
+            format!("{} is older than {}", describe(&path), min_version),
+            "Upgrade CMake or lower stages.min_cmake_version",
+        )
+    } else {
+        DoctorCheck::pass("cmake", describe(&path))
+    }
+}
+
+fn check_generator(name: &str) -> DoctorCheck {
+    let Some(generator) = Generator::from_name(name) else {
+        return DoctorCheck::fail(
+            "generator",
+            format!("unknown build.generator '{}'", name),
+            "Set build.generator to ninja, make or xcode",
+        );
+    };
+
+    match find_in_path(generator.program()) {
+        Some(path) => DoctorCheck::pass(generator.program(), describe(&path)),
+        None => DoctorCheck::fail(
+            generator.program(),
+            "not found on PATH".to_string(),
+            "Install the build tool or change build.generator",
+        ),
+    }
+}
+
+fn check_compiler() -> DoctorCheck {
+    let from_env = std::env::var("CXX").ok().map(PathBuf::from);
+    let found = match &from_env {
+        Some(cxx) if cxx.is_absolute() => Some(cxx.clone()).filter(|p| p.is_file()),
+        Some(cxx) => find_in_path(&cxx.display().to_string()),
+        None => COMPILERS.iter().find_map(|compiler| find_in_path(compiler)),
+    };
+
+    match found {
+        Some(path) => DoctorCheck::pass("compiler", describe(&path)),
+        None => DoctorCheck::fail(
+            "compiler",
+            match from_env {
+                Some(cxx) => format!("CXX={} not found", cxx.display()),
+                None => "no C++ compiler found on PATH".to_string(),
+            },
+            "Install gcc or clang, or point CXX at your compiler",
+        ),
+    }
+}
+
+fn check_git() -> DoctorCheck {
+    match find_in_path("git") {
+        Some(path) => DoctorCheck::pass("git", describe(&path)),
+        None => DoctorCheck::fail(
+            "git",
+            "not found on PATH".to_string(),
+            "Install git to record commits in build history",
+        ),
+    }
+    .optional()
+}
+
+fn check_editor(command: &str) -> DoctorCheck {
+    let resolved = resolve_editor_command(command);
+    let program = resolved.split_whitespace().next().unwrap_or_default();
+    let path = PathBuf::from(program);
+    let found = if path.is_absolute() {
+        Some(path).filter(|p| p.is_file())
+    } else {
+        find_in_path(program)
+    };
+
+    match found {
+        Some(path) => DoctorCheck::pass("editor", path.display().to_string()),
+        None => DoctorCheck::fail(
+            "editor",
+            format!("'{}' not found", resolved),
+            "Set editor.command or the EDITOR environment variable",
+        ),
+    }
+    .optional()
+}
+
+pub fn validate_config(config: &Config) -> Vec<String> {
+    match config.validate() {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn check_config(source_dir: &Path) -> (DoctorCheck, Option<Config>) {
+    let path = source_dir.join("ignis.toml");
+    if !path.is_file() {
+        let check = DoctorCheck::fail(
+            "config",
+            format!("no ignis.toml in {}", source_dir.display()),
+            "Run 'ignis init' to create one",
+        );
+        return (check, None);
+    }
+
+    let config = match Config::load_from_file(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            let check = DoctorCheck::fail(
+                "config",
+                format!("{}: {:#}", path.display(), e),
+                "Fix the syntax error in ignis.toml",
+            );
+            return (check, None);
+        }
+    };
+    let config =
+        config.merge_with(Config::load_from_file(Config::global_path()).unwrap_or_default());
+
+    let problems = validate_config(&config);
+    let check = if problems.is_empty() {
+        DoctorCheck::pass("config", path.display().to_string())
+    } else {
+        DoctorCheck::fail(
+            "config",
+            problems.join("; "),
+            "Fix the listed keys in ignis.toml",
+        )
+    };
+
+    (check, Some(config))
+}
+
+pub fn run_checks(source_dir: &Path) -> Vec<DoctorCheck> {
+    let (config_check, config) = check_config(source_dir);
+    let config = config.unwrap_or_default();
+
+    vec![
+        config_check,
+        check_cmake(&config.stages.min_cmake_version),
+        check_generator(&config.build.generator),
+        check_compiler(),
+        check_git(),
+        check_editor(&config.editor.command),
+        DoctorCheck::pass("history", config.storage_path().display().to_string()).optional(),
+    ]
+}
+
+pub fn has_critical_failure(checks: &[DoctorCheck]) -> bool {
+    checks.iter().any(|check| check.critical && !check.passed)
+}
+
+pub fn render_table(checks: &[DoctorCheck]) -> String {
+    let width = checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+
+    for check in checks {
+        let mark = if check.passed { "✓" } else { "✗" };
+        out.push_str(&format!(
+            "{} {:<width$}  {}\n",
+            mark,
+            check.name,
+            check.detail,
+            width = width
+        ));
+        if let (false, Some(hint)) = (check.passed, &check.hint) {
+            out.push_str(&format!("  {:<width$}  → {}\n", "", hint, width = width));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_config_reports_bad_keys() {
+        let mut config = Config::default();
+        config.build.kind = Some("root".to_string());
+        assert!(validate_config(&config).is_empty());
+
+        config.build.generator = "bazel".to_string();
+        config.build.retry_on_patterns = vec!["(".to_string()];
+        config.watch.ignore = vec!["src/[".to_string()];
+
+        let problems = validate_config(&config);
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("bazel"));
+        assert!(problems[1].starts_with("build.retry_on_patterns[0]"));
+        assert!(problems[2].starts_with("watch.ignore[0]"));
+    }
+
+    #[test]
+    fn test_critical_failure_ignores_optional_checks() {
+        let git = DoctorCheck::fail("git", "missing".to_string(), "install").optional();
+        let cmake = DoctorCheck::pass("cmake", "3.28.1".to_string());
+        assert!(!has_critical_failure(&[git.clone(), cmake]));
+
+        let compiler = DoctorCheck::fail("compiler", "missing".to_string(), "install");
+        assert!(has_critical_failure(&[git, compiler]));
+    }
+
+    #[test]
+    fn test_render_table_aligns_and_adds_hints() {
+        let table = render_table(&[
+            DoctorCheck::pass("cmake", "3.28.1".to_string()),
+            DoctorCheck::fail("git", "not found on PATH".to_string(), "Install git"),
+        ]);
+
+        assert_eq!(
+            table,
+            "✓ cmake  3.28.1\n✗ git    not found on PATH\n         → Install git\n"
+        );
+    }
+}
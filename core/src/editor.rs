@@ -3,61 +3,119 @@ use arboard::Clipboard;
 use std::path::Path;
 use std::process::Command;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorKind {
+    VsCode,
+    Neovim,
+    Vim,
+    Emacs,
+    Sublime,
+    Idea,
+    Generic,
+}
+
+impl EditorKind {
+    pub fn from_command(command: &str) -> Self {
+        let program = command.split_whitespace().next().unwrap_or_default();
+        let name = Path::new(program)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match name.as_str() {
+            "code" | "code-insiders" | "codium" | "vscode" => EditorKind::VsCode,
+            "nvim" => EditorKind::Neovim,
+            "vim" | "vi" | "gvim" | "mvim" => EditorKind::Vim,
+            "emacs" | "emacsclient" => EditorKind::Emacs,
+            "subl" | "sublime_text" => EditorKind::Sublime,
+            "idea" | "idea64" | "clion" | "clion64" => EditorKind::Idea,
+            _ => EditorKind::Generic,
+        }
+    }
+
+    pub fn args(&self, file: &str, line: Option<usize>, column: Option<usize>) -> Vec<String> {
+        let file = file.to_string();
+        let Some(line) = line else {
+            return vec![file];
+        };
+
+        match (self, column) {
+            (EditorKind::VsCode, Some(col)) => {
+                vec!["-g".to_string(), format!("{}:{}:{}", file, line, col)]
+            }
+            (EditorKind::VsCode, None) => vec!["-g".to_string(), format!("{}:{}", file, line)],
+            (EditorKind::Neovim | EditorKind::Vim, Some(col)) => {
+                vec![format!("+call cursor({},{})", line, col), file]
+            }
+            (EditorKind::Neovim | EditorKind::Vim, None) => vec![format!("+{}", line), file],
+            (EditorKind::Emacs, Some(col)) => vec![format!("+{}:{}", line, col), file],
+            (EditorKind::Emacs, None) => vec![format!("+{}", line), file],
+            (EditorKind::Sublime, Some(col)) => vec![format!("{}:{}:{}", file, line, col)],
+            (EditorKind::Sublime, None) => vec![format!("{}:{}", file, line)],
+            (EditorKind::Idea, Some(col)) => vec![
+                "--line".to_string(),
+                line.to_string(),
+                "--column".to_string(),
+                col.to_string(),
+                file,
+            ],
+            (EditorKind::Idea, None) => vec!["--line".to_string(), line.to_string(), file],
+            (EditorKind::Generic, _) => vec![file],
+        }
+    }
+}
+
 pub struct Editor {
     editor_command: String,
-    vscode_integration: bool,
+    kind: EditorKind,
 }
 
 impl Editor {
     pub fn new(config_command: String, vscode_integration: bool) -> Self {
         let editor_command = resolve_editor_command(&config_command);
+        let kind = match EditorKind::from_command(&editor_command) {
+            EditorKind::VsCode if !vscode_integration => EditorKind::Generic,
+            kind => kind,
+        };
 
         Self {
             editor_command,
-            vscode_integration,
+            kind,
         }
     }
 
+    pub fn kind(&self) -> EditorKind {
+        self.kind
+    }
+
+    pub fn command_for(
+        &self,
+        file_path: &Path,
+        line: Option<usize>,
+        column: Option<usize>,
+    ) -> Vec<String> {
+        let file = file_path.display().to_string();
+
+        self.editor_command
+            .split_whitespace()
+            .map(String::from)
+            .chain(self.kind.args(&file, line, column))
+            .collect()
+    }
+
     pub fn open_file(
         &self,
         file_path: &Path,
         line: Option<usize>,
         column: Option<usize>,
     ) -> Result<()> {
-        let editor = &self.editor_command;
-
-        let command =
-            if self.vscode_integration && (editor.contains("code") || editor.contains("vscode")) {
-                let location = if let Some(line) = line {
-                    if let Some(col) = column {
-                        format!("{}:{}:{}", file_path.display(), line, col)
-                    } else {
-                        format!("{}:{}", file_path.display(), line)
-                    }
-                } else {
-                    file_path.display().to_string()
-                };
-                vec![editor.clone(), "-g".to_string(), location]
-            } else if editor.contains("vim") || editor.contains("nvim") {
-                let mut cmd = vec![editor.clone()];
-                if let Some(line) = line {
-                    cmd.push(format!("+{}", line));
-                }
-                cmd.push(file_path.display().to_string());
-                cmd
-            } else if editor.contains("emacs") {
-                let location = if let Some(line) = line {
-                    format!("+{}:{}", line, column.unwrap_or(0))
-                } else {
-                    file_path.display().to_string()
-                };
-                vec![editor.clone(), location]
-            } else {
-                vec![editor.clone(), file_path.display().to_string()]
-            };
-
-        Command::new(&command[0])
-            .args(&command[1..])
+        let command = self.command_for(file_path, line, column);
+        let Some((program, args)) = command.split_first() else {
+            anyhow::bail!("Editor command is empty");
+        };
+
+        Command::new(program)
+            .args(args)
             .spawn()
             .context("Failed to spawn editor")?;
 
@@ -73,7 +131,7 @@ pub fn copy_to_clipboard(text: &str) -> Result<()> {
     Ok(())
 }
 
-fn resolve_editor_command(config_command: &str) -> String {
+pub fn resolve_editor_command(config_command: &str) -> String {
     if config_command == "${EDITOR}" {
         if let Ok(editor) = std::env::var("EDITOR") {
             return editor;
@@ -97,3 +155,87 @@ fn resolve_editor_command(config_command: &str) -> String {
 
     "vim".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_command() {
+        assert_eq!(EditorKind::from_command("code --wait"), EditorKind::VsCode);
+        assert_eq!(
+            EditorKind::from_command("/usr/bin/nvim"),
+            EditorKind::Neovim
+        );
+        assert_eq!(EditorKind::from_command("vi"), EditorKind::Vim);
+        assert_eq!(
+            EditorKind::from_command("emacsclient -n"),
+            EditorKind::Emacs
+        );
+        assert_eq!(EditorKind::from_command("subl"), EditorKind::Sublime);
+        assert_eq!(EditorKind::from_command("idea.sh"), EditorKind::Idea);
+        assert_eq!(EditorKind::from_command("nano"), EditorKind::Generic);
+    }
+
+    #[test]
+    fn test_args_per_editor() {
+        let cases = [
+            (EditorKind::VsCode, vec!["-g", "main.cpp:12:5"]),
+            (EditorKind::Neovim, vec!["+call cursor(12,5)", "main.cpp"]),
+            (EditorKind::Vim, vec!["+call cursor(12,5)", "main.cpp"]),
+            (EditorKind::Emacs, vec!["+12:5", "main.cpp"]),
+            (EditorKind::Sublime, vec!["main.cpp:12:5"]),
+            (
+                EditorKind::Idea,
+                vec!["--line", "12", "--column", "5", "main.cpp"],
+            ),
+            (EditorKind::Generic, vec!["main.cpp"]),
+        ];
+
+        for (kind, expected) in cases {
+            assert_eq!(
+                kind.args("main.cpp", Some(12), Some(5)),
+                expected,
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_args_without_column_or_line() {
+        assert_eq!(
+            EditorKind::VsCode.args("a.h", Some(3), None),
+            vec!["-g", "a.h:3"]
+        );
+        assert_eq!(
+            EditorKind::Neovim.args("a.h", Some(3), None),
+            vec!["+3", "a.h"]
+        );
+        assert_eq!(
+            EditorKind::Emacs.args("a.h", Some(3), None),
+            vec!["+3", "a.h"]
+        );
+        assert_eq!(
+            EditorKind::Idea.args("a.h", Some(3), None),
+            vec!["--line", "3", "a.h"]
+        );
+        assert_eq!(EditorKind::Sublime.args("a.h", None, Some(2)), vec!["a.h"]);
+    }
+
+    #[test]
+    fn test_command_keeps_editor_arguments() {
+        let editor = Editor::new("emacsclient -n".to_string(), true);
+        assert_eq!(
+            editor.command_for(Path::new("src/a.cpp"), Some(4), Some(2)),
+            vec!["emacsclient", "-n", "+4:2", "src/a.cpp"]
+        );
+
+        let editor = Editor::new("code".to_string(), false);
+        assert_eq!(editor.kind(), EditorKind::Generic);
+        assert_eq!(
+            editor.command_for(Path::new("src/a.cpp"), Some(4), Some(2)),
+            vec!["code", "src/a.cpp"]
+        );
+    }
+}
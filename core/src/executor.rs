@@ -1,12 +1,38 @@
 use anyhow::Context;
-use std::collections::VecDeque;
-use std::process::Stdio;
-use std::time::Instant;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tokio::sync::mpsc;
+use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
 
 use crate::builder::BuildStep;
+use crate::parser::entry::{LogComponent, LogEntry, StreamKind};
+use crate::parser::parser::{is_out_of_memory, CompilerOutputParser, OOM_DIAGNOSTIC};
+
+const ABORTED_DIAGNOSTIC: &str = "Stopped by ignis";
+
+static REQUESTED_KILLS: Lazy<Mutex<HashSet<u32>>> = Lazy::new(Default::default);
+
+pub fn note_kill_requested(pid: u32) {
+    if let Ok(mut pids) = REQUESTED_KILLS.lock() {
+        pids.insert(pid);
+    }
+}
+
+fn take_kill_requested(pid: Option<u32>) -> bool {
+    pid.is_some_and(|pid| {
+        REQUESTED_KILLS
+            .lock()
+            .map(|mut pids| pids.remove(&pid))
+            .unwrap_or(false)
+    })
+}
 
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
@@ -16,6 +42,7 @@ pub struct ExecutionResult {
     pub stderr: Vec<String>,
     pub exit_code: Option<i32>,
     pub failure_reason: Option<String>,
+    pub timed_out: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -25,9 +52,31 @@ pub struct RuntimeMetric {
     pub timestamp: Instant,
     pub category: String,
     pub explicit_visualization: Option<MetricVisualization>,
+    pub range: Option<(f64, f64)>,
+    pub tags: HashMap<String, String>,
+}
+
+pub fn tag_label(tags: &HashMap<String, String>) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+
+    let mut pairs: Vec<String> = tags
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    pairs.sort();
+    Some(pairs.join(","))
 }
 
 impl RuntimeMetric {
+    pub fn series_key(&self) -> String {
+        match tag_label(&self.tags) {
+            Some(label) => format!("{}:{}{{{}}}", self.category, self.key, label),
+            None => format!("{}:{}", self.category, self.key),
+        }
+    }
+
     pub fn parse_numeric_value(&self) -> Option<f64> {
         self.value.parse::<f64>().ok()
     }
@@ -36,6 +85,9 @@ impl RuntimeMetric {
         if let Some(viz) = self.explicit_visualization {
             return viz;
         }
+        if !self.tags.is_empty() {
+            return MetricVisualization::Sparkline;
+        }
 
         match self.metric_type() {
             MetricType::FPS | MetricType::TimeMillis | MetricType::Count | MetricType::Memory => {
@@ -97,6 +149,7 @@ pub enum MetricVisualization {
     Chart,
     Bar,
     Text,
+    Histogram,
     Auto,
 }
 
@@ -109,6 +162,7 @@ impl MetricVisualization {
             "chart" => Some(MetricVisualization::Chart),
             "bar" => Some(MetricVisualization::Bar),
             "text" => Some(MetricVisualization::Text),
+            "histogram" => Some(MetricVisualization::Histogram),
             "auto" => Some(MetricVisualization::Auto),
             _ => None,
         }
@@ -123,6 +177,8 @@ pub struct MetricHistory {
     pub timestamps: VecDeque<Instant>,
     pub metric_type: MetricType,
     pub visualization: MetricVisualization,
+    pub range: Option<(f64, f64)>,
+    pub tags: HashMap<String, String>,
     pub max_history: usize,
 }
 
@@ -140,10 +196,51 @@ impl MetricHistory {
             timestamps: VecDeque::with_capacity(50),
             metric_type,
             visualization,
+            range: None,
+            tags: HashMap::new(),
             max_history: 50,
         }
     }
 
+    pub fn from_metric(metric: &RuntimeMetric) -> Self {
+        Self::new(
+            metric.category.clone(),
+            metric.key.clone(),
+            metric.metric_type(),
+            metric.visualization(),
+        )
+        .with_tags(metric.tags.clone())
+    }
+
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn series_label(&self) -> String {
+        match tag_label(&self.tags) {
+            Some(label) => format!("{}{{{}}}", self.key, label),
+            None => self.key.clone(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match tag_label(&self.tags) {
+            Some(label) => format!(" {}: {} [{}] ", self.category, self.key, label),
+            None => format!(" {}: {} ", self.category, self.key),
+        }
+    }
+
+    pub fn with_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn ratio(&self, value: f64) -> f64 {
+        let (min, max) = self.range.unwrap_or((0.0, 100.0));
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    }
+
     pub fn add_value(&mut self, value: f64, timestamp: Instant) {
         if self.values.len() >= self.max_history {
             self.values.pop_front();
@@ -182,22 +279,366 @@ impl MetricHistory {
             .copied()
             .max_by(|a, b| a.partial_cmp(b).unwrap())
     }
+
+    pub fn percentile(&self, pct: f64) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<f64> = self.values.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, u64)> {
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return Vec::new();
+        };
+        let bins = bins.max(1);
+        let width = (max - min) / bins as f64;
+
+        let mut counts = vec![0u64; bins];
+        for &value in &self.values {
+            let bin = if width > 0.0 {
+                (((value - min) / width) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (min + width * i as f64, count))
+            .collect()
+    }
+}
+
+// The first value of a series in each tick goes out immediately; later values in
+// the same tick only replace a pending one, so a chatty program sends at most two
+// updates per series per tick however fast it prints.
+pub struct MetricCoalescer {
+    tick: Duration,
+    window_start: Instant,
+    sent: HashSet<String>,
+    pending: BTreeMap<String, RuntimeMetric>,
+}
+
+impl MetricCoalescer {
+    pub fn new(tick: Duration) -> Self {
+        Self {
+            tick,
+            window_start: Instant::now(),
+            sent: HashSet::new(),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, metric: RuntimeMetric, now: Instant) -> Vec<RuntimeMetric> {
+        let mut ready = if now.saturating_duration_since(self.window_start) >= self.tick {
+            self.flush(now)
+        } else {
+            Vec::new()
+        };
+
+        let key = metric.series_key();
+        if self.sent.insert(key.clone()) {
+            ready.push(metric);
+        } else {
+            self.pending.insert(key, metric);
+        }
+        ready
+    }
+
+    pub fn flush(&mut self, now: Instant) -> Vec<RuntimeMetric> {
+        self.window_start = now;
+        self.sent.clear();
+        std::mem::take(&mut self.pending).into_values().collect()
+    }
+
+    pub fn next_flush(&self, now: Instant) -> Option<Duration> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some((self.window_start + self.tick).saturating_duration_since(now))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedStep {
+    pub description: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Clone)]
 pub enum StepUpdate {
-    Started(String),
+    Started(BuildStep, Instant),
     Progress(String),
-    Finished(ExecutionResult),
+    Finished(BuildStep, ExecutionResult, Instant),
+    Skipped(SkippedStep),
     ProcessStarted(u32),
     ProcessFinished(u32),
     Metric(RuntimeMetric),
 }
 
+const KILL_GRACE: Duration = Duration::from_secs(2);
+pub const METRIC_TICK: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessEnv {
+    pub vars: HashMap<String, String>,
+    pub clear: bool,
+}
+
+impl ProcessEnv {
+    pub fn new(vars: &HashMap<String, String>, clear: bool) -> Self {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| {
+                let expanded = shellexpand::env(value)
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|_| value.clone());
+                (key.clone(), expanded)
+            })
+            .collect();
+
+        Self { vars, clear }
+    }
+
+    fn apply(&self, cmd: &mut Command) {
+        if self.clear {
+            cmd.env_clear();
+        }
+        cmd.envs(&self.vars);
+    }
+}
+
+/// Optional knobs for `execute_program`; the default runs with no timeout, no
+/// metric coalescing and no kill switch.
+#[derive(Debug, Default)]
+pub struct ProgramOptions {
+    timeout: Option<Duration>,
+    metric_tick: Option<Duration>,
+    kill_rx: Option<mpsc::UnboundedReceiver<()>>,
+}
+
+impl ProgramOptions {
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_metric_tick(mut self, metric_tick: Duration) -> Self {
+        self.metric_tick = Some(metric_tick);
+        self
+    }
+
+    pub fn with_kill_switch(mut self, kill_rx: mpsc::UnboundedReceiver<()>) -> Self {
+        self.kill_rx = Some(kill_rx);
+        self
+    }
+}
+
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub retries: usize,
+    patterns: Vec<Regex>,
+    backoff: Duration,
+    log_tx: Option<mpsc::UnboundedSender<LogEntry>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 0,
+            patterns: Vec::new(),
+            backoff: RETRY_BACKOFF,
+            log_tx: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(retries: usize, patterns: &[String]) -> anyhow::Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|p| Regex::new(p).with_context(|| format!("Invalid retry pattern '{}'", p)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            retries,
+            patterns,
+            backoff: RETRY_BACKOFF,
+            log_tx: None,
+        })
+    }
+
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn with_log(mut self, log_tx: mpsc::UnboundedSender<LogEntry>) -> Self {
+        self.log_tx = Some(log_tx);
+        self
+    }
+
+    fn should_retry(&self, result: &ExecutionResult, attempt: usize) -> bool {
+        !result.success
+            && attempt <= self.retries
+            && result
+                .stdout
+                .iter()
+                .chain(result.stderr.iter())
+                .any(|line| self.patterns.iter().any(|p| p.is_match(line)))
+    }
+
+    fn delay(&self, attempt: usize) -> Duration {
+        self.backoff * 2u32.saturating_pow(attempt.saturating_sub(1) as u32)
+    }
+}
+
+pub fn retry_entry(description: &str, attempt: usize, retries: usize, delay: Duration) -> LogEntry {
+    use crate::parser::entry::LogLevel;
+
+    let message = format!(
+        "Retrying '{}' (attempt {}/{}) in {:.1}s",
+        description,
+        attempt + 1,
+        retries + 1,
+        delay.as_secs_f64()
+    );
+    LogEntry::new(
+        LogLevel::Warning,
+        message.clone(),
+        message,
+        LogComponent::Other("system".to_string()),
+        999996,
+    )
+    .with_tags(vec!["retry".to_string()])
+}
+
+pub fn timeout_reason(limit: Duration) -> String {
+    format!("Timed out after {}s", limit.as_secs())
+}
+
+pub fn timeout_entry(limit: Duration) -> LogEntry {
+    use crate::parser::entry::LogLevel;
+
+    let message = format!("Process killed: {}", timeout_reason(limit).to_lowercase());
+    LogEntry::new(
+        LogLevel::Error,
+        message.clone(),
+        message,
+        LogComponent::Other("system".to_string()),
+        999997,
+    )
+    .with_tags(vec!["timeout".to_string()])
+}
+
+// Graceful first on Unix: SIGTERM, then SIGKILL if the process is still alive after
+// KILL_GRACE. Windows has no equivalent signal, so Child::kill (TerminateProcess) is
+// used straight away.
+async fn terminate(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        if kill(Pid::from_raw(pid as i32), Signal::SIGTERM).is_ok()
+            && tokio::time::timeout(KILL_GRACE, child.wait()).await.is_ok()
+        {
+            return;
+        }
+    }
+
+    let _ = child.kill().await;
+}
+
+async fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> anyhow::Result<Option<ExitStatus>> {
+    let Some(limit) = timeout else {
+        return child
+            .wait()
+            .await
+            .map(Some)
+            .context("Failed to wait for child process");
+    };
+
+    match tokio::time::timeout(limit, child.wait()).await {
+        Ok(status) => status.map(Some).context("Failed to wait for child process"),
+        Err(_) => {
+            terminate(child).await;
+            Ok(None)
+        }
+    }
+}
+
+async fn wait_or_kill(
+    child: &mut Child,
+    timeout: Option<Duration>,
+    kill_rx: Option<&mut mpsc::UnboundedReceiver<()>>,
+) -> anyhow::Result<Option<ExitStatus>> {
+    let Some(kill_rx) = kill_rx else {
+        return wait_with_timeout(child, timeout).await;
+    };
+
+    tokio::select! {
+        status = wait_with_timeout(child, timeout) => return status,
+        Some(()) = kill_rx.recv() => {}
+    }
+
+    terminate(child).await;
+    child
+        .wait()
+        .await
+        .map(Some)
+        .context("Failed to wait for killed child process")
+}
+
+async fn join_output<T: Default>(
+    task: JoinHandle<T>,
+    timed_out: bool,
+    name: &str,
+) -> anyhow::Result<T> {
+    if !timed_out {
+        return task
+            .await
+            .with_context(|| format!("{} task panicked", name));
+    }
+
+    match tokio::time::timeout(KILL_GRACE, task).await {
+        Ok(output) => output.with_context(|| format!("{} task panicked", name)),
+        Err(_) => Ok(T::default()),
+    }
+}
+
 pub async fn execute_step<F>(
     command: Vec<String>,
+    output_callback: F,
+    step_callback: Option<&mpsc::UnboundedSender<StepUpdate>>,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
+) -> anyhow::Result<ExecutionResult>
+where
+    F: FnMut(String) + Send + 'static,
+{
+    execute_step_in(command, None, output_callback, step_callback, timeout, env).await
+}
+
+pub async fn execute_step_in<F>(
+    command: Vec<String>,
+    working_dir: Option<&Path>,
     mut output_callback: F,
     step_callback: Option<&mpsc::UnboundedSender<StepUpdate>>,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
 ) -> anyhow::Result<ExecutionResult>
 where
     F: FnMut(String) + Send + 'static,
@@ -207,18 +648,23 @@ where
     let program = &command[0];
     let args = &command[1..];
 
-    let mut child = Command::new(program)
+    let mut cmd = Command::new(program);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    env.apply(&mut cmd);
+
+    let mut child = cmd
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
         .with_context(|| format!("Failed to spawn command: {}", program))?;
 
     let pid = child.id();
     if let (Some(pid), Some(callback)) = (pid, step_callback) {
-        callback
-            .send(StepUpdate::ProcessStarted(pid))
-            .expect("Failed to send ProcessStarted update");
+        let _ = callback.send(StepUpdate::ProcessStarted(pid));
     }
 
     let stdout = child.stdout.take().context("Failed to capture stdout")?;
@@ -232,9 +678,9 @@ where
         let mut reader = BufReader::new(stdout).lines();
         while let Ok(Some(line)) = reader.next_line().await {
             lines.push(line.clone());
-            tx_clone
-                .send(line)
-                .expect("Failed to send stdout line to channel");
+            if tx_clone.send(line).is_err() {
+                break;
+            }
         }
         lines
     });
@@ -244,8 +690,9 @@ where
         let mut reader = BufReader::new(stderr).lines();
         while let Ok(Some(line)) = reader.next_line().await {
             lines.push(line.clone());
-            tx.send(line)
-                .expect("Failed to send stderr line to channel");
+            if tx.send(line).is_err() {
+                break;
+            }
         }
         lines
     });
@@ -256,26 +703,50 @@ where
         }
     });
 
-    let status = child
-        .wait()
-        .await
-        .context("Failed to wait for child process")?;
+    let status = wait_with_timeout(&mut child, timeout).await?;
+    let timed_out = status.is_none();
 
     if let (Some(pid), Some(callback)) = (pid, step_callback) {
-        callback
-            .send(StepUpdate::ProcessFinished(pid))
-            .expect("Failed to send ProcessFinished update");
+        let _ = callback.send(StepUpdate::ProcessFinished(pid));
     }
 
-    let stdout_lines = stdout_task.await.context("stdout task panicked")?;
-    let stderr_lines = stderr_task.await.context("stderr task panicked")?;
+    let stdout_lines = join_output(stdout_task, timed_out, "stdout").await?;
+    let stderr_lines = join_output(stderr_task, timed_out, "stderr").await?;
 
     callback_task.abort();
 
     let duration = start.elapsed().as_secs_f64();
+
+    let Some(status) = status else {
+        return Ok(ExecutionResult {
+            success: false,
+            duration,
+            stdout: stdout_lines,
+            stderr: stderr_lines,
+            exit_code: None,
+            failure_reason: timeout.map(timeout_reason),
+            timed_out: true,
+        });
+    };
+
     let exit_code = status.code();
+
+    #[cfg(unix)]
+    let signal = {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    };
+    #[cfg(not(unix))]
+    let signal = None;
+
+    let killed_by_ignis = take_kill_requested(pid);
     let failure_reason = if !status.success() {
-        exit_code.map(|code| format!("Exit code {}", code))
+        describe_failure(
+            exit_code,
+            signal,
+            killed_by_ignis,
+            stdout_lines.iter().chain(stderr_lines.iter()),
+        )
     } else {
         None
     };
@@ -287,13 +758,78 @@ where
         stderr: stderr_lines,
         exit_code,
         failure_reason,
+        timed_out: false,
     })
 }
 
+fn describe_failure<'a>(
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    killed_by_ignis: bool,
+    mut output: impl Iterator<Item = &'a String>,
+) -> Option<String> {
+    if killed_by_ignis {
+        return Some(ABORTED_DIAGNOSTIC.to_string());
+    }
+    if signal == Some(9) || output.any(|line| is_out_of_memory(line)) {
+        return Some(OOM_DIAGNOSTIC.to_string());
+    }
+
+    match (exit_code, signal) {
+        (Some(code), _) => Some(format!("Exit code {}", code)),
+        (None, Some(signal)) => Some(format!("Signal {}", signal)),
+        (None, None) => None,
+    }
+}
+
+async fn execute_with_retry<F>(
+    step: &BuildStep,
+    output_callback: F,
+    step_callback: &mpsc::UnboundedSender<StepUpdate>,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
+    retry: &RetryPolicy,
+) -> anyhow::Result<ExecutionResult>
+where
+    F: FnMut(String) + Send + 'static + Clone,
+{
+    let mut attempt = 1;
+    loop {
+        let result = execute_step_in(
+            step.argv(),
+            step.working_dir.as_deref(),
+            output_callback.clone(),
+            Some(step_callback),
+            timeout,
+            env,
+        )
+        .await?;
+
+        if !retry.should_retry(&result, attempt) {
+            return Ok(result);
+        }
+
+        let delay = retry.delay(attempt);
+        if let Some(log_tx) = &retry.log_tx {
+            let _ = log_tx.send(retry_entry(
+                &step.description,
+                attempt,
+                retry.retries,
+                delay,
+            ));
+        }
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
 pub async fn execute_steps<F>(
     steps: Vec<BuildStep>,
     output_callback: F,
     step_callback: mpsc::UnboundedSender<StepUpdate>,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
+    retry: &RetryPolicy,
 ) -> anyhow::Result<Vec<ExecutionResult>>
 where
     F: FnMut(String) + Send + 'static + Clone,
@@ -301,16 +837,19 @@ where
     let mut results = Vec::new();
 
     for step in steps {
-        step_callback
-            .send(StepUpdate::Started(step.description.clone()))
-            .expect("Failed to send step Started update");
+        let _ = step_callback.send(StepUpdate::Started(step.clone(), Instant::now()));
 
-        let callback = output_callback.clone();
-        let result = execute_step(step.commands, callback, Some(&step_callback)).await?;
+        let result = execute_with_retry(
+            &step,
+            output_callback.clone(),
+            &step_callback,
+            timeout,
+            env,
+            retry,
+        )
+        .await?;
 
-        step_callback
-            .send(StepUpdate::Finished(result.clone()))
-            .expect("Failed to send step Finished update");
+        let _ = step_callback.send(StepUpdate::Finished(step, result.clone(), Instant::now()));
 
         let success = result.success;
         results.push(result);
@@ -323,88 +862,233 @@ where
     Ok(results)
 }
 
+pub fn dry_run_steps<F>(
+    steps: Vec<BuildStep>,
+    mut output_callback: F,
+    step_callback: &mpsc::UnboundedSender<StepUpdate>,
+) -> Vec<ExecutionResult>
+where
+    F: FnMut(String),
+{
+    steps
+        .into_iter()
+        .map(|step| {
+            let _ = step_callback.send(StepUpdate::Started(step.clone(), Instant::now()));
+            output_callback(format!("[dry-run] {}", step.description));
+            output_callback(format!("[dry-run]   {}", step.invocation()));
+
+            let result = ExecutionResult {
+                success: true,
+                duration: 0.0,
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+                exit_code: Some(0),
+                failure_reason: None,
+                timed_out: false,
+            };
+            let _ = step_callback.send(StepUpdate::Finished(step, result.clone(), Instant::now()));
+            result
+        })
+        .collect()
+}
+
+pub async fn execute_steps_parallel<M, F>(
+    steps: Vec<BuildStep>,
+    make_callback: M,
+    step_callback: mpsc::UnboundedSender<StepUpdate>,
+    max_parallel: usize,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
+    retry: &RetryPolicy,
+) -> anyhow::Result<Vec<ExecutionResult>>
+where
+    M: Fn(&BuildStep) -> F,
+    F: FnMut(String) + Send + 'static + Clone,
+{
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::new();
+
+    for step in steps {
+        let callback = make_callback(&step);
+        let semaphore = Arc::clone(&semaphore);
+        let step_callback = step_callback.clone();
+        let env = env.clone();
+        let retry = retry.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            let _ = step_callback.send(StepUpdate::Started(step.clone(), Instant::now()));
+
+            let result =
+                execute_with_retry(&step, callback, &step_callback, timeout, &env, &retry).await?;
+
+            let _ = step_callback.send(StepUpdate::Finished(step, result.clone(), Instant::now()));
+
+            Ok::<_, anyhow::Error>(result)
+        }));
+    }
+
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await.context("Build step task panicked")??);
+    }
+
+    Ok(results)
+}
+
+fn program_entry(
+    parser: &mut CompilerOutputParser,
+    line: &str,
+    index: usize,
+    stream: StreamKind,
+) -> LogEntry {
+    let mut entry = parser.parse_line(line);
+    entry.component = LogComponent::Other("exec".to_string());
+    entry.index = index;
+    entry.with_stream(stream)
+}
+
 pub async fn execute_program(
     exec_info: crate::builder::ExecutableInfo,
-    log_tx: mpsc::UnboundedSender<crate::parser::entry::LogEntry>,
+    log_tx: mpsc::UnboundedSender<LogEntry>,
     step_tx: mpsc::UnboundedSender<StepUpdate>,
+    env: &ProcessEnv,
+    options: ProgramOptions,
 ) -> anyhow::Result<ExecutionResult> {
-    use crate::parser::entry::{LogComponent, LogEntry, LogLevel};
+    use crate::parser::entry::LogLevel;
     use crate::parser::parser::MetricParser;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let ProgramOptions {
+        timeout,
+        metric_tick,
+        mut kill_rx,
+    } = options;
 
     let start = Instant::now();
+    let program_step = BuildStep::new(
+        exec_info.name.clone(),
+        vec![exec_info.path.display().to_string()],
+    );
+
+    let mut cmd = Command::new(&exec_info.path);
+    cmd.args(&exec_info.args);
+    env.apply(&mut cmd);
 
-    let mut child = Command::new(&exec_info.path)
+    let mut child = cmd
         .current_dir(&exec_info.project_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
         .with_context(|| format!("Failed to spawn executable: {}", exec_info.path.display()))?;
 
     let pid = child.id();
     if let Some(pid) = pid {
-        step_tx
-            .send(StepUpdate::ProcessStarted(pid))
-            .expect("Failed to send program ProcessStarted update");
+        let _ = step_tx.send(StepUpdate::ProcessStarted(pid));
     }
 
     let stdout = child.stdout.take().context("Failed to capture stdout")?;
     let stderr = child.stderr.take().context("Failed to capture stderr")?;
 
+    let next_index = Arc::new(AtomicUsize::new(0));
+
     let log_tx_stdout = log_tx.clone();
     let step_tx_clone = step_tx.clone();
+    let stdout_index = Arc::clone(&next_index);
 
     let stdout_task = tokio::spawn(async move {
         let mut reader = BufReader::new(stdout).lines();
-        let mut index = 0;
-        while let Ok(Some(line)) = reader.next_line().await {
-            if let Some(metric) = MetricParser::parse_metric_line(&line) {
-                step_tx_clone
-                    .send(StepUpdate::Metric(metric))
-                    .expect("Failed to send Metric update");
+        let mut parser = CompilerOutputParser::new();
+        // A zero tick forwards every sample as it arrives.
+        let mut coalescer = MetricCoalescer::new(metric_tick.unwrap_or_default());
+        let send_metrics = |metrics: Vec<RuntimeMetric>| {
+            metrics
+                .into_iter()
+                .all(|metric| step_tx_clone.send(StepUpdate::Metric(metric)).is_ok())
+        };
+
+        loop {
+            let wait = coalescer.next_flush(Instant::now());
+            let line = tokio::select! {
+                line = reader.next_line() => line,
+                _ = tokio::time::sleep(wait.unwrap_or_default()), if wait.is_some() => {
+                    if !send_metrics(coalescer.flush(Instant::now())) {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let Ok(Some(line)) = line else {
+                break;
+            };
+
+            let sent = if let Some(metric) = MetricParser::parse_metric_line(&line) {
+                send_metrics(coalescer.push(metric, Instant::now()))
             } else {
-                let entry = LogEntry::new(
-                    LogLevel::Info,
-                    line.clone(),
-                    line.clone(),
-                    LogComponent::Other("exec".to_string()),
-                    index,
-                );
-                index += 1;
+                let index = stdout_index.fetch_add(1, Ordering::SeqCst);
                 log_tx_stdout
-                    .send(entry)
-                    .expect("Failed to send stdout log entry");
+                    .send(program_entry(&mut parser, &line, index, StreamKind::Stdout))
+                    .is_ok()
+            };
+            if !sent {
+                break;
             }
         }
+        send_metrics(coalescer.flush(Instant::now()));
     });
 
     let log_tx_stderr = log_tx.clone();
+    let stderr_index = Arc::clone(&next_index);
     let stderr_task = tokio::spawn(async move {
         let mut reader = BufReader::new(stderr).lines();
-        let mut index = 100000;
+        let mut parser = CompilerOutputParser::new();
         while let Ok(Some(line)) = reader.next_line().await {
-            let entry = LogEntry::new(
-                LogLevel::Error,
-                line.clone(),
-                line.clone(),
-                LogComponent::Other("exec".to_string()),
-                index,
-            );
-            index += 1;
-            log_tx_stderr
-                .send(entry)
-                .expect("Failed to send stderr log entry");
+            let index = stderr_index.fetch_add(1, Ordering::SeqCst);
+            if log_tx_stderr
+                .send(program_entry(&mut parser, &line, index, StreamKind::Stderr))
+                .is_err()
+            {
+                break;
+            }
         }
     });
 
-    let status = child
-        .wait()
-        .await
-        .context("Failed to wait for child process")?;
+    let status = wait_or_kill(&mut child, timeout, kill_rx.as_mut()).await?;
+    let timed_out = status.is_none();
 
-    stdout_task.await.context("stdout task panicked")?;
-    stderr_task.await.context("stderr task panicked")?;
+    join_output(stdout_task, timed_out, "stdout").await?;
+    join_output(stderr_task, timed_out, "stderr").await?;
 
     let duration = start.elapsed().as_secs_f64();
+
+    let Some(status) = status else {
+        if let Some(limit) = timeout {
+            let _ = log_tx.send(timeout_entry(limit));
+        }
+
+        let result = ExecutionResult {
+            success: false,
+            duration,
+            stdout: vec![],
+            stderr: vec![],
+            exit_code: None,
+            failure_reason: timeout.map(timeout_reason),
+            timed_out: true,
+        };
+
+        let _ = step_tx.send(StepUpdate::Finished(
+            program_step,
+            result.clone(),
+            Instant::now(),
+        ));
+        if let Some(pid) = pid {
+            let _ = step_tx.send(StepUpdate::ProcessFinished(pid));
+        }
+
+        return Ok(result);
+    };
+
     let exit_code = status.code();
     let mut failure_reason: Option<String> = None;
 
@@ -444,9 +1128,7 @@ pub async fn execute_program(
                 LogComponent::Other("system".to_string()),
                 999999,
             );
-            log_tx
-                .send(error_entry)
-                .expect("Failed to send signal error log entry");
+            let _ = log_tx.send(error_entry);
         }
     }
 
@@ -462,9 +1144,7 @@ pub async fn execute_program(
                 LogComponent::Other("system".to_string()),
                 999999,
             );
-            log_tx
-                .send(error_entry)
-                .expect("Failed to send abnormal termination log entry");
+            let _ = log_tx.send(error_entry);
         }
     }
 
@@ -481,9 +1161,7 @@ pub async fn execute_program(
                 LogComponent::Other("system".to_string()),
                 999998,
             );
-            log_tx
-                .send(info_entry)
-                .expect("Failed to send exit code log entry");
+            let _ = log_tx.send(info_entry);
         }
     }
 
@@ -494,17 +1172,662 @@ pub async fn execute_program(
         stderr: vec![],
         exit_code,
         failure_reason,
+        timed_out: false,
     };
 
-    step_tx
-        .send(StepUpdate::Finished(result.clone()))
-        .expect("Failed to send program Finished update");
+    let _ = step_tx.send(StepUpdate::Finished(
+        program_step,
+        result.clone(),
+        Instant::now(),
+    ));
 
     if let Some(pid) = pid {
-        step_tx
-            .send(StepUpdate::ProcessFinished(pid))
-            .expect("Failed to send program ProcessFinished update");
+        let _ = step_tx.send(StepUpdate::ProcessFinished(pid));
     }
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_histogram_buckets() {
+        let mut metric = MetricHistory::new(
+            "perf".to_string(),
+            "frame_ms".to_string(),
+            MetricType::TimeMillis,
+            MetricVisualization::Histogram,
+        );
+        assert!(metric.histogram(4).is_empty());
+
+        for value in [10.0, 12.0, 14.9, 15.0, 16.6, 16.7, 29.0, 30.0] {
+            metric.add_value(value, Instant::now());
+        }
+
+        assert_eq!(
+            metric.histogram(4),
+            vec![(10.0, 3), (15.0, 3), (20.0, 0), (25.0, 2)]
+        );
+        assert_eq!(metric.histogram(4).iter().map(|(_, c)| c).sum::<u64>(), 8);
+
+        let mut flat = metric.clone();
+        flat.values = VecDeque::from(vec![16.6; 3]);
+        assert_eq!(flat.histogram(3), vec![(16.6, 3), (16.6, 0), (16.6, 0)]);
+    }
+
+    #[test]
+    fn test_metric_percentile_nearest_rank() {
+        let mut metric = MetricHistory::new(
+            "perf".to_string(),
+            "frame_ms".to_string(),
+            MetricType::TimeMillis,
+            MetricVisualization::Sparkline,
+        );
+        assert_eq!(metric.percentile(95.0), None);
+
+        for value in (1..=20).rev() {
+            metric.add_value(value as f64, Instant::now());
+        }
+
+        assert_eq!(metric.percentile(95.0), Some(19.0));
+        assert_eq!(metric.percentile(50.0), Some(10.0));
+        assert_eq!(metric.percentile(100.0), Some(20.0));
+        assert_eq!(metric.percentile(0.0), Some(1.0));
+    }
+
+    #[test]
+    fn test_metric_ratio_uses_range() {
+        let metric = MetricHistory::new(
+            "health".to_string(),
+            "hp".to_string(),
+            MetricType::Generic,
+            MetricVisualization::Gauge,
+        );
+        assert_eq!(metric.ratio(75.0), 0.75);
+        assert_eq!(metric.ratio(150.0), 1.0);
+
+        let metric = metric.with_range(Some((0.0, 200.0)));
+        assert_eq!(metric.ratio(75.0), 0.375);
+
+        let metric = metric.with_range(Some((-50.0, 50.0)));
+        assert_eq!(metric.ratio(0.0), 0.5);
+        assert_eq!(metric.ratio(-80.0), 0.0);
+    }
+
+    #[test]
+    fn test_describe_failure_detects_out_of_memory() {
+        let output = [
+            "[41/120] Building CXX object renderer.cpp.o".to_string(),
+            "c++: fatal error: Killed signal terminated program cc1plus".to_string(),
+        ];
+        assert_eq!(
+            describe_failure(Some(1), None, false, output.iter()).as_deref(),
+            Some(OOM_DIAGNOSTIC)
+        );
+        assert_eq!(
+            describe_failure(None, Some(9), false, std::iter::empty()).as_deref(),
+            Some(OOM_DIAGNOSTIC)
+        );
+    }
+
+    #[test]
+    fn test_describe_failure_reports_requested_kills_as_aborts() {
+        let output = ["c++: fatal error: Killed signal terminated program cc1plus".to_string()];
+        assert_eq!(
+            describe_failure(None, Some(9), true, output.iter()).as_deref(),
+            Some(ABORTED_DIAGNOSTIC)
+        );
+
+        note_kill_requested(u32::MAX);
+        assert!(take_kill_requested(Some(u32::MAX)));
+        assert!(!take_kill_requested(Some(u32::MAX)));
+        assert!(!take_kill_requested(None));
+    }
+
+    async fn collect_steps(steps: Vec<BuildStep>) -> Vec<ExecutionResult> {
+        let (step_tx, _step_rx) = mpsc::unbounded_channel();
+        execute_steps(
+            steps,
+            |_| {},
+            step_tx,
+            None,
+            &ProcessEnv::default(),
+            &RetryPolicy::default(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dry_run_steps_reports_commands_without_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("created");
+        let step = BuildStep::new(
+            "Touch".to_string(),
+            vec!["touch".to_string(), marker.display().to_string()],
+        )
+        .with_working_dir(std::env::temp_dir());
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+        let mut lines = Vec::new();
+
+        let results = dry_run_steps(vec![step], |line| lines.push(line), &step_tx);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+        assert_eq!(results[0].exit_code, Some(0));
+        assert_eq!(
+            lines,
+            vec![
+                "[dry-run] Touch".to_string(),
+                format!(
+                    "[dry-run]   cd {} && touch {}",
+                    std::env::temp_dir().display(),
+                    marker.display()
+                ),
+            ]
+        );
+        assert!(!marker.exists());
+        assert!(matches!(step_rx.try_recv(), Ok(StepUpdate::Started(..))));
+        assert!(matches!(step_rx.try_recv(), Ok(StepUpdate::Finished(..))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_step_runs_pipeline() {
+        let step = BuildStep::new(
+            "Shout".to_string(),
+            vec!["echo hello | tr a-z A-Z".to_string()],
+        )
+        .with_shell(true);
+
+        let results = collect_steps(vec![step]).await;
+        assert!(results[0].success);
+        assert_eq!(results[0].stdout, vec!["HELLO"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_direct_step_passes_argv_as_is() {
+        let step = BuildStep::new(
+            "Echo".to_string(),
+            vec!["echo".to_string(), "hello | tr a-z A-Z".to_string()],
+        );
+
+        let results = collect_steps(vec![step]).await;
+        assert!(results[0].success);
+        assert_eq!(results[0].stdout, vec!["hello | tr a-z A-Z"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_parallel_steps_run_concurrently() {
+        let steps = vec![
+            BuildStep::new(
+                "Building a".to_string(),
+                vec!["sleep".to_string(), "0.5".to_string()],
+            ),
+            BuildStep::new(
+                "Building b".to_string(),
+                vec!["sleep".to_string(), "0.5".to_string()],
+            ),
+        ];
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+
+        let start = Instant::now();
+        let results = execute_steps_parallel(
+            steps,
+            |_| |_| {},
+            step_tx,
+            2,
+            None,
+            &ProcessEnv::default(),
+            &RetryPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(start.elapsed().as_secs_f64() < 0.9);
+        assert!(results.iter().all(|r| r.success));
+
+        let mut pairs = Vec::new();
+        let mut pids = 0;
+        while let Ok(update) = step_rx.try_recv() {
+            match update {
+                StepUpdate::Started(step, _) => pairs.push(step.description),
+                StepUpdate::Finished(..) => assert!(!pairs.is_empty()),
+                StepUpdate::ProcessStarted(_) => pids += 1,
+                _ => {}
+            }
+        }
+        pairs.sort();
+        assert_eq!(pairs, vec!["Building a", "Building b"]);
+        assert_eq!(pids, 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_step_timeout_kills_process() {
+        let start = Instant::now();
+        let result = execute_step(
+            vec!["sleep".to_string(), "5".to_string()],
+            |_| {},
+            None,
+            Some(Duration::from_secs(1)),
+            &ProcessEnv::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(4));
+        assert!(!result.success);
+        assert!(result.timed_out);
+        assert_eq!(result.failure_reason.as_deref(), Some("Timed out after 1s"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_injected_env_reaches_process() {
+        std::env::set_var("IGNIS_TEST_ENV_SOURCE", "world");
+        let vars = HashMap::from([(
+            "IGNIS_TEST_GREETING".to_string(),
+            "hello-${IGNIS_TEST_ENV_SOURCE}".to_string(),
+        )]);
+
+        let step = BuildStep::new(
+            "Greet".to_string(),
+            vec!["echo \"$IGNIS_TEST_GREETING|$IGNIS_TEST_ENV_SOURCE\"".to_string()],
+        )
+        .with_shell(true);
+        let (step_tx, _step_rx) = mpsc::unbounded_channel();
+
+        let results = execute_steps(
+            vec![step.clone()],
+            |_| {},
+            step_tx.clone(),
+            None,
+            &ProcessEnv::new(&vars, false),
+            &RetryPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].stdout, vec!["hello-world|world"]);
+
+        let mut vars = vars;
+        vars.insert("PATH".to_string(), "/usr/bin:/bin".to_string());
+        let results = execute_steps(
+            vec![step],
+            |_| {},
+            step_tx,
+            None,
+            &ProcessEnv::new(&vars, true),
+            &RetryPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results[0].stdout, vec!["hello-world|"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_steps_retries_transient_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("attempted");
+        let script = format!(
+            "if [ -f {0} ]; then echo fetched; else touch {0}; echo 'Could not resolve host'; exit 1; fi",
+            marker.display()
+        );
+        let step = BuildStep::new("Fetch".to_string(), vec![script]).with_shell(true);
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+
+        let retry = RetryPolicy::new(2, &["Could not resolve host".to_string()])
+            .unwrap()
+            .with_backoff(Duration::from_millis(10))
+            .with_log(log_tx.clone());
+        let results = execute_steps(
+            vec![step.clone()],
+            |_| {},
+            step_tx.clone(),
+            None,
+            &ProcessEnv::default(),
+            &retry,
+        )
+        .await
+        .unwrap();
+
+        assert!(results[0].success);
+        assert_eq!(results[0].stdout, vec!["fetched"]);
+        let retry_entry = log_rx.try_recv().unwrap();
+        assert!(retry_entry.message.contains("attempt 2/3"));
+        assert!(log_rx.try_recv().is_err());
+
+        let mut finished = 0;
+        while let Ok(update) = step_rx.try_recv() {
+            if let StepUpdate::Finished(_, result, _) = update {
+                assert!(result.success);
+                finished += 1;
+            }
+        }
+        assert_eq!(finished, 1);
+
+        std::fs::remove_file(&marker).unwrap();
+        let retry = RetryPolicy::new(2, &["unrelated".to_string()])
+            .unwrap()
+            .with_backoff(Duration::from_millis(10))
+            .with_log(log_tx);
+        let results = execute_steps(
+            vec![step],
+            |_| {},
+            step_tx,
+            None,
+            &ProcessEnv::default(),
+            &retry,
+        )
+        .await
+        .unwrap();
+        assert!(!results[0].success);
+        assert!(log_rx.try_recv().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_parallel_steps_apply_retry_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let steps = ["engine", "editor"].map(|name| {
+            let marker = dir.path().join(name);
+            let script = format!(
+                "if [ -f {0} ]; then echo built; else touch {0}; echo 'Could not resolve host'; exit 1; fi",
+                marker.display()
+            );
+            BuildStep::new(name.to_string(), vec![script]).with_shell(true)
+        });
+        let (step_tx, _step_rx) = mpsc::unbounded_channel();
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+        let retry = RetryPolicy::new(1, &["Could not resolve host".to_string()])
+            .unwrap()
+            .with_backoff(Duration::from_millis(10))
+            .with_log(log_tx);
+
+        let results = execute_steps_parallel(
+            steps.to_vec(),
+            |_| |_| {},
+            step_tx,
+            2,
+            None,
+            &ProcessEnv::default(),
+            &retry,
+        )
+        .await
+        .unwrap();
+
+        assert!(results.iter().all(|r| r.success && r.stdout == ["built"]));
+        assert!(log_rx.try_recv().is_ok());
+        assert!(log_rx.try_recv().is_ok());
+        assert!(log_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_retry_policy_rejects_invalid_pattern() {
+        assert!(RetryPolicy::new(1, &["(unclosed".to_string()]).is_err());
+        let retry = RetryPolicy::default().with_backoff(Duration::from_millis(100));
+        assert_eq!(retry.delay(1), Duration::from_millis(100));
+        assert_eq!(retry.delay(3), Duration::from_millis(400));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_program_kill_request_terminates_process() {
+        let exec_info = crate::builder::ExecutableInfo {
+            path: std::path::PathBuf::from("sleep"),
+            name: "sleep".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec!["5".to_string()],
+        };
+        let (log_tx, _log_rx) = mpsc::unbounded_channel();
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+        let (kill_tx, kill_rx) = mpsc::unbounded_channel();
+
+        let start = Instant::now();
+        let handle = tokio::spawn(async move {
+            execute_program(
+                exec_info,
+                log_tx,
+                step_tx,
+                &ProcessEnv::default(),
+                ProgramOptions::default().with_kill_switch(kill_rx),
+            )
+            .await
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        kill_tx.send(()).unwrap();
+
+        let result = handle.await.unwrap().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(4));
+        assert!(!result.success);
+
+        let mut finished = false;
+        while let Ok(update) = step_rx.try_recv() {
+            finished |= matches!(update, StepUpdate::ProcessFinished(_));
+        }
+        assert!(finished);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_program_survives_dropped_receivers() {
+        let exec_info = crate::builder::ExecutableInfo {
+            path: std::path::PathBuf::from("sh"),
+            name: "sh".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec![
+                "-c".to_string(),
+                "while true; do echo '[IGNIS_METRIC] perf:fps=60'; echo spam; done".to_string(),
+            ],
+        };
+        let (log_tx, log_rx) = mpsc::unbounded_channel();
+        let (step_tx, step_rx) = mpsc::unbounded_channel();
+
+        let handle = tokio::spawn(async move {
+            execute_program(
+                exec_info,
+                log_tx,
+                step_tx,
+                &ProcessEnv::default(),
+                ProgramOptions::default()
+                    .with_timeout(Some(Duration::from_secs(10)))
+                    .with_metric_tick(METRIC_TICK),
+            )
+            .await
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        drop(log_rx);
+        drop(step_rx);
+
+        let result = handle.await.unwrap().unwrap();
+        assert!(!result.timed_out);
+    }
+
+    #[test]
+    fn test_metric_coalescer_keeps_latest_value_per_tick() {
+        let metric = |value: usize| {
+            let line = format!("[IGNIS_METRIC] perf:fps={}", value);
+            crate::parser::parser::MetricParser::parse_metric_line(&line).unwrap()
+        };
+        let start = Instant::now();
+        let mut coalescer = MetricCoalescer::new(Duration::from_millis(50));
+        coalescer.flush(start);
+
+        assert_eq!(coalescer.push(metric(0), start).len(), 1);
+        for value in 1..100 {
+            assert!(coalescer.push(metric(value), start).is_empty());
+        }
+        assert_eq!(coalescer.next_flush(start), Some(Duration::from_millis(50)));
+
+        let ready = coalescer.push(metric(100), start + Duration::from_millis(60));
+        let values: Vec<_> = ready.iter().map(|metric| metric.value.as_str()).collect();
+        assert_eq!(values, vec!["99", "100"]);
+        assert_eq!(coalescer.next_flush(start), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_metric_flood_is_coalesced() {
+        let exec_info = crate::builder::ExecutableInfo {
+            path: std::path::PathBuf::from("awk"),
+            name: "awk".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec![
+                "BEGIN { for (i = 0; i < 100000; i++) print \"[IGNIS_METRIC] perf:fps=\" i }"
+                    .to_string(),
+            ],
+        };
+        let (log_tx, _log_rx) = mpsc::unbounded_channel();
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+
+        let start = Instant::now();
+        execute_program(
+            exec_info,
+            log_tx,
+            step_tx,
+            &ProcessEnv::default(),
+            ProgramOptions::default().with_metric_tick(METRIC_TICK),
+        )
+        .await
+        .unwrap();
+        let elapsed = start.elapsed();
+
+        let mut values = Vec::new();
+        while let Ok(update) = step_rx.try_recv() {
+            if let StepUpdate::Metric(metric) = update {
+                values.push(metric.value);
+            }
+        }
+
+        let ticks = elapsed.as_millis() as usize / METRIC_TICK.as_millis() as usize + 1;
+        assert!(
+            values.len() <= 2 * ticks + 2,
+            "{} metrics sent",
+            values.len()
+        );
+        assert_eq!(values.last().map(String::as_str), Some("99999"));
+        assert!(elapsed < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_metrics_are_not_coalesced_without_a_tick() {
+        let exec_info = crate::builder::ExecutableInfo {
+            path: std::path::PathBuf::from("awk"),
+            name: "awk".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec![
+                "BEGIN { for (i = 0; i < 1000; i++) print \"[IGNIS_METRIC] perf:fps=\" i }"
+                    .to_string(),
+            ],
+        };
+        let (log_tx, _log_rx) = mpsc::unbounded_channel();
+        let (step_tx, mut step_rx) = mpsc::unbounded_channel();
+
+        execute_program(
+            exec_info,
+            log_tx,
+            step_tx,
+            &ProcessEnv::default(),
+            ProgramOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let mut count = 0;
+        while let Ok(update) = step_rx.try_recv() {
+            count += matches!(update, StepUpdate::Metric(_)) as usize;
+        }
+        assert_eq!(count, 1000);
+    }
+
+    #[test]
+    fn test_program_entry_level_is_independent_of_stream() {
+        let mut parser = CompilerOutputParser::new();
+
+        let info = program_entry(&mut parser, "loading scene", 3, StreamKind::Stderr);
+        assert_eq!(info.level, crate::parser::entry::LogLevel::Info);
+        assert_eq!(info.stream, StreamKind::Stderr);
+        assert_eq!(info.index, 3);
+
+        let error = program_entry(
+            &mut parser,
+            "src/main.cpp:4:2: error: boom",
+            4,
+            StreamKind::Stdout,
+        );
+        assert_eq!(error.level, crate::parser::entry::LogLevel::Error);
+        assert_eq!(error.stream, StreamKind::Stdout);
+        assert_eq!(error.component, LogComponent::Other("exec".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_program_streams_share_one_index_sequence() {
+        let exec_info = crate::builder::ExecutableInfo {
+            path: std::path::PathBuf::from("sh"),
+            name: "sh".to_string(),
+            project_dir: std::env::temp_dir(),
+            build_dir: std::env::temp_dir(),
+            install_dir: std::env::temp_dir(),
+            args: vec![
+                "-c".to_string(),
+                "echo one; echo two >&2; echo three".to_string(),
+            ],
+        };
+        let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+        let (step_tx, _step_rx) = mpsc::unbounded_channel();
+
+        execute_program(
+            exec_info,
+            log_tx,
+            step_tx,
+            &ProcessEnv::default(),
+            ProgramOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let mut entries = Vec::new();
+        while let Ok(entry) = log_rx.try_recv() {
+            entries.push(entry);
+        }
+        let mut indices: Vec<usize> = entries.iter().map(|e| e.index).collect();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        let stderr: Vec<&LogEntry> = entries
+            .iter()
+            .filter(|e| e.stream == StreamKind::Stderr)
+            .collect();
+        assert_eq!(stderr.len(), 1);
+        assert_eq!(stderr[0].message, "two");
+        assert_eq!(stderr[0].level, crate::parser::entry::LogLevel::Info);
+    }
+
+    #[test]
+    fn test_describe_failure_generic() {
+        assert_eq!(
+            describe_failure(Some(2), None, false, std::iter::empty()).as_deref(),
+            Some("Exit code 2")
+        );
+        assert_eq!(
+            describe_failure(None, Some(11), false, std::iter::empty()).as_deref(),
+            Some("Signal 11")
+        );
+    }
+}
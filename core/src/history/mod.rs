@@ -1,6 +1,6 @@
 pub mod storage;
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -11,6 +11,12 @@ pub struct BuildStepResult {
     pub success: bool,
     pub error_count: usize,
     pub warning_count: usize,
+    #[serde(default)]
+    pub commands: Vec<String>,
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub start_offset: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +31,10 @@ pub struct BuildHistoryEntry {
     pub note: Option<String>,
     pub git_commit: Option<String>,
     pub git_branch: Option<String>,
+    #[serde(default)]
+    pub incremental: bool,
+    #[serde(default)]
+    pub compile_steps: usize,
 }
 
 impl BuildHistoryEntry {
@@ -40,6 +50,8 @@ impl BuildHistoryEntry {
             note: None,
             git_commit: capture_git_commit(),
             git_branch: capture_git_branch(),
+            incremental: false,
+            compile_steps: 0,
         }
     }
 
@@ -54,12 +66,200 @@ impl BuildHistoryEntry {
         self.duration = total_duration;
         self.success = self.steps.iter().all(|s| s.success);
     }
+
+    pub fn kind_label(&self) -> &'static str {
+        if self.incremental {
+            "incr"
+        } else {
+            "clean"
+        }
+    }
+
+    pub fn short_commit(&self) -> Option<&str> {
+        self.git_commit
+            .as_deref()
+            .map(|commit| commit.get(..8).unwrap_or(commit))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepDiff {
+    pub description: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl StepDiff {
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.after? - self.before?)
+    }
+}
+
+pub fn diff_steps(before: &BuildHistoryEntry, after: &BuildHistoryEntry) -> Vec<StepDiff> {
+    let mut diffs: Vec<StepDiff> = Vec::new();
+    let mut matched = vec![false; after.steps.len()];
+
+    for step in &before.steps {
+        let counterpart = after
+            .steps
+            .iter()
+            .enumerate()
+            .find(|(idx, s)| !matched[*idx] && s.description == step.description);
+
+        let after_duration = counterpart.map(|(idx, s)| {
+            matched[idx] = true;
+            s.duration
+        });
+
+        diffs.push(StepDiff {
+            description: step.description.clone(),
+            before: Some(step.duration),
+            after: after_duration,
+        });
+    }
+
+    for (idx, step) in after.steps.iter().enumerate() {
+        if !matched[idx] {
+            diffs.push(StepDiff {
+                description: step.description.clone(),
+                before: None,
+                after: Some(step.duration),
+            });
+        }
+    }
+
+    diffs
+}
+
+pub fn is_incremental(
+    history: &[BuildHistoryEntry],
+    preset: &str,
+    cache_existed: bool,
+    compile_steps: usize,
+) -> bool {
+    if !cache_existed {
+        return false;
+    }
+
+    history
+        .iter()
+        .rev()
+        .find(|entry| entry.preset == preset && !entry.incremental && entry.compile_steps > 0)
+        .map_or(true, |clean| compile_steps < clean.compile_steps)
+}
+
+const REGRESSION_BASELINE_BUILDS: usize = 10;
+const REGRESSION_MIN_BUILDS: usize = 3;
+
+pub fn regression_baseline(
+    history: &[BuildHistoryEntry],
+    preset: &str,
+    incremental: bool,
+) -> Option<(f64, usize)> {
+    let mut durations: Vec<f64> = history
+        .iter()
+        .rev()
+        .filter(|entry| {
+            entry.success && entry.preset == preset && entry.incremental == incremental
+        })
+        .take(REGRESSION_BASELINE_BUILDS)
+        .map(|entry| entry.duration)
+        .collect();
+
+    if durations.len() < REGRESSION_MIN_BUILDS {
+        return None;
+    }
+
+    durations.sort_by(|a, b| a.total_cmp(b));
+    let mid = durations.len() / 2;
+    let median = if durations.len() % 2 == 0 {
+        (durations[mid - 1] + durations[mid]) / 2.0
+    } else {
+        durations[mid]
+    };
+
+    Some((median, durations.len()))
+}
+
+pub fn detect_regression(
+    history: &[BuildHistoryEntry],
+    preset: &str,
+    incremental: bool,
+    duration: f64,
+    threshold_pct: f64,
+) -> Option<String> {
+    let (median, count) = regression_baseline(history, preset, incremental)?;
+    let kind = if incremental { "incremental" } else { "clean" };
+    if median <= 0.0 {
+        return None;
+    }
+
+    let regression_pct = (duration - median) / median * 100.0;
+    (regression_pct > threshold_pct).then(|| {
+        format!(
+            "Build took {:.1}s, {:.0}% slower than the {:.1}s median of the last {} {} {} builds",
+            duration, regression_pct, median, count, kind, preset
+        )
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub preset: Option<String>,
+    pub failed_only: bool,
+    pub since: Option<NaiveDate>,
+    pub query: Option<String>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &BuildHistoryEntry) -> bool {
+        if let Some(preset) = &self.preset {
+            if !matches_preset(entry, preset) {
+                return false;
+            }
+        }
+        if self.failed_only && entry.success {
+            return false;
+        }
+        if let Some(since) = self.since {
+            if !matches_since(entry, since) {
+                return false;
+            }
+        }
+        if let Some(query) = &self.query {
+            if !matches_query(entry, query) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn apply<'a>(&self, entries: &'a [BuildHistoryEntry]) -> Vec<&'a BuildHistoryEntry> {
+        entries.iter().filter(|entry| self.matches(entry)).collect()
+    }
+}
+
+pub fn matches_preset(entry: &BuildHistoryEntry, preset: &str) -> bool {
+    entry.preset.eq_ignore_ascii_case(preset)
+}
+
+pub fn matches_since(entry: &BuildHistoryEntry, since: NaiveDate) -> bool {
+    entry.timestamp.date_naive() >= since
+}
+
+pub fn matches_query(entry: &BuildHistoryEntry, query: &str) -> bool {
+    let query = query.to_lowercase();
+    [&entry.note, &entry.git_commit, &entry.git_branch]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(&query))
 }
 
 pub struct BuildHistory {
     entries: Vec<BuildHistoryEntry>,
     storage_path: PathBuf,
     max_builds: usize,
+    max_age_days: Option<u64>,
 }
 
 impl BuildHistory {
@@ -69,17 +269,32 @@ impl BuildHistory {
             entries,
             storage_path,
             max_builds,
+            max_age_days: None,
         })
     }
 
+    pub fn with_max_age_days(mut self, max_age_days: Option<u64>) -> Self {
+        self.max_age_days = max_age_days;
+        self
+    }
+
     pub fn add_entry(&mut self, entry: BuildHistoryEntry) -> anyhow::Result<()> {
         self.entries.push(entry);
+        self.prune(Local::now());
 
-        if self.entries.len() > self.max_builds {
-            self.entries.remove(0);
+        storage::save_history(&self.storage_path, &self.entries)
+    }
+
+    fn prune(&mut self, now: DateTime<Local>) {
+        if let Some(days) = self.max_age_days {
+            let cutoff = now - Duration::days(days as i64);
+            self.entries.retain(|entry| entry.timestamp >= cutoff);
         }
 
-        storage::save_history(&self.storage_path, &self.entries)
+        if self.entries.len() > self.max_builds {
+            let excess = self.entries.len() - self.max_builds;
+            self.entries.drain(..excess);
+        }
     }
 
     pub fn entries(&self) -> &[BuildHistoryEntry] {
@@ -142,6 +357,8 @@ pub struct ExecutionHistoryEntry {
     pub log_count: usize,
     #[serde(default)]
     pub failure_reason: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 impl ExecutionHistoryEntry {
@@ -158,6 +375,7 @@ impl ExecutionHistoryEntry {
             metric_count: 0,
             log_count: 0,
             failure_reason: None,
+            args: Vec::new(),
         }
     }
 }
@@ -201,3 +419,244 @@ impl ExecutionHistory {
         storage::save_exec_history(&self.storage_path, &self.entries)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(description: &str, duration: f64) -> BuildStepResult {
+        BuildStepResult {
+            description: description.to_string(),
+            duration,
+            success: true,
+            error_count: 0,
+            warning_count: 0,
+            commands: Vec::new(),
+            target: None,
+            start_offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_diff_steps_matches_by_description() {
+        let mut before = BuildHistoryEntry::new("debug".to_string());
+        before.add_step(step("Configuring CMake", 2.0));
+        before.add_step(step("Building app", 10.0));
+
+        let mut after = BuildHistoryEntry::new("debug".to_string());
+        after.add_step(step("Configuring CMake", 10.0));
+        after.add_step(step("Building app", 8.0));
+
+        let diffs = diff_steps(&before, &after);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].delta(), Some(8.0));
+        assert_eq!(diffs[1].delta(), Some(-2.0));
+    }
+
+    #[test]
+    fn test_diff_steps_repeated_and_missing_steps() {
+        let mut before = BuildHistoryEntry::new("debug".to_string());
+        before.add_step(step("Configuring CMake", 1.0));
+        before.add_step(step("Configuring CMake", 2.0));
+        before.add_step(step("Installing artifacts", 1.0));
+
+        let mut after = BuildHistoryEntry::new("debug".to_string());
+        after.add_step(step("Configuring CMake", 3.0));
+        after.add_step(step("Configuring CMake", 4.0));
+        after.add_step(step("Building tests", 5.0));
+
+        let diffs = diff_steps(&before, &after);
+        assert_eq!(diffs.len(), 4);
+        assert_eq!(diffs[0].delta(), Some(2.0));
+        assert_eq!(diffs[1].delta(), Some(2.0));
+        assert_eq!(diffs[2].after, None);
+        assert_eq!(diffs[3].before, None);
+        assert_eq!(diffs[3].description, "Building tests");
+    }
+
+    fn entry(preset: &str, success: bool, date: &str) -> BuildHistoryEntry {
+        let mut entry = BuildHistoryEntry::new(preset.to_string());
+        entry.success = success;
+        entry.timestamp = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap();
+        entry.note = None;
+        entry.git_commit = Some("3f2a9c1d0e".to_string());
+        entry.git_branch = Some("main".to_string());
+        entry
+    }
+
+    fn history_with_ages(ages_in_days: &[i64], now: DateTime<Local>) -> BuildHistory {
+        let entries = ages_in_days
+            .iter()
+            .map(|&age| {
+                let mut entry = BuildHistoryEntry::new("debug".to_string());
+                entry.timestamp = now - Duration::days(age);
+                entry
+            })
+            .collect();
+
+        BuildHistory {
+            entries,
+            storage_path: PathBuf::new(),
+            max_builds: 50,
+            max_age_days: None,
+        }
+    }
+
+    #[test]
+    fn test_prune_drops_entries_older_than_max_age() {
+        let now = Local::now();
+        let mut history = history_with_ages(&[45, 31, 29, 1, 0], now).with_max_age_days(Some(30));
+
+        history.prune(now);
+        assert_eq!(history.entries().len(), 3);
+        assert!(history
+            .entries()
+            .iter()
+            .all(|e| e.timestamp >= now - Duration::days(30)));
+    }
+
+    #[test]
+    fn test_prune_applies_age_before_count_cap() {
+        let now = Local::now();
+        let mut history = history_with_ages(&[60, 20, 10, 5, 1], now).with_max_age_days(Some(30));
+        history.max_builds = 2;
+
+        history.prune(now);
+        let ages: Vec<i64> = history
+            .entries()
+            .iter()
+            .map(|e| (now - e.timestamp).num_days())
+            .collect();
+        assert_eq!(ages, vec![5, 1]);
+    }
+
+    #[test]
+    fn test_prune_without_max_age_only_caps_count() {
+        let now = Local::now();
+        let mut history = history_with_ages(&[400, 200, 1], now);
+        history.max_builds = 5;
+
+        history.prune(now);
+        assert_eq!(history.entries().len(), 3);
+    }
+
+    fn timed(preset: &str, duration: f64, success: bool) -> BuildHistoryEntry {
+        let mut entry = BuildHistoryEntry::new(preset.to_string());
+        entry.duration = duration;
+        entry.success = success;
+        entry
+    }
+
+    #[test]
+    fn test_regression_baseline_uses_successful_builds_of_same_preset() {
+        let history = vec![
+            timed("debug", 10.0, true),
+            timed("debug", 2.0, false),
+            timed("release", 50.0, true),
+            timed("debug", 12.0, true),
+            timed("debug", 11.0, true),
+        ];
+
+        assert_eq!(regression_baseline(&history, "debug", false), Some((11.0, 3)));
+    }
+
+    #[test]
+    fn test_regression_requires_three_comparable_builds() {
+        let history = vec![timed("debug", 10.0, true), timed("debug", 10.0, true)];
+
+        assert_eq!(regression_baseline(&history, "debug", false), None);
+        assert_eq!(detect_regression(&history, "debug", false, 100.0, 20.0), None);
+    }
+
+    #[test]
+    fn test_detect_regression_threshold() {
+        let history = vec![
+            timed("debug", 10.0, true),
+            timed("debug", 10.0, true),
+            timed("debug", 10.0, true),
+        ];
+
+        assert_eq!(detect_regression(&history, "debug", false, 11.5, 20.0), None);
+        let note = detect_regression(&history, "debug", false, 13.0, 20.0).unwrap();
+        assert!(note.contains("30% slower"));
+        assert!(note.contains("clean debug builds"));
+    }
+
+    #[test]
+    fn test_incremental_builds_have_their_own_baseline() {
+        let mut history = vec![timed("debug", 60.0, true), timed("debug", 60.0, true)];
+        for _ in 0..3 {
+            let mut entry = timed("debug", 5.0, true);
+            entry.incremental = true;
+            history.push(entry);
+        }
+
+        assert_eq!(regression_baseline(&history, "debug", true), Some((5.0, 3)));
+        assert_eq!(regression_baseline(&history, "debug", false), None);
+        assert_eq!(detect_regression(&history, "debug", false, 60.0, 20.0), None);
+    }
+
+    #[test]
+    fn test_is_incremental_needs_cache_and_fewer_steps_than_last_clean_build() {
+        let mut clean = timed("debug", 60.0, true);
+        clean.compile_steps = 200;
+        let history = vec![timed("release", 1.0, true), clean];
+
+        assert!(!is_incremental(&history, "debug", false, 3));
+        assert!(is_incremental(&history, "debug", true, 3));
+        assert!(!is_incremental(&history, "debug", true, 200));
+        assert!(is_incremental(&history, "release", true, 500));
+    }
+
+    #[test]
+    fn test_matches_preset() {
+        assert!(matches_preset(&entry("release", true, "2024-03-01"), "Release"));
+        assert!(!matches_preset(&entry("debug", true, "2024-03-01"), "release"));
+    }
+
+    #[test]
+    fn test_matches_since() {
+        let since = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert!(matches_since(&entry("debug", true, "2024-03-01"), since));
+        assert!(matches_since(&entry("debug", true, "2024-04-10"), since));
+        assert!(!matches_since(&entry("debug", true, "2024-02-29"), since));
+    }
+
+    #[test]
+    fn test_matches_query() {
+        let mut noted = entry("debug", true, "2024-03-01");
+        noted.note = Some("First build after toolchain upgrade".to_string());
+
+        assert!(matches_query(&noted, "toolchain"));
+        assert!(matches_query(&noted, "3F2A"));
+        assert!(matches_query(&noted, "main"));
+        assert!(!matches_query(&noted, "feature/"));
+    }
+
+    #[test]
+    fn test_history_filter_combines_predicates() {
+        let entries = vec![
+            entry("debug", true, "2024-01-01"),
+            entry("debug", false, "2024-03-05"),
+            entry("release", false, "2024-03-06"),
+            entry("debug", false, "2024-02-01"),
+        ];
+
+        let filter = HistoryFilter {
+            preset: Some("debug".to_string()),
+            failed_only: true,
+            since: NaiveDate::from_ymd_opt(2024, 3, 1),
+            query: None,
+        };
+
+        let matched = filter.apply(&entries);
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].timestamp.date_naive().to_string(), "2024-03-05");
+        assert_eq!(HistoryFilter::default().apply(&entries).len(), 4);
+    }
+}
@@ -1,19 +1,31 @@
+pub mod bench;
 pub mod builder;
+pub mod clean;
 pub mod cli;
 pub mod command;
 pub mod config;
 pub mod dependency_graph;
+pub mod doctor;
 pub mod editor;
 pub mod executor;
 pub mod history;
 pub mod logger;
+pub mod metrics_server;
 pub mod monitor;
+pub mod notification;
 pub mod parser;
+pub mod plan;
+pub mod presets;
+pub mod report;
+pub mod prevalidation;
 pub mod stage;
 pub mod stage_context;
 pub mod stage_runner;
 pub mod target;
+pub mod trace;
 pub mod tui;
+pub mod watch;
+pub mod watchdog;
 
 pub use builder::{Builder, ExecutableInfo};
 pub use cli::{Cli, HistoryCommands, HistoryType};
@@ -27,8 +39,9 @@ pub use history::{
     BuildHistory, BuildHistoryEntry, BuildStepResult, ExecutionHistory, ExecutionHistoryEntry,
 };
 pub use monitor::{ResourceMonitor, ResourceStats};
+pub use plan::{BuildPlan, PlannedStage};
 pub use stage::{Stage, StageMetadata};
-pub use stage_context::{SharedStageContext, StageContext, StageResult, StageStatus};
+pub use stage_context::{SharedStageContext, StageContext, StagePlanner, StageResult, StageStatus};
 pub use stage_runner::{BuildContext, ExecRunner, StageRunner};
 pub use tui::{BuildAction, ExecAction};
 
@@ -1,26 +1,130 @@
-use crate::parser::entry::{LogEntry, LogLevel};
+use crate::config::Config;
+use crate::parser::entry::{LogComponent, LogEntry, LogLevel};
+use anyhow::{Context, Result};
+use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Jsonl,
+}
+
+pub struct LogFile {
+    dir: PathBuf,
+    stem: String,
+    max_bytes: u64,
+    part: usize,
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl LogFile {
+    pub fn create(dir: &Path, prefix: &str, max_bytes: u64) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let stem = format!(
+            "{}_{}",
+            prefix,
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        );
+        let path = dir.join(format!("{}.txt", stem));
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            stem,
+            max_bytes,
+            part: 0,
+            path,
+            file,
+            written: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write_line(&mut self, line: &str) -> Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.written > 0 && self.written + len > self.max_bytes {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{}", line)
+            .with_context(|| format!("Failed to write logs to {}", self.path.display()))?;
+        self.written += len;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.part += 1;
+        self.path = self.dir.join(format!("{}.{}.txt", self.stem, self.part));
+        self.file = File::create(&self.path)
+            .with_context(|| format!("Failed to create {}", self.path.display()))?;
+        self.written = 0;
+        Ok(())
+    }
+}
 
 pub struct Logger {
     use_colors: bool,
+    format: LogFormat,
+    file: Option<Mutex<LogFile>>,
 }
 
 impl Logger {
     pub fn new() -> Self {
         Self {
             use_colors: atty::is(atty::Stream::Stdout),
+            format: LogFormat::Text,
+            file: None,
         }
     }
 
+    pub fn new_with_config(config: &Config, prefix: &str) -> Result<Self> {
+        let mut logger = Self::new();
+        logger.format = config.logs.log_format()?;
+        if config.logs.auto_save {
+            let file = LogFile::create(
+                &config.log_directory(),
+                prefix,
+                config.logs.max_file_size_kb * 1024,
+            )?;
+            logger.file = Some(Mutex::new(file));
+        }
+        Ok(logger)
+    }
+
     pub fn log_entry(&self, entry: &LogEntry) {
-        if self.use_colors {
+        if self.format == LogFormat::Text && self.use_colors {
             self.log_colored(entry);
         } else {
-            self.log_plain(entry);
+            println!("{}", self.format_line(entry));
+            std::io::stdout().flush().unwrap();
         }
+        self.write_to_file(&self.format_line(entry));
     }
 
     pub fn log(&self, level: LogLevel, message: &str) {
+        if self.format == LogFormat::Jsonl {
+            let entry = LogEntry::new(
+                level,
+                message.to_string(),
+                message.to_string(),
+                LogComponent::Other("ignis".to_string()),
+                0,
+            );
+            return self.log_entry(&entry);
+        }
+
         if self.use_colors {
             let color = match level {
                 LogLevel::Debug => "\x1b[90m",
@@ -33,6 +137,18 @@ impl Logger {
         } else {
             println!("{}", message);
         }
+        self.write_to_file(message);
+    }
+
+    fn write_to_file(&self, line: &str) {
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        let mut file = file.lock().unwrap();
+        if let Err(e) = file.write_line(line) {
+            eprintln!("{:#}", e);
+        }
     }
 
     fn log_colored(&self, entry: &LogEntry) {
@@ -58,16 +174,21 @@ impl Logger {
         std::io::stdout().flush().unwrap();
     }
 
-    fn log_plain(&self, entry: &LogEntry) {
+    fn format_line(&self, entry: &LogEntry) -> String {
+        match self.format {
+            LogFormat::Text => Self::plain_line(entry),
+            LogFormat::Jsonl => serde_json::to_string(entry).unwrap_or_default(),
+        }
+    }
+
+    fn plain_line(entry: &LogEntry) -> String {
         let timestamp = entry.timestamp.format("%H:%M:%S");
 
         if let Some(location) = entry.location_string() {
-            println!("[{}] {} ({})", timestamp, entry.message, location);
+            format!("[{}] {} ({})", timestamp, entry.message, location)
         } else {
-            println!("[{}] {}", timestamp, entry.raw_line);
+            format!("[{}] {}", timestamp, entry.raw_line)
         }
-
-        std::io::stdout().flush().unwrap();
     }
 }
 
@@ -76,3 +197,124 @@ impl Default for Logger {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_file_rotates_when_size_exceeded() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = dir.path().join("nested/logs");
+        let mut file = LogFile::create(&logs, "build_log", 32).unwrap();
+        let first = file.path().to_path_buf();
+
+        file.write_line("0123456789").unwrap();
+        file.write_line("0123456789").unwrap();
+        assert_eq!(file.path(), first);
+
+        file.write_line("0123456789").unwrap();
+        let second = file.path().to_path_buf();
+        assert_ne!(second, first);
+        assert!(second.to_string_lossy().ends_with(".1.txt"));
+
+        file.write_line("a line longer than the whole size limit")
+            .unwrap();
+        assert!(file.path().to_string_lossy().ends_with(".2.txt"));
+
+        assert_eq!(
+            std::fs::read_to_string(&first).unwrap(),
+            "0123456789\n0123456789\n"
+        );
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "0123456789\n");
+    }
+
+    #[test]
+    fn test_logger_mirrors_to_file_only_with_auto_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.logs.save_directory = dir.path().join("logs").to_string_lossy().to_string();
+
+        let logger = Logger::new_with_config(&config, "build_log").unwrap();
+        assert!(logger.file.is_none());
+
+        config.logs.auto_save = true;
+        let logger = Logger::new_with_config(&config, "build_log").unwrap();
+        logger.log(LogLevel::Info, "Starting build...");
+        logger.log_entry(&LogEntry::new(
+            LogLevel::Error,
+            "boom".to_string(),
+            "src/main.cpp:1:1: error: boom".to_string(),
+            LogComponent::Compiler,
+            0,
+        ));
+
+        let path = logger
+            .file
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .path()
+            .to_path_buf();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("Starting build...\n["));
+        assert!(content.contains("src/main.cpp:1:1: error: boom"));
+    }
+
+    #[test]
+    fn test_jsonl_output_round_trips_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.logs.save_directory = dir.path().to_string_lossy().to_string();
+        config.logs.auto_save = true;
+        config.logs.format = "jsonl".to_string();
+
+        let logger = Logger::new_with_config(&config, "build_log").unwrap();
+        let error = LogEntry::new(
+            LogLevel::Error,
+            "expected ';'".to_string(),
+            "src/main.cpp:7:1: error: expected ';'".to_string(),
+            LogComponent::Compiler,
+            3,
+        )
+        .with_location("src/main.cpp".to_string(), Some(7), Some(1));
+        logger.log(LogLevel::Info, "Starting build...");
+        logger.log_entry(&error);
+
+        let path = logger
+            .file
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .path()
+            .to_path_buf();
+        let entries: Vec<LogEntry> = std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "Starting build...");
+        assert_eq!(
+            entries[0].component,
+            LogComponent::Other("ignis".to_string())
+        );
+        assert_eq!(entries[1].level, LogLevel::Error);
+        assert_eq!(entries[1].component, LogComponent::Compiler);
+        assert_eq!(entries[1].message, error.message);
+        assert_eq!(entries[1].file_path.as_deref(), Some("src/main.cpp"));
+        assert_eq!(entries[1].line_number, Some(7));
+        assert_eq!(entries[1].column, Some(1));
+        assert_eq!(entries[1].timestamp, error.timestamp);
+    }
+
+    #[test]
+    fn test_unknown_log_format_is_rejected() {
+        let mut config = Config::default();
+        config.logs.format = "xml".to_string();
+        assert!(Logger::new_with_config(&config, "build_log").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::executor::MetricHistory;
+
+pub type MetricsSnapshot = Arc<Mutex<HashMap<String, MetricHistory>>>;
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn sanitize_label_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match sanitized.chars().next() {
+        Some(c) if !c.is_ascii_digit() => sanitized,
+        _ => format!("_{}", sanitized),
+    }
+}
+
+pub fn format_prometheus(metrics: &HashMap<String, MetricHistory>) -> String {
+    let mut series: Vec<(&String, &MetricHistory)> = metrics.iter().collect();
+    series.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut body = String::from("# TYPE ignis_metric gauge\n");
+    for (_, metric) in series {
+        let Some(value) = metric.latest_value() else {
+            continue;
+        };
+
+        let mut labels = vec![
+            format!("category=\"{}\"", escape_label(&metric.category)),
+            format!("key=\"{}\"", escape_label(&metric.key)),
+        ];
+        let mut tags: Vec<(&String, &String)> = metric.tags.iter().collect();
+        tags.sort();
+        labels.extend(tags.into_iter().map(|(name, value)| {
+            format!("{}=\"{}\"", sanitize_label_name(name), escape_label(value))
+        }));
+
+        body.push_str(&format!("ignis_metric{{{}}} {}\n", labels.join(","), value));
+    }
+    body
+}
+
+#[cfg(feature = "metrics-server")]
+pub async fn serve(port: u16, snapshot: MetricsSnapshot) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to bind metrics server to port {}", port))?;
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let snapshot = Arc::clone(&snapshot);
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await;
+
+            let body = format_prometheus(&snapshot.lock().unwrap());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[cfg(not(feature = "metrics-server"))]
+pub async fn serve(_port: u16, _snapshot: MetricsSnapshot) -> anyhow::Result<()> {
+    anyhow::bail!("ignis was built without the metrics-server feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::{MetricType, MetricVisualization};
+    use std::time::Instant;
+
+    fn metric(category: &str, key: &str, values: &[f64]) -> MetricHistory {
+        let mut metric = MetricHistory::new(
+            category.to_string(),
+            key.to_string(),
+            MetricType::Generic,
+            MetricVisualization::Auto,
+        );
+        for value in values {
+            metric.add_value(*value, Instant::now());
+        }
+        metric
+    }
+
+    #[test]
+    fn test_format_prometheus() {
+        let mut metrics = HashMap::new();
+        metrics.insert("perf:fps".to_string(), metric("perf", "fps", &[58.0, 60.0]));
+        metrics.insert(
+            "perf:frame_ms".to_string(),
+            metric("perf", "frame_ms", &[16.6]),
+        );
+        metrics.insert(
+            "physics:velocity{body=player}".to_string(),
+            metric("physics", "velocity", &[12.5]).with_tags(HashMap::from([(
+                "body".to_string(),
+                "pla\"yer".to_string(),
+            )])),
+        );
+        metrics.insert(
+            "render:draws{pass.name=main,2d=yes}".to_string(),
+            metric("render", "draws", &[42.0]).with_tags(HashMap::from([
+                ("pass.name".to_string(), "a\\b".to_string()),
+                ("2d".to_string(), "yes".to_string()),
+            ])),
+        );
+        metrics.insert("empty:none".to_string(), metric("empty", "none", &[]));
+
+        assert_eq!(
+            format_prometheus(&metrics),
+            "# TYPE ignis_metric gauge\n\
+             ignis_metric{category=\"perf\",key=\"fps\"} 60\n\
+             ignis_metric{category=\"perf\",key=\"frame_ms\"} 16.6\n\
+             ignis_metric{category=\"physics\",key=\"velocity\",body=\"pla\\\"yer\"} 12.5\n\
+             ignis_metric{category=\"render\",key=\"draws\",_2d=\"yes\",pass_name=\"a\\\\b\"} 42\n"
+        );
+    }
+}
@@ -1,8 +1,16 @@
+use crate::config::MonitorConfig;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use sysinfo::{CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, RefreshKind, System};
+use sysinfo::{
+    CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessStatus, RefreshKind, Signal,
+    System,
+};
+use tokio::sync::mpsc;
 use tokio::time::{interval, Duration};
 
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Clone)]
 pub struct ResourceSample {
     pub timestamp: f64,
@@ -11,11 +19,22 @@ pub struct ResourceSample {
     pub thread_count: usize,
     pub disk_read_bytes: u64,
     pub disk_write_bytes: u64,
+    pub gpu_usage: f32,
+    pub vram_mb: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessSample {
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory_mb: f64,
+    pub peak_memory_mb: f64,
 }
 
 #[derive(Debug, Clone)]
 pub struct ResourceStats {
     pub samples: Vec<ResourceSample>,
+    pub processes: HashMap<Pid, ProcessSample>,
     pub peak_cpu: f32,
     pub peak_memory_mb: f64,
     pub avg_cpu: f32,
@@ -25,12 +44,18 @@ pub struct ResourceStats {
     pub total_disk_read_mb: f64,
     pub total_disk_write_mb: f64,
     pub load_avg_1min: f64,
+    pub gpu_available: bool,
+    pub gpu_usage: f32,
+    pub vram_mb: f64,
+    pub peak_gpu_usage: f32,
+    pub peak_vram_mb: f64,
 }
 
 impl ResourceStats {
     pub fn new() -> Self {
         Self {
             samples: Vec::new(),
+            processes: HashMap::new(),
             peak_cpu: 0.0,
             peak_memory_mb: 0.0,
             avg_cpu: 0.0,
@@ -40,6 +65,11 @@ impl ResourceStats {
             total_disk_read_mb: 0.0,
             total_disk_write_mb: 0.0,
             load_avg_1min: 0.0,
+            gpu_available: false,
+            gpu_usage: 0.0,
+            vram_mb: 0.0,
+            peak_gpu_usage: 0.0,
+            peak_vram_mb: 0.0,
         }
     }
 
@@ -47,6 +77,10 @@ impl ResourceStats {
         self.peak_cpu = self.peak_cpu.max(sample.cpu_usage);
         self.peak_memory_mb = self.peak_memory_mb.max(sample.memory_mb);
         self.peak_threads = self.peak_threads.max(sample.thread_count);
+        self.gpu_usage = sample.gpu_usage;
+        self.vram_mb = sample.vram_mb;
+        self.peak_gpu_usage = self.peak_gpu_usage.max(sample.gpu_usage);
+        self.peak_vram_mb = self.peak_vram_mb.max(sample.vram_mb);
 
         if let Some(last) = self.samples.last() {
             self.total_disk_read_mb += (sample.disk_read_bytes.saturating_sub(last.disk_read_bytes))
@@ -65,6 +99,34 @@ impl ResourceStats {
         self.update_averages();
     }
 
+    pub fn record_process(&mut self, pid: Pid, name: &str, cpu_usage: f32, memory_mb: f64) {
+        let process = self.processes.entry(pid).or_insert_with(|| ProcessSample {
+            name: name.to_string(),
+            cpu_usage,
+            memory_mb,
+            peak_memory_mb: memory_mb,
+        });
+        process.cpu_usage = cpu_usage;
+        process.memory_mb = memory_mb;
+        process.peak_memory_mb = process.peak_memory_mb.max(memory_mb);
+    }
+
+    pub fn retain_processes(&mut self, live: &[Pid]) {
+        self.processes.retain(|pid, _| live.contains(pid));
+    }
+
+    pub fn top_processes_by_memory(&self, n: usize) -> Vec<(Pid, &ProcessSample)> {
+        let mut processes: Vec<(Pid, &ProcessSample)> =
+            self.processes.iter().map(|(pid, p)| (*pid, p)).collect();
+        processes.sort_by(|a, b| {
+            b.1.peak_memory_mb
+                .total_cmp(&a.1.peak_memory_mb)
+                .then(a.0.cmp(&b.0))
+        });
+        processes.truncate(n);
+        processes
+    }
+
     fn update_averages(&mut self) {
         if self.samples.is_empty() {
             return;
@@ -83,11 +145,278 @@ impl ResourceStats {
     }
 }
 
+pub fn samples_to_csv(samples: &[ResourceSample]) -> String {
+    let mut csv =
+        String::from("timestamp,cpu,memory_mb,threads,disk_read_bytes,disk_write_bytes\n");
+    for sample in samples {
+        csv.push_str(&format!(
+            "{:.3},{:.2},{:.2},{},{},{}\n",
+            sample.timestamp,
+            sample.cpu_usage,
+            sample.memory_mb,
+            sample.thread_count,
+            sample.disk_read_bytes,
+            sample.disk_write_bytes
+        ));
+    }
+    csv
+}
+
+#[derive(Debug, Clone)]
+pub struct StageWindow {
+    pub description: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceAdvisory {
+    SingleThreaded {
+        stage: Option<String>,
+        duration: f64,
+    },
+    MemoryPressure {
+        stage: Option<String>,
+        peak_mb: f64,
+        cap_mb: f64,
+    },
+}
+
+impl std::fmt::Display for ResourceAdvisory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceAdvisory::SingleThreaded { stage, duration } => write!(
+                f,
+                "{} ran single-threaded for {:.0}s",
+                stage.as_deref().unwrap_or("Build"),
+                duration
+            ),
+            ResourceAdvisory::MemoryPressure {
+                stage,
+                peak_mb,
+                cap_mb,
+            } => {
+                write!(f, "Peak memory {}", format_memory(*peak_mb))?;
+                if let Some(stage) = stage {
+                    write!(f, " during {}", stage)?;
+                }
+                write!(f, " (cap {})", format_memory(*cap_mb))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertKind {
+    Memory,
+    Cpu,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceAlert {
+    pub kind: AlertKind,
+    pub threshold: f64,
+    pub timestamp: f64,
+}
+
+impl std::fmt::Display for ResourceAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            AlertKind::Memory => write!(
+                f,
+                "⚠ memory exceeded {:.0}MB at {:.1}s",
+                self.threshold, self.timestamp
+            ),
+            AlertKind::Cpu => write!(
+                f,
+                "⚠ cpu exceeded {:.0}% at {:.1}s",
+                self.threshold, self.timestamp
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlertTracker {
+    memory_mb: Option<f64>,
+    cpu_pct: Option<f32>,
+    memory_breached: bool,
+    cpu_breached: bool,
+}
+
+impl AlertTracker {
+    pub fn new(config: &MonitorConfig) -> Self {
+        Self {
+            memory_mb: config.alert_memory_mb,
+            cpu_pct: config.alert_cpu_pct,
+            ..Self::default()
+        }
+    }
+
+    // An alert fires when a sample crosses a threshold and is re-armed only once a
+    // sample drops back below it, so a sustained breach produces a single alert.
+    pub fn check(&mut self, sample: &ResourceSample) -> Vec<ResourceAlert> {
+        let mut alerts = Vec::new();
+
+        if let Some(limit) = self.memory_mb {
+            let breached = sample.memory_mb > limit;
+            if breached && !self.memory_breached {
+                alerts.push(ResourceAlert {
+                    kind: AlertKind::Memory,
+                    threshold: limit,
+                    timestamp: sample.timestamp,
+                });
+            }
+            self.memory_breached = breached;
+        }
+
+        if let Some(limit) = self.cpu_pct {
+            let breached = sample.cpu_usage > limit;
+            if breached && !self.cpu_breached {
+                alerts.push(ResourceAlert {
+                    kind: AlertKind::Cpu,
+                    threshold: limit as f64,
+                    timestamp: sample.timestamp,
+                });
+            }
+            self.cpu_breached = breached;
+        }
+
+        alerts
+    }
+}
+
+const SINGLE_THREAD_CPU_MIN: f32 = 85.0;
+const SINGLE_THREAD_CPU_MAX: f32 = 115.0;
+const MEMORY_CAP_RATIO: f64 = 0.9;
+
+fn format_memory(mb: f64) -> String {
+    if mb >= 1024.0 {
+        format!("{:.1}GB", mb / 1024.0)
+    } else {
+        format!("{:.0}MB", mb)
+    }
+}
+
+fn stage_at(windows: &[StageWindow], timestamp: f64) -> Option<&str> {
+    windows
+        .iter()
+        .find(|w| w.start <= timestamp && timestamp <= w.end)
+        .map(|w| w.description.as_str())
+}
+
+pub fn detect_advisories(
+    samples: &[ResourceSample],
+    windows: &[StageWindow],
+    config: &MonitorConfig,
+) -> Vec<ResourceAdvisory> {
+    let mut advisories = Vec::new();
+
+    let mut push_run = |run: &[ResourceSample]| {
+        if let (Some(first), Some(last)) = (run.first(), run.last()) {
+            let duration = last.timestamp - first.timestamp;
+            if duration >= config.single_thread_min_secs {
+                advisories.push(ResourceAdvisory::SingleThreaded {
+                    stage: stage_at(windows, first.timestamp).map(str::to_string),
+                    duration,
+                });
+            }
+        }
+    };
+
+    let mut run_start: Option<usize> = None;
+    for (i, sample) in samples.iter().enumerate() {
+        let pegged = (SINGLE_THREAD_CPU_MIN..=SINGLE_THREAD_CPU_MAX).contains(&sample.cpu_usage);
+
+        if let Some(start) = run_start {
+            let same_stage =
+                stage_at(windows, samples[start].timestamp) == stage_at(windows, sample.timestamp);
+            if !pegged || !same_stage {
+                push_run(&samples[start..i]);
+                run_start = None;
+            }
+        }
+
+        if pegged && run_start.is_none() {
+            run_start = Some(i);
+        }
+    }
+    if let Some(start) = run_start {
+        push_run(&samples[start..]);
+    }
+
+    if let Some(cap_mb) = config.memory_cap_mb {
+        let peak = samples
+            .iter()
+            .max_by(|a, b| a.memory_mb.total_cmp(&b.memory_mb));
+        if let Some(peak) = peak.filter(|p| p.memory_mb >= cap_mb * MEMORY_CAP_RATIO) {
+            advisories.push(ResourceAdvisory::MemoryPressure {
+                stage: stage_at(windows, peak.timestamp).map(str::to_string),
+                peak_mb: peak.memory_mb,
+                cap_mb,
+            });
+        }
+    }
+
+    advisories
+}
+
+struct GpuSampler {
+    #[cfg(feature = "gpu")]
+    nvml: Option<nvml_wrapper::Nvml>,
+}
+
+impl GpuSampler {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "gpu")]
+            nvml: nvml_wrapper::Nvml::init().ok(),
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    fn sample(&self) -> Option<(f32, f64)> {
+        let nvml = self.nvml.as_ref()?;
+        let count = nvml.device_count().ok().filter(|&count| count > 0)?;
+
+        let mut usage = 0.0f32;
+        let mut vram_mb = 0.0f64;
+        for index in 0..count {
+            let device = nvml.device_by_index(index).ok()?;
+            usage += device.utilization_rates().ok()?.gpu as f32;
+            vram_mb += device.memory_info().ok()?.used as f64 / 1024.0 / 1024.0;
+        }
+
+        Some((usage / count as f32, vram_mb))
+    }
+
+    #[cfg(not(feature = "gpu"))]
+    fn sample(&self) -> Option<(f32, f64)> {
+        None
+    }
+}
+
+fn with_descendants(sys: &System, roots: &[Pid]) -> Vec<Pid> {
+    let mut pids = roots.to_vec();
+    let mut index = 0;
+    while index < pids.len() {
+        let parent = pids[index];
+        for (pid, process) in sys.processes() {
+            if process.parent() == Some(parent) && !pids.contains(pid) {
+                pids.push(*pid);
+            }
+        }
+        index += 1;
+    }
+    pids
+}
+
 #[derive(Clone)]
 pub struct ResourceMonitor {
     stats: Arc<Mutex<ResourceStats>>,
     tracked_pids: Arc<Mutex<Vec<Pid>>>,
     start_time: Instant,
+    alerts: Option<(AlertTracker, mpsc::UnboundedSender<ResourceAlert>)>,
 }
 
 impl ResourceMonitor {
@@ -96,9 +425,29 @@ impl ResourceMonitor {
             stats: Arc::new(Mutex::new(ResourceStats::new())),
             tracked_pids: Arc::new(Mutex::new(Vec::new())),
             start_time: Instant::now(),
+            alerts: None,
         }
     }
 
+    pub fn with_alerts(
+        mut self,
+        config: &MonitorConfig,
+        alert_tx: mpsc::UnboundedSender<ResourceAlert>,
+    ) -> Self {
+        self.alerts = Some((AlertTracker::new(config), alert_tx));
+        self
+    }
+
+    pub fn elapsed(&self) -> f64 {
+        self.start_time.elapsed().as_secs_f64()
+    }
+
+    pub fn offset_of(&self, instant: Instant) -> f64 {
+        instant
+            .saturating_duration_since(self.start_time)
+            .as_secs_f64()
+    }
+
     pub fn add_pid(&self, pid: u32) {
         if let Ok(mut pids) = self.tracked_pids.lock() {
             pids.push(Pid::from_u32(pid));
@@ -111,7 +460,67 @@ impl ResourceMonitor {
         }
     }
 
-    pub fn start_monitoring(self) -> tokio::task::JoinHandle<()> {
+    pub fn tracked_pids(&self) -> Vec<u32> {
+        self.tracked_pids
+            .lock()
+            .map(|pids| pids.iter().map(|p| p.as_u32()).collect())
+            .unwrap_or_default()
+    }
+
+    // Descendants are collected before anything is signalled: once a tracked
+    // process dies its children are re-parented and can no longer be found.
+    pub async fn kill_tracked(&self) -> usize {
+        let roots = self
+            .tracked_pids
+            .lock()
+            .map(|mut pids| std::mem::take(&mut *pids))
+            .unwrap_or_default();
+        if roots.is_empty() {
+            return 0;
+        }
+        for pid in &roots {
+            crate::executor::note_kill_requested(pid.as_u32());
+        }
+
+        let mut sys = System::new();
+        sys.refresh_processes();
+
+        let signalled: Vec<Pid> = with_descendants(&sys, &roots)
+            .into_iter()
+            .filter(|pid| {
+                sys.process(*pid).is_some_and(|process| {
+                    process
+                        .kill_with(Signal::Term)
+                        .unwrap_or_else(|| process.kill())
+                })
+            })
+            .collect();
+
+        let deadline = Instant::now() + KILL_GRACE;
+        loop {
+            sys.refresh_processes();
+            let survivors: Vec<_> = signalled
+                .iter()
+                .filter_map(|pid| sys.process(*pid))
+                .filter(|process| process.status() != ProcessStatus::Zombie)
+                .collect();
+
+            if survivors.is_empty() {
+                break;
+            }
+            if Instant::now() >= deadline {
+                for process in survivors {
+                    process.kill();
+                }
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        signalled.len()
+    }
+
+    pub fn start_monitoring(mut self) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
             let mut sys = System::new_with_specifics(
                 RefreshKind::new()
@@ -125,6 +534,8 @@ impl ResourceMonitor {
                     ),
             );
 
+            let gpu = GpuSampler::new();
+            let mut alerts = self.alerts.take();
             let mut interval = interval(Duration::from_millis(500));
 
             loop {
@@ -141,11 +552,19 @@ impl ResourceMonitor {
                 let mut total_threads = 0usize;
                 let mut total_disk_read = 0u64;
                 let mut total_disk_write = 0u64;
+                let mut processes = Vec::new();
 
                 for pid in &pids {
                     if let Some(process) = sys.process(*pid) {
+                        let memory_mb = process.memory() as f64 / 1024.0 / 1024.0;
+                        processes.push((
+                            *pid,
+                            process.name().to_string(),
+                            process.cpu_usage(),
+                            memory_mb,
+                        ));
                         total_cpu += process.cpu_usage();
-                        total_memory += process.memory() as f64 / 1024.0 / 1024.0;
+                        total_memory += memory_mb;
                         total_threads += process.tasks().map(|t| t.len()).unwrap_or(1);
                         total_disk_read += process.disk_usage().total_read_bytes;
                         total_disk_write += process.disk_usage().total_written_bytes;
@@ -153,6 +572,8 @@ impl ResourceMonitor {
                 }
 
                 let timestamp = self.start_time.elapsed().as_secs_f64();
+                let gpu_sample = gpu.sample();
+                let (gpu_usage, vram_mb) = gpu_sample.unwrap_or_default();
 
                 let sample = ResourceSample {
                     timestamp,
@@ -161,12 +582,26 @@ impl ResourceMonitor {
                     thread_count: total_threads,
                     disk_read_bytes: total_disk_read,
                     disk_write_bytes: total_disk_write,
+                    gpu_usage,
+                    vram_mb,
                 };
 
                 let load_avg = System::load_average().one;
 
+                if let Some((tracker, alert_tx)) = alerts.as_mut() {
+                    for alert in tracker.check(&sample) {
+                        let _ = alert_tx.send(alert);
+                    }
+                }
+
                 if let Ok(mut stats) = self.stats.lock() {
                     stats.load_avg_1min = load_avg;
+                    stats.gpu_available |= gpu_sample.is_some();
+                    let live: Vec<Pid> = processes.iter().map(|(pid, ..)| *pid).collect();
+                    stats.retain_processes(&live);
+                    for (pid, name, cpu_usage, memory_mb) in &processes {
+                        stats.record_process(*pid, name, *cpu_usage, *memory_mb);
+                    }
                     stats.add_sample(sample);
                 }
             }
@@ -177,3 +612,223 @@ impl ResourceMonitor {
         self.stats.lock().unwrap().clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(cpu: &[f32], memory_mb: f64) -> Vec<ResourceSample> {
+        cpu.iter()
+            .enumerate()
+            .map(|(i, &cpu_usage)| ResourceSample {
+                timestamp: i as f64,
+                cpu_usage,
+                memory_mb,
+                thread_count: 1,
+                disk_read_bytes: 0,
+                disk_write_bytes: 0,
+                gpu_usage: 0.0,
+                vram_mb: 0.0,
+            })
+            .collect()
+    }
+
+    fn window(description: &str, start: f64, end: f64) -> StageWindow {
+        StageWindow {
+            description: description.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn config(single_thread_min_secs: f64, memory_cap_mb: Option<f64>) -> MonitorConfig {
+        MonitorConfig {
+            memory_cap_mb,
+            single_thread_min_secs,
+            ..MonitorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_single_threaded_run() {
+        let samples = samples(&[400.0, 99.0, 100.0, 101.0, 98.0, 350.0], 100.0);
+        let windows = vec![window("Configuring engine", 0.0, 10.0)];
+
+        let advisories = detect_advisories(&samples, &windows, &config(3.0, None));
+        assert_eq!(
+            advisories,
+            vec![ResourceAdvisory::SingleThreaded {
+                stage: Some("Configuring engine".to_string()),
+                duration: 3.0,
+            }]
+        );
+        assert_eq!(
+            advisories[0].to_string(),
+            "Configuring engine ran single-threaded for 3s"
+        );
+    }
+
+    #[test]
+    fn test_short_single_threaded_run_is_ignored() {
+        let samples = samples(&[100.0, 100.0, 400.0, 100.0], 100.0);
+
+        assert!(detect_advisories(&samples, &[], &config(3.0, None)).is_empty());
+    }
+
+    #[test]
+    fn test_single_threaded_run_splits_at_stage_boundary() {
+        let samples = samples(&[100.0; 8], 100.0);
+        let windows = vec![window("Configuring", 0.0, 3.5), window("Linking", 3.5, 8.0)];
+
+        let advisories = detect_advisories(&samples, &windows, &config(3.0, None));
+        assert_eq!(advisories.len(), 2);
+        assert!(matches!(
+            &advisories[1],
+            ResourceAdvisory::SingleThreaded { stage: Some(s), .. } if s == "Linking"
+        ));
+    }
+
+    #[test]
+    fn test_add_sample_tracks_gpu_peaks() {
+        let mut stats = ResourceStats::new();
+        for (gpu_usage, vram_mb) in [(40.0, 1024.0), (95.0, 3072.0), (60.0, 2048.0)] {
+            stats.add_sample(ResourceSample {
+                gpu_usage,
+                vram_mb,
+                ..samples(&[100.0], 100.0).remove(0)
+            });
+        }
+
+        assert_eq!(stats.gpu_usage, 60.0);
+        assert_eq!(stats.vram_mb, 2048.0);
+        assert_eq!(stats.peak_gpu_usage, 95.0);
+        assert_eq!(stats.peak_vram_mb, 3072.0);
+        assert!(!stats.gpu_available);
+    }
+
+    #[test]
+    fn test_alert_tracker_debounces_sustained_breach() {
+        let mut tracker = AlertTracker::new(&MonitorConfig {
+            alert_memory_mb: Some(1500.0),
+            alert_cpu_pct: Some(400.0),
+            ..MonitorConfig::default()
+        });
+        let memory = [1000.0, 1600.0, 1700.0, 1800.0, 1200.0, 1550.0];
+
+        let alerts: Vec<Vec<ResourceAlert>> = samples(&[100.0; 6], 0.0)
+            .into_iter()
+            .zip(memory)
+            .map(|(sample, memory_mb)| {
+                tracker.check(&ResourceSample {
+                    memory_mb,
+                    ..sample
+                })
+            })
+            .collect();
+
+        let fired: Vec<usize> = (0..alerts.len())
+            .filter(|&i| !alerts[i].is_empty())
+            .collect();
+        assert_eq!(fired, vec![1, 5]);
+        assert_eq!(alerts[1][0].kind, AlertKind::Memory);
+        assert_eq!(alerts[1][0].to_string(), "⚠ memory exceeded 1500MB at 1.0s");
+
+        let cpu_alerts = tracker.check(&ResourceSample {
+            cpu_usage: 450.0,
+            ..samples(&[0.0], 0.0).remove(0)
+        });
+        assert_eq!(cpu_alerts.len(), 1);
+        assert_eq!(cpu_alerts[0].to_string(), "⚠ cpu exceeded 400% at 0.0s");
+    }
+
+    #[test]
+    fn test_alert_tracker_ignores_unset_thresholds() {
+        let mut tracker = AlertTracker::new(&MonitorConfig::default());
+        for sample in samples(&[10_000.0], 1_000_000.0) {
+            assert!(tracker.check(&sample).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_top_processes_by_memory() {
+        let mut stats = ResourceStats::new();
+        stats.record_process(Pid::from_u32(10), "ninja", 5.0, 20.0);
+        stats.record_process(Pid::from_u32(11), "cc1plus", 100.0, 900.0);
+        stats.record_process(Pid::from_u32(12), "ld", 100.0, 1500.0);
+        stats.record_process(Pid::from_u32(13), "cc1plus", 100.0, 600.0);
+        stats.record_process(Pid::from_u32(11), "cc1plus", 50.0, 300.0);
+
+        let top = stats.top_processes_by_memory(3);
+        let pids: Vec<u32> = top.iter().map(|(pid, _)| pid.as_u32()).collect();
+        assert_eq!(pids, vec![12, 11, 13]);
+        assert_eq!(top[1].1.memory_mb, 300.0);
+        assert_eq!(top[1].1.peak_memory_mb, 900.0);
+        assert_eq!(top[1].1.cpu_usage, 50.0);
+
+        stats.retain_processes(&[Pid::from_u32(11), Pid::from_u32(99)]);
+        let pids: Vec<u32> = stats.processes.keys().map(|pid| pid.as_u32()).collect();
+        assert_eq!(pids, vec![11]);
+    }
+
+    #[test]
+    fn test_samples_to_csv() {
+        let mut samples = samples(&[150.5, 99.0], 512.25);
+        samples[1].timestamp = 0.5;
+        samples[1].thread_count = 8;
+        samples[1].disk_read_bytes = 4096;
+        samples[1].disk_write_bytes = 1024;
+
+        assert_eq!(
+            samples_to_csv(&samples),
+            "timestamp,cpu,memory_mb,threads,disk_read_bytes,disk_write_bytes\n\
+             0.000,150.50,512.25,1,0,0\n\
+             0.500,99.00,512.25,8,4096,1024\n"
+        );
+        assert_eq!(samples_to_csv(&[]).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_detects_memory_pressure() {
+        let mut samples = samples(&[400.0, 400.0, 400.0], 1000.0);
+        samples[1].memory_mb = 7987.2;
+        let windows = vec![window("Linking editor", 0.0, 3.0)];
+
+        let advisories = detect_advisories(&samples, &windows, &config(10.0, Some(8192.0)));
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(
+            advisories[0].to_string(),
+            "Peak memory 7.8GB during Linking editor (cap 8.0GB)"
+        );
+
+        assert!(detect_advisories(&samples, &windows, &config(10.0, Some(16384.0))).is_empty());
+        assert!(detect_advisories(&samples, &windows, &config(10.0, None)).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_kill_tracked_stops_tracked_processes_and_children() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let monitor = ResourceMonitor::new();
+        assert_eq!(monitor.kill_tracked().await, 0);
+
+        let mut untracked = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut tracked = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .spawn()
+            .unwrap();
+        monitor.add_pid(tracked.id());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(monitor.kill_tracked().await, 2);
+        assert!(monitor.tracked_pids().is_empty());
+        assert_eq!(tracked.wait().unwrap().signal(), Some(15));
+        assert!(untracked.try_wait().unwrap().is_none());
+
+        untracked.kill().unwrap();
+        untracked.wait().unwrap();
+    }
+}
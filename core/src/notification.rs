@@ -0,0 +1,72 @@
+use std::io::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildOutcome {
+    pub success: bool,
+    pub duration: f64,
+    pub preset: String,
+    pub error_count: usize,
+}
+
+impl BuildOutcome {
+    pub fn summary(&self) -> String {
+        if self.success {
+            format!("Build succeeded in {:.0}s [{}]", self.duration, self.preset)
+        } else {
+            format!(
+                "Build FAILED: {} error{} [{}]",
+                self.error_count,
+                if self.error_count == 1 { "" } else { "s" },
+                self.preset
+            )
+        }
+    }
+}
+
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+#[cfg(feature = "desktop-notify")]
+pub fn send_desktop(outcome: &BuildOutcome) -> anyhow::Result<()> {
+    notify_rust::Notification::new()
+        .summary("ignis")
+        .body(&outcome.summary())
+        .show()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "desktop-notify"))]
+pub fn send_desktop(_outcome: &BuildOutcome) -> anyhow::Result<()> {
+    Ok(())
+}
+
+pub fn notify_build_complete(outcome: &BuildOutcome) {
+    ring_bell();
+    let _ = send_desktop(outcome);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_summary() {
+        let mut outcome = BuildOutcome {
+            success: true,
+            duration: 42.3,
+            preset: "debug".to_string(),
+            error_count: 0,
+        };
+        assert_eq!(outcome.summary(), "Build succeeded in 42s [debug]");
+
+        outcome.success = false;
+        outcome.error_count = 3;
+        assert_eq!(outcome.summary(), "Build FAILED: 3 errors [debug]");
+
+        outcome.error_count = 1;
+        assert_eq!(outcome.summary(), "Build FAILED: 1 error [debug]");
+    }
+}
@@ -22,12 +22,30 @@ impl LogLevel {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamKind {
+    #[default]
+    Stdout,
+    Stderr,
+}
+
+impl StreamKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "stdout" => Some(StreamKind::Stdout),
+            "stderr" => Some(StreamKind::Stderr),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LogComponent {
     CMake,
     Compiler,
     Linker,
     Build,
+    Test,
     Other(String),
 }
 
@@ -43,6 +61,16 @@ pub struct LogEntry {
     pub component: LogComponent,
     pub tags: Vec<String>,
     pub index: usize,
+    #[serde(default)]
+    pub entry_id: usize,
+    #[serde(default)]
+    pub stream: StreamKind,
+    #[serde(default = "default_repeat_count")]
+    pub repeat_count: usize,
+}
+
+fn default_repeat_count() -> usize {
+    1
 }
 
 impl LogEntry {
@@ -64,6 +92,9 @@ impl LogEntry {
             component,
             tags: Vec::new(),
             index,
+            entry_id: 0,
+            stream: StreamKind::Stdout,
+            repeat_count: 1,
         }
     }
 
@@ -84,6 +115,29 @@ impl LogEntry {
         self
     }
 
+    pub fn with_stream(mut self, stream: StreamKind) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.message = format!("[{}] {}", prefix, self.message);
+        self.raw_line = format!("[{}] {}", prefix, self.raw_line);
+        self
+    }
+
+    pub fn is_repeat_of(&self, other: &LogEntry) -> bool {
+        self.message == other.message
+            && self.level == other.level
+            && self.component == other.component
+            && self.file_path == other.file_path
+            && self.line_number == other.line_number
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
     pub fn location_string(&self) -> Option<String> {
         self.file_path.as_ref().map(|path| {
             let mut loc = path.clone();
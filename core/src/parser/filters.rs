@@ -1,44 +1,110 @@
-use super::entry::{LogEntry, LogLevel, LogComponent};
+use super::entry::{LogEntry, LogLevel, LogComponent, StreamKind};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 pub trait LogFilter: Send + Sync {
     fn matches(&self, entry: &LogEntry) -> bool;
     fn description(&self) -> String;
+
+    fn spec(&self) -> Option<FilterSpec> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterSpec {
+    Level(LogLevel),
+    OnlyLevel(LogLevel),
+    Pattern { pattern: String, case_sensitive: bool },
+    File(String),
+    Component(LogComponent),
+    Stream(StreamKind),
+    All(Vec<FilterSpec>),
+    Any(Vec<FilterSpec>),
+}
+
+impl FilterSpec {
+    pub fn build(&self) -> Option<Box<dyn LogFilter>> {
+        let filter: Box<dyn LogFilter> = match self {
+            Self::Level(level) => Box::new(LevelFilter::new(*level)),
+            Self::OnlyLevel(level) => Box::new(LevelFilter::only(*level)),
+            Self::Pattern {
+                pattern,
+                case_sensitive,
+            } => Box::new(PatternFilter::new(pattern, *case_sensitive).ok()?),
+            Self::File(pattern) => Box::new(FileFilter::new(pattern).ok()?),
+            Self::Component(component) => Box::new(ComponentFilter::new(component.clone())),
+            Self::Stream(stream) => Box::new(StreamFilter::new(*stream)),
+            Self::All(specs) => Box::new(CompositeFilter::and(build_all(specs)?)),
+            Self::Any(specs) => Box::new(CompositeFilter::or(build_all(specs)?)),
+        };
+        Some(filter)
+    }
+}
+
+fn build_all(specs: &[FilterSpec]) -> Option<Vec<Box<dyn LogFilter>>> {
+    specs.iter().map(FilterSpec::build).collect()
 }
 
 pub struct LevelFilter {
     min_level: LogLevel,
+    exact: bool,
 }
 
 impl LevelFilter {
     pub fn new(min_level: LogLevel) -> Self {
-        Self { min_level }
+        Self {
+            min_level,
+            exact: false,
+        }
+    }
+
+    pub fn only(level: LogLevel) -> Self {
+        Self {
+            min_level: level,
+            exact: true,
+        }
     }
 }
 
 impl LogFilter for LevelFilter {
     fn matches(&self, entry: &LogEntry) -> bool {
-        entry.level >= self.min_level
+        if self.exact {
+            entry.level == self.min_level
+        } else {
+            entry.level >= self.min_level
+        }
     }
 
     fn description(&self) -> String {
-        format!("level >= {}", self.min_level.to_str())
+        let op = if self.exact { "=" } else { ">=" };
+        format!("level {} {}", op, self.min_level.to_str())
+    }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        Some(if self.exact {
+            FilterSpec::OnlyLevel(self.min_level)
+        } else {
+            FilterSpec::Level(self.min_level)
+        })
     }
 }
 
 pub struct PatternFilter {
+    source: String,
     pattern: Regex,
     case_sensitive: bool,
 }
 
 impl PatternFilter {
-    pub fn new(pattern: &str, case_sensitive: bool) -> Result<Self, regex::Error> {
+    pub fn new(source: &str, case_sensitive: bool) -> Result<Self, regex::Error> {
         let pattern = if case_sensitive {
-            Regex::new(pattern)?
+            Regex::new(source)?
         } else {
-            Regex::new(&format!("(?i){}", pattern))?
+            Regex::new(&format!("(?i){}", source))?
         };
         Ok(Self {
+            source: source.to_string(),
             pattern,
             case_sensitive,
         })
@@ -61,6 +127,13 @@ impl LogFilter for PatternFilter {
             }
         )
     }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        Some(FilterSpec::Pattern {
+            pattern: self.source.clone(),
+            case_sensitive: self.case_sensitive,
+        })
+    }
 }
 
 pub struct FileFilter {
@@ -87,6 +160,10 @@ impl LogFilter for FileFilter {
     fn description(&self) -> String {
         format!("file matches: {}", self.file_pattern.as_str())
     }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        Some(FilterSpec::File(self.file_pattern.as_str().to_string()))
+    }
 }
 
 pub struct ComponentFilter {
@@ -107,6 +184,34 @@ impl LogFilter for ComponentFilter {
     fn description(&self) -> String {
         format!("component: {:?}", self.component)
     }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        Some(FilterSpec::Component(self.component.clone()))
+    }
+}
+
+pub struct StreamFilter {
+    stream: StreamKind,
+}
+
+impl StreamFilter {
+    pub fn new(stream: StreamKind) -> Self {
+        Self { stream }
+    }
+}
+
+impl LogFilter for StreamFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        entry.stream == self.stream
+    }
+
+    fn description(&self) -> String {
+        format!("stream: {:?}", self.stream).to_lowercase()
+    }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        Some(FilterSpec::Stream(self.stream))
+    }
 }
 
 pub struct CompositeFilter {
@@ -149,4 +254,16 @@ impl LogFilter for CompositeFilter {
         let descriptions: Vec<_> = self.filters.iter().map(|f| f.description()).collect();
         format!("({})", descriptions.join(&format!(" {} ", mode)))
     }
+
+    fn spec(&self) -> Option<FilterSpec> {
+        let specs = self
+            .filters
+            .iter()
+            .map(|f| f.spec())
+            .collect::<Option<Vec<_>>>()?;
+        Some(match self.mode {
+            FilterMode::And => FilterSpec::All(specs),
+            FilterMode::Or => FilterSpec::Any(specs),
+        })
+    }
 }
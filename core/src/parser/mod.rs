@@ -2,6 +2,9 @@ pub mod entry;
 pub mod parser;
 pub mod filters;
 
-pub use entry::{LogEntry, LogLevel, LogComponent};
+pub use entry::{LogEntry, LogLevel, LogComponent, StreamKind};
 pub use parser::CompilerOutputParser;
-pub use filters::{LogFilter, LevelFilter, PatternFilter, FileFilter, ComponentFilter, CompositeFilter};
+pub use filters::{
+    LogFilter, LevelFilter, PatternFilter, FileFilter, ComponentFilter, CompositeFilter, StreamFilter,
+    FilterSpec,
+};
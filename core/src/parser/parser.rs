@@ -1,11 +1,17 @@
 use super::entry::{LogComponent, LogEntry, LogLevel};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 
 static GCC_CLANG_ERROR: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([^:]+):(\d+):(\d+): error: (.+)$").unwrap());
 static GCC_CLANG_WARNING: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([^:]+):(\d+):(\d+): warning: (.+)$").unwrap());
+static GCC_CLANG_FATAL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([^:]+):(\d+):(\d+): (?:fatal error|internal compiler error): (.+)$").unwrap()
+});
+static FATAL_ERROR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|: )(?:fatal error|internal compiler error): (.+)$").unwrap());
 static GCC_CLANG_NOTE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([^:]+):(\d+):(\d+): note: (.+)$").unwrap());
 static CMAKE_ERROR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^CMake Error").unwrap());
@@ -15,6 +21,22 @@ static LINKER_ERROR_UNDEF: Lazy<Regex> =
 static LINKER_ERROR_MULTI: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"multiple definition of").unwrap());
 
+static CTEST_RESULT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*\d+/\d+ Test\s+#\d+: (\S+) .*?(Passed|\*\*\*Failed|\*\*\*Timeout|\*\*\*Exception|Not Run)")
+        .unwrap()
+});
+static CTEST_SUMMARY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\d+% tests passed, (\d+) tests? failed out of (\d+)").unwrap());
+
+static OUT_OF_MEMORY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)out of memory|virtual memory exhausted|cannot allocate memory|terminated with signal 9|Killed signal terminated program",
+    )
+    .unwrap()
+});
+
+pub const OOM_DIAGNOSTIC: &str = "Build ran out of memory — reduce -j or increase RAM";
+
 static BUILD_PROGRESS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[(\d+)/(\d+)\]").unwrap());
 static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
 
@@ -33,6 +55,42 @@ impl CompilerOutputParser {
         let index = self.log_index;
         self.log_index += 1;
 
+        if OUT_OF_MEMORY.is_match(&stripped) {
+            return LogEntry::new(
+                LogLevel::Error,
+                format!("{} ({})", OOM_DIAGNOSTIC, stripped),
+                line.to_string(),
+                LogComponent::Compiler,
+                index,
+            )
+            .with_tags(vec!["oom".to_string()]);
+        }
+
+        if let Some(caps) = GCC_CLANG_FATAL.captures(&stripped) {
+            return LogEntry::new(
+                LogLevel::Fatal,
+                caps.get(4).unwrap().as_str().to_string(),
+                line.to_string(),
+                LogComponent::Compiler,
+                index,
+            )
+            .with_location(
+                caps.get(1).unwrap().as_str().to_string(),
+                caps.get(2).and_then(|m| m.as_str().parse().ok()),
+                caps.get(3).and_then(|m| m.as_str().parse().ok()),
+            );
+        }
+
+        if let Some(caps) = FATAL_ERROR.captures(&stripped) {
+            return LogEntry::new(
+                LogLevel::Fatal,
+                caps.get(1).unwrap().as_str().to_string(),
+                line.to_string(),
+                LogComponent::Compiler,
+                index,
+            );
+        }
+
         if let Some(caps) = GCC_CLANG_ERROR.captures(&stripped) {
             return LogEntry::new(
                 LogLevel::Error,
@@ -108,6 +166,47 @@ impl CompilerOutputParser {
             );
         }
 
+        if let Some(caps) = CTEST_RESULT.captures(&stripped) {
+            let name = caps.get(1).unwrap().as_str();
+            let (level, message, tag) = match caps.get(2).unwrap().as_str() {
+                "Passed" => (
+                    LogLevel::Info,
+                    format!("Test {} passed", name),
+                    "test-passed",
+                ),
+                outcome => (
+                    LogLevel::Error,
+                    format!(
+                        "Test {} {}",
+                        name,
+                        outcome.trim_start_matches('*').to_lowercase()
+                    ),
+                    "test-failed",
+                ),
+            };
+
+            return LogEntry::new(level, message, line.to_string(), LogComponent::Test, index)
+                .with_tags(vec![tag.to_string()]);
+        }
+
+        if let Some(caps) = CTEST_SUMMARY.captures(&stripped) {
+            let failed: usize = caps.get(1).unwrap().as_str().parse().unwrap_or(0);
+            let level = if failed > 0 {
+                LogLevel::Error
+            } else {
+                LogLevel::Info
+            };
+
+            return LogEntry::new(
+                level,
+                stripped.clone(),
+                line.to_string(),
+                LogComponent::Test,
+                index,
+            )
+            .with_tags(vec!["test-summary".to_string()]);
+        }
+
         if BUILD_PROGRESS.is_match(&stripped) {
             return LogEntry::new(
                 LogLevel::Info,
@@ -139,6 +238,10 @@ impl Default for CompilerOutputParser {
     }
 }
 
+pub fn is_out_of_memory(line: &str) -> bool {
+    OUT_OF_MEMORY.is_match(&strip_ansi(line))
+}
+
 fn strip_ansi(s: &str) -> String {
     ANSI_ESCAPE.replace_all(s, "").to_string()
 }
@@ -155,17 +258,11 @@ impl MetricParser {
 
             if parts.len() >= 2 {
                 let category = parts[0].to_string();
-                let kv: Vec<&str> = parts[1].split('=').collect();
-                if kv.len() == 2 {
-                    let key = kv[0].to_string();
-                    let value_and_viz: Vec<&str> = kv[1].split(':').collect();
-
-                    let value = value_and_viz[0].to_string();
-                    let explicit_visualization = if value_and_viz.len() > 1 {
-                        MetricVisualization::from_str(value_and_viz[1])
-                    } else {
-                        None
-                    };
+                if let Some((key, tags, value)) = Self::parse_key_value(parts[1]) {
+                    let explicit_visualization = parts
+                        .get(2)
+                        .and_then(|viz| MetricVisualization::from_str(viz));
+                    let range = Self::parse_range(parts.get(3).copied(), parts.get(4).copied());
 
                     return Some(crate::executor::RuntimeMetric {
                         key,
@@ -173,18 +270,133 @@ impl MetricParser {
                         timestamp: Instant::now(),
                         category,
                         explicit_visualization,
+                        range,
+                        tags,
                     });
                 }
             }
         }
         None
     }
+
+    fn parse_key_value(kv: &str) -> Option<(String, HashMap<String, String>, String)> {
+        let (key, tags, value) = match kv.find('{') {
+            Some(open) => {
+                let close = open + kv[open..].find('}')?;
+                let value = kv[close + 1..].strip_prefix('=')?;
+                (&kv[..open], Self::parse_tags(&kv[open + 1..close])?, value)
+            }
+            None => {
+                let (key, value) = kv.split_once('=')?;
+                (key, HashMap::new(), value)
+            }
+        };
+
+        if key.is_empty() || value.contains('=') {
+            return None;
+        }
+        Some((key.to_string(), tags, value.to_string()))
+    }
+
+    fn parse_tags(tags: &str) -> Option<HashMap<String, String>> {
+        tags.split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| {
+                let (name, value) = tag.split_once('=')?;
+                let (name, value) = (name.trim(), value.trim());
+                (!name.is_empty() && !value.is_empty())
+                    .then(|| (name.to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    fn parse_range(min: Option<&str>, max: Option<&str>) -> Option<(f64, f64)> {
+        let min = min?.trim().parse::<f64>().ok()?;
+        let max = max?.trim().parse::<f64>().ok()?;
+        (min.is_finite() && max.is_finite() && min < max).then_some((min, max))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_metric_line_with_visualization() {
+        use crate::executor::MetricVisualization;
+
+        let metric =
+            MetricParser::parse_metric_line("[IGNIS_METRIC] perf:frame_ms=16.6:histogram").unwrap();
+        assert_eq!(metric.category, "perf");
+        assert_eq!(metric.key, "frame_ms");
+        assert_eq!(metric.value, "16.6");
+        assert_eq!(metric.visualization(), MetricVisualization::Histogram);
+        assert_eq!(metric.range, None);
+    }
+
+    #[test]
+    fn test_metric_line_with_tags() {
+        let metric =
+            MetricParser::parse_metric_line("[IGNIS_METRIC] physics:velocity{body=player}=12.3")
+                .unwrap();
+        assert_eq!(metric.category, "physics");
+        assert_eq!(metric.key, "velocity");
+        assert_eq!(metric.value, "12.3");
+        assert_eq!(metric.tags.get("body").map(String::as_str), Some("player"));
+        assert_eq!(metric.series_key(), "physics:velocity{body=player}");
+
+        let metric = MetricParser::parse_metric_line(
+            "[IGNIS_METRIC] physics:velocity{ zone = b, body=enemy_1 }=4:sparkline",
+        )
+        .unwrap();
+        assert_eq!(metric.tags.len(), 2);
+        assert_eq!(metric.series_key(), "physics:velocity{body=enemy_1,zone=b}");
+
+        let metric =
+            MetricParser::parse_metric_line("[IGNIS_METRIC] physics:velocity{}=1").unwrap();
+        assert!(metric.tags.is_empty());
+        assert_eq!(metric.series_key(), "physics:velocity");
+
+        for line in [
+            "[IGNIS_METRIC] physics:velocity{body=player=12.3",
+            "[IGNIS_METRIC] physics:velocity{body}=12.3",
+            "[IGNIS_METRIC] physics:velocity{=player}=12.3",
+            "[IGNIS_METRIC] physics:velocity{body=player}12.3",
+            "[IGNIS_METRIC] physics:{body=player}=12.3",
+            "[IGNIS_METRIC] physics:velocity=1=2",
+        ] {
+            assert!(MetricParser::parse_metric_line(line).is_none(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_metric_line_with_range() {
+        use crate::executor::MetricVisualization;
+
+        let metric =
+            MetricParser::parse_metric_line("[IGNIS_METRIC] health:hp=75:gauge:0:200").unwrap();
+        assert_eq!(metric.value, "75");
+        assert_eq!(metric.visualization(), MetricVisualization::Gauge);
+        assert_eq!(metric.range, Some((0.0, 200.0)));
+
+        let metric =
+            MetricParser::parse_metric_line("[IGNIS_METRIC] temp:core=-5:gauge:-20.5:40").unwrap();
+        assert_eq!(metric.range, Some((-20.5, 40.0)));
+
+        for line in [
+            "[IGNIS_METRIC] health:hp=75:gauge:0",
+            "[IGNIS_METRIC] health:hp=75:gauge:low:high",
+            "[IGNIS_METRIC] health:hp=75:gauge:200:0",
+            "[IGNIS_METRIC] health:hp=75:gauge:5:5",
+            "[IGNIS_METRIC] health:hp=75:gauge:0:inf",
+        ] {
+            let metric = MetricParser::parse_metric_line(line).unwrap();
+            assert_eq!(metric.range, None, "{}", line);
+            assert_eq!(metric.visualization(), MetricVisualization::Gauge);
+        }
+    }
+
     #[test]
     fn test_gcc_error_parsing() {
         let mut parser = CompilerOutputParser::new();
@@ -198,6 +410,31 @@ mod tests {
         assert_eq!(entry.column, Some(10));
     }
 
+    #[test]
+    fn test_fatal_error_parsing() {
+        let mut parser = CompilerOutputParser::new();
+
+        let entry = parser.parse_line("fatal error: 'foo.h' file not found");
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.component, LogComponent::Compiler);
+        assert_eq!(entry.message, "'foo.h' file not found");
+
+        let entry = parser.parse_line("src/main.cpp:3:10: fatal error: 'foo.h' file not found");
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.file_path, Some("src/main.cpp".to_string()));
+        assert_eq!(entry.line_number, Some(3));
+
+        let entry = parser.parse_line(
+            "src/mesh.cpp:88:1: internal compiler error: in expand_expr_real_1, at expr.c:6010",
+        );
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.line_number, Some(88));
+
+        let entry = parser.parse_line("c++: fatal error: no input files");
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.message, "no input files");
+    }
+
     #[test]
     fn test_cmake_error_parsing() {
         let mut parser = CompilerOutputParser::new();
@@ -208,6 +445,66 @@ mod tests {
         assert_eq!(entry.component, LogComponent::CMake);
     }
 
+    #[test]
+    fn test_ctest_result_parsing() {
+        let mut parser = CompilerOutputParser::new();
+
+        let entry = parser
+            .parse_line("1/3 Test #1: math_tests .......................   Passed    0.01 sec");
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.component, LogComponent::Test);
+        assert_eq!(entry.message, "Test math_tests passed");
+
+        let entry = parser
+            .parse_line("2/3 Test #2: io_tests .........................***Failed    0.02 sec");
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.message, "Test io_tests failed");
+
+        let entry = parser
+            .parse_line("3/3 Test #3: slow_tests .......................***Timeout  10.00 sec");
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.message, "Test slow_tests timeout");
+    }
+
+    #[test]
+    fn test_ctest_summary_parsing() {
+        let mut parser = CompilerOutputParser::new();
+
+        let entry = parser.parse_line("100% tests passed, 0 tests failed out of 3");
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.component, LogComponent::Test);
+
+        let entry = parser.parse_line("67% tests passed, 1 tests failed out of 3");
+        assert_eq!(entry.level, LogLevel::Error);
+    }
+
+    #[test]
+    fn test_out_of_memory_signatures() {
+        let lines = [
+            "cc1plus: out of memory allocating 65536 bytes after a total of 1234 bytes",
+            "virtual memory exhausted: Cannot allocate memory",
+            "collect2: fatal error: ld terminated with signal 9 [Killed]",
+            "c++: fatal error: Killed signal terminated program cc1plus",
+        ];
+
+        let mut parser = CompilerOutputParser::new();
+        for line in lines {
+            assert!(is_out_of_memory(line), "{}", line);
+
+            let entry = parser.parse_line(line);
+            assert_eq!(entry.level, LogLevel::Error);
+            assert!(entry.message.starts_with(OOM_DIAGNOSTIC));
+            assert!(entry.tags.contains(&"oom".to_string()));
+        }
+
+        assert!(!is_out_of_memory(
+            "src/main.cpp:42:10: error: 'foo' was not declared"
+        ));
+        assert!(!is_out_of_memory(
+            "[12/40] Building CXX object memory_pool.cpp.o"
+        ));
+    }
+
     #[test]
     fn test_ansi_stripping() {
         let ansi_str = "\x1b[31mError:\x1b[0m Something went wrong";
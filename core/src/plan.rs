@@ -0,0 +1,134 @@
+use crate::{
+    builder::{BuildStep, Builder},
+    dependency_graph::{GraphError, StageDependencyGraph},
+    stage::Stage,
+    stage_context::StagePlanner,
+    ExecutableInfo,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedStage {
+    pub stage: Stage,
+    pub steps: Vec<BuildStep>,
+}
+
+/// Stages grouped into dependency layers; stages within a layer may run in parallel.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildPlan {
+    pub layers: Vec<Vec<PlannedStage>>,
+}
+
+impl BuildPlan {
+    pub fn resolve(
+        builder: &Builder,
+        stages: &[Stage],
+        exec_info: Option<&ExecutableInfo>,
+    ) -> Result<Self, GraphError> {
+        let layers =
+            StageDependencyGraph::from_config(stages.to_vec(), &builder.root().config.stages)?
+                .topological_sort()?;
+
+        let mut planner = StagePlanner::new(builder.clone());
+        if let Some(exec_info) = exec_info {
+            planner = planner.with_executable(exec_info.clone());
+        }
+
+        let layers = layers
+            .into_iter()
+            .map(|layer| {
+                layer
+                    .into_iter()
+                    .map(|stage| PlannedStage {
+                        stage,
+                        steps: planner.generate_steps_for_stage(stage),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self { layers })
+    }
+
+    pub fn stages(&self) -> impl Iterator<Item = &PlannedStage> {
+        self.layers.iter().flatten()
+    }
+
+    pub fn steps(&self) -> impl Iterator<Item = &BuildStep> {
+        self.stages().flat_map(|planned| planned.steps.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        target::{Target, TargetKind},
+        Config,
+    };
+
+    fn installer_builder(path: &std::path::Path) -> Builder {
+        let root = Target {
+            path: path.to_path_buf(),
+            kind: TargetKind::Root,
+            config: Config::default(),
+        };
+        let installer = Target {
+            path: path.to_path_buf(),
+            kind: TargetKind::Installer,
+            config: Config::default(),
+        };
+        Builder::new(root, "debug".to_string()).with_targets(vec![installer])
+    }
+
+    #[test]
+    fn test_plan_orders_configure_build_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let builder = installer_builder(dir.path());
+
+        let plan = builder
+            .plan(&[Stage::Install, Stage::Build, Stage::Configure])
+            .unwrap();
+
+        let order: Vec<Vec<Stage>> = plan
+            .layers
+            .iter()
+            .map(|layer| layer.iter().map(|planned| planned.stage).collect())
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                vec![Stage::Configure],
+                vec![Stage::Build],
+                vec![Stage::Install]
+            ]
+        );
+
+        for planned in plan.stages() {
+            assert_eq!(planned.steps.len(), 1, "{}", planned.stage);
+        }
+        let commands: Vec<&str> = plan.steps().map(|step| step.commands[0].as_str()).collect();
+        assert_eq!(commands, ["cmake"; 3]);
+        assert!(plan.layers[1][0].steps[0]
+            .commands
+            .contains(&"--build".to_string()));
+        assert!(plan.layers[2][0].steps[0]
+            .commands
+            .contains(&"--install".to_string()));
+    }
+
+    #[test]
+    fn test_plan_keeps_stages_without_steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let builder = installer_builder(dir.path());
+
+        let plan = builder
+            .plan(&[Stage::PreValidation, Stage::Configure])
+            .unwrap();
+
+        assert_eq!(plan.layers.len(), 2);
+        assert_eq!(plan.layers[0][0].stage, Stage::PreValidation);
+        assert!(plan.layers[0][0].steps.is_empty());
+        assert_eq!(plan.steps().count(), 1);
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+pub const PRESET_FILES: [&str; 2] = ["CMakePresets.json", "CMakeUserPresets.json"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurePreset {
+    pub name: String,
+    pub binary_dir: Option<String>,
+}
+
+impl ConfigurePreset {
+    pub fn binary_dir_in(&self, source_dir: &Path) -> Option<PathBuf> {
+        let expanded = expand_macros(self.binary_dir.as_deref()?, &self.name, source_dir);
+        let path = PathBuf::from(expanded);
+
+        if path.is_absolute() {
+            Some(path)
+        } else {
+            Some(source_dir.join(path))
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PresetsFile {
+    #[serde(default, rename = "configurePresets")]
+    configure_presets: Vec<RawPreset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawPreset {
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    inherits: Inherits,
+    #[serde(default, rename = "binaryDir")]
+    binary_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(untagged)]
+enum Inherits {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Inherits {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            Inherits::None => Vec::new(),
+            Inherits::One(name) => vec![name.as_str()],
+            Inherits::Many(names) => names.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+fn parse_raw(json: &str) -> anyhow::Result<Vec<RawPreset>> {
+    let file: PresetsFile = serde_json::from_str(json)?;
+    Ok(file.configure_presets)
+}
+
+fn resolve_binary_dir(preset: &RawPreset, all: &[RawPreset], depth: usize) -> Option<String> {
+    if preset.binary_dir.is_some() || depth > all.len() {
+        return preset.binary_dir.clone();
+    }
+
+    preset.inherits.names().into_iter().find_map(|parent| {
+        all.iter()
+            .find(|p| p.name == parent)
+            .and_then(|p| resolve_binary_dir(p, all, depth + 1))
+    })
+}
+
+fn resolve(raw: &[RawPreset]) -> Vec<ConfigurePreset> {
+    raw.iter()
+        .filter(|preset| !preset.hidden)
+        .map(|preset| ConfigurePreset {
+            name: preset.name.clone(),
+            binary_dir: resolve_binary_dir(preset, raw, 0),
+        })
+        .collect()
+}
+
+pub fn parse_presets(json: &str) -> anyhow::Result<Vec<ConfigurePreset>> {
+    Ok(resolve(&parse_raw(json)?))
+}
+
+pub fn load_presets(source_dir: &Path) -> Vec<ConfigurePreset> {
+    let raw: Vec<RawPreset> = PRESET_FILES
+        .iter()
+        .filter_map(|file| std::fs::read_to_string(source_dir.join(file)).ok())
+        .filter_map(|json| parse_raw(&json).ok())
+        .flatten()
+        .collect();
+
+    resolve(&raw)
+}
+
+pub fn find_preset(source_dir: &Path, name: &str) -> Option<ConfigurePreset> {
+    load_presets(source_dir)
+        .into_iter()
+        .find(|preset| preset.name == name)
+}
+
+fn expand_macros(value: &str, preset_name: &str, source_dir: &Path) -> String {
+    let source_parent = source_dir.parent().unwrap_or(source_dir);
+    let source_dir_name = source_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    value
+        .replace("${sourceDir}", &source_dir.display().to_string())
+        .replace("${sourceParentDir}", &source_parent.display().to_string())
+        .replace("${sourceDirName}", &source_dir_name)
+        .replace("${presetName}", preset_name)
+        .replace("${dollar}", "$")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"{
+        "version": 3,
+        "configurePresets": [
+            {
+                "name": "base",
+                "hidden": true,
+                "generator": "Ninja",
+                "binaryDir": "${sourceDir}/out/build/${presetName}"
+            },
+            {
+                "name": "linux-debug",
+                "inherits": "base",
+                "cacheVariables": { "CMAKE_BUILD_TYPE": "Debug" }
+            },
+            {
+                "name": "linux-release",
+                "inherits": ["base"],
+                "binaryDir": "build-release"
+            },
+            {
+                "name": "standalone"
+            }
+        ],
+        "buildPresets": [
+            { "name": "linux-debug", "configurePreset": "linux-debug" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_presets_fixture() {
+        let presets = parse_presets(FIXTURE).unwrap();
+        let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+
+        assert_eq!(names, vec!["linux-debug", "linux-release", "standalone"]);
+        assert_eq!(
+            presets[0].binary_dir.as_deref(),
+            Some("${sourceDir}/out/build/${presetName}")
+        );
+        assert_eq!(presets[1].binary_dir.as_deref(), Some("build-release"));
+        assert_eq!(presets[2].binary_dir, None);
+    }
+
+    #[test]
+    fn test_binary_dir_expansion() {
+        let presets = parse_presets(FIXTURE).unwrap();
+        let source = Path::new("/work/engine");
+
+        assert_eq!(
+            presets[0].binary_dir_in(source),
+            Some(PathBuf::from("/work/engine/out/build/linux-debug"))
+        );
+        assert_eq!(
+            presets[1].binary_dir_in(source),
+            Some(PathBuf::from("/work/engine/build-release"))
+        );
+        assert_eq!(presets[2].binary_dir_in(source), None);
+    }
+
+    #[test]
+    fn test_parse_presets_rejects_invalid_json() {
+        assert!(parse_presets("{ not json").is_err());
+        assert!(parse_presets("{}").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+
+use crate::command::Generator;
+use crate::parser::entry::{LogComponent, LogEntry, LogLevel};
+use crate::Builder;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub passed: bool,
+    pub message: String,
+}
+
+impl CheckResult {
+    fn pass(message: String) -> Self {
+        Self {
+            passed: true,
+            message,
+        }
+    }
+
+    fn fail(message: String) -> Self {
+        Self {
+            passed: false,
+            message,
+        }
+    }
+
+    pub fn to_log_entry(&self, index: usize) -> LogEntry {
+        let (level, prefix) = if self.passed {
+            (LogLevel::Info, "✓")
+        } else {
+            (LogLevel::Error, "✗")
+        };
+        let message = format!("{} {}", prefix, self.message);
+
+        LogEntry::new(
+            level,
+            message.clone(),
+            message,
+            LogComponent::Other("prevalidation".to_string()),
+            index,
+        )
+        .with_tags(vec!["prevalidation".to_string()])
+    }
+}
+
+pub fn parse_version(text: &str) -> Option<Vec<u32>> {
+    let token = text
+        .split_whitespace()
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+
+    let parts: Vec<u32> = token
+        .split('.')
+        .map_while(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        })
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts)
+    }
+}
+
+pub fn version_at_least(actual: &[u32], minimum: &[u32]) -> bool {
+    let len = actual.len().max(minimum.len());
+    let pad = |v: &[u32]| {
+        let mut v = v.to_vec();
+        v.resize(len, 0);
+        v
+    };
+
+    pad(actual) >= pad(minimum)
+}
+
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+async fn check_cmake(min_version: &str) -> CheckResult {
+    if find_in_path("cmake").is_none() {
+        return CheckResult::fail("cmake not found on PATH".to_string());
+    }
+
+    let output = match tokio::process::Command::new("cmake")
+        .arg("--version")
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) => return CheckResult::fail(format!("Failed to run cmake --version: {}", e)),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(version) = stdout.lines().next().and_then(parse_version) else {
+        return CheckResult::fail("Could not determine cmake version".to_string());
+    };
+    let version_str = version
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+
+    match parse_version(min_version) {
+        Some(minimum) if !version_at_least(&version, &minimum) => CheckResult::fail(format!(
+            "cmake {} is older than the required {}",
+            version_str, min_version
+        )),
+        _ => CheckResult::pass(format!("cmake {} found", version_str)),
+    }
+}
+
+fn check_generator(name: &str) -> CheckResult {
+    let Some(generator) = Generator::from_name(name) else {
+        return CheckResult::fail(format!(
+            "Unknown build.generator '{}' (expected ninja, make or xcode)",
+            name
+        ));
+    };
+    let generator = generator.program();
+
+    match find_in_path(generator) {
+        Some(path) => CheckResult::pass(format!("{} found at {}", generator, path.display())),
+        None => CheckResult::fail(format!("{} not found on PATH", generator)),
+    }
+}
+
+fn check_cmake_lists(path: &Path) -> CheckResult {
+    if path.join("CMakeLists.txt").is_file() {
+        CheckResult::pass(format!("CMakeLists.txt found in {}", path.display()))
+    } else {
+        CheckResult::fail(format!("No CMakeLists.txt in {}", path.display()))
+    }
+}
+
+pub async fn run_checks(builder: &Builder) -> Vec<CheckResult> {
+    let mut results = vec![
+        check_cmake(&builder.root().config.stages.min_cmake_version).await,
+        check_generator(&builder.root().config.build.generator),
+    ];
+
+    if builder.targets().is_empty() {
+        results.push(check_cmake_lists(&builder.root().path));
+    }
+    for target in builder.targets() {
+        results.push(check_cmake_lists(&target.path));
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("cmake version 3.28.1"), Some(vec![3, 28, 1]));
+        assert_eq!(
+            parse_version("cmake version 3.30.0-rc2"),
+            Some(vec![3, 30, 0])
+        );
+        assert_eq!(parse_version("3.20"), Some(vec![3, 20]));
+        assert_eq!(parse_version("cmake version unknown"), None);
+    }
+
+    #[test]
+    fn test_version_at_least() {
+        assert!(version_at_least(&[3, 28, 1], &[3, 20]));
+        assert!(version_at_least(&[3, 20], &[3, 20, 0]));
+        assert!(version_at_least(&[4, 0], &[3, 31, 9]));
+        assert!(!version_at_least(&[3, 19, 9], &[3, 20]));
+        assert!(!version_at_least(&[3, 9], &[3, 10]));
+    }
+
+    #[test]
+    fn test_check_generator_rejects_unknown_name() {
+        let result = check_generator("bazel");
+        assert!(!result.passed);
+        assert!(result.message.contains("bazel"));
+    }
+
+    #[test]
+    fn test_check_cmake_lists() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(!check_cmake_lists(dir.path()).passed);
+        std::fs::write(dir.path().join("CMakeLists.txt"), "").unwrap();
+        assert!(check_cmake_lists(dir.path()).passed);
+    }
+}
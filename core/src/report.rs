@@ -0,0 +1,181 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::builder::BuildStep;
+use crate::executor::{execute_step, ExecutionResult, ProcessEnv};
+use crate::history::BuildStepResult;
+use crate::parser::entry::{LogEntry, LogLevel};
+use crate::parser::CompilerOutputParser;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportMessage {
+    pub step: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl ReportMessage {
+    fn from_entry(step: &str, entry: &LogEntry) -> Self {
+        Self {
+            step: step.to_string(),
+            message: entry.message.clone(),
+            file: entry.file_path.clone(),
+            line: entry.line_number,
+            column: entry.column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BuildReport {
+    pub success: bool,
+    pub duration: f64,
+    pub steps: Vec<BuildStepResult>,
+    pub errors: Vec<ReportMessage>,
+    pub warnings: Vec<ReportMessage>,
+}
+
+impl BuildReport {
+    pub fn record_step(
+        &mut self,
+        step: &BuildStep,
+        result: &ExecutionResult,
+        entries: &[LogEntry],
+    ) {
+        let description = step.description.as_str();
+        let mut error_count = 0;
+        let mut warning_count = 0;
+
+        for entry in entries {
+            match entry.level {
+                LogLevel::Error | LogLevel::Fatal => {
+                    error_count += 1;
+                    self.errors
+                        .push(ReportMessage::from_entry(description, entry));
+                }
+                LogLevel::Warning => {
+                    warning_count += 1;
+                    self.warnings
+                        .push(ReportMessage::from_entry(description, entry));
+                }
+                _ => {}
+            }
+        }
+
+        let start_offset = self.steps.iter().map(|step| step.duration).sum();
+        self.steps.push(BuildStepResult {
+            description: description.to_string(),
+            duration: result.duration,
+            success: result.success,
+            error_count,
+            warning_count,
+            commands: step.commands.clone(),
+            target: step.target.clone(),
+            start_offset,
+        });
+    }
+
+    pub fn finish(&mut self, duration: f64) {
+        self.duration = duration;
+        self.success = self.steps.iter().all(|step| step.success);
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub async fn run_report(
+    steps: Vec<BuildStep>,
+    timeout: Option<Duration>,
+    env: &ProcessEnv,
+) -> anyhow::Result<BuildReport> {
+    let start = Instant::now();
+    let parser = Arc::new(Mutex::new(CompilerOutputParser::new()));
+    let mut report = BuildReport::default();
+
+    for step in steps {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let entries_clone = entries.clone();
+        let parser_clone = parser.clone();
+
+        let result = execute_step(
+            step.commands.clone(),
+            move |line| {
+                let entry = parser_clone.lock().unwrap().parse_line(&line);
+                entries_clone.lock().unwrap().push(entry);
+            },
+            None,
+            timeout,
+            env,
+        )
+        .await?;
+
+        report.record_step(&step, &result, &entries.lock().unwrap());
+        if !result.success {
+            break;
+        }
+    }
+
+    report.finish(start.elapsed().as_secs_f64());
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell_step(description: &str, script: &str) -> BuildStep {
+        BuildStep {
+            description: description.to_string(),
+            commands: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            working_dir: None,
+            shell: false,
+            target: None,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_two_step_report_json_shape() {
+        let steps = vec![
+            shell_step(
+                "Configure",
+                "echo 'src/main.cpp:3:5: warning: unused variable x [-Wunused-variable]'",
+            ),
+            shell_step(
+                "Build",
+                "echo \"src/main.cpp:7:1: error: expected ';' before '}' token\"; exit 1",
+            ),
+        ];
+
+        let report = run_report(steps, None, &ProcessEnv::default())
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["success"], false);
+        assert!(json["duration"].as_f64().unwrap() >= 0.0);
+
+        let steps = json["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0]["description"], "Configure");
+        assert_eq!(steps[0]["success"], true);
+        assert_eq!(steps[0]["warning_count"], 1);
+        assert_eq!(steps[0]["error_count"], 0);
+        assert_eq!(steps[1]["description"], "Build");
+        assert_eq!(steps[1]["success"], false);
+        assert_eq!(steps[1]["error_count"], 1);
+        assert!(steps[1]["duration"].is_number());
+
+        assert_eq!(json["warnings"][0]["file"], "src/main.cpp");
+        assert_eq!(json["warnings"][0]["line"], 3);
+        assert_eq!(json["errors"][0]["step"], "Build");
+        assert_eq!(json["errors"][0]["line"], 7);
+        assert_eq!(json["errors"][0]["column"], 1);
+    }
+}
@@ -1,6 +1,7 @@
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum Stage {
     PreValidation,
     Configure,
@@ -78,6 +79,12 @@ impl Stage {
         }
     }
 
+    pub fn from_name(name: &str) -> Option<Stage> {
+        Stage::all()
+            .into_iter()
+            .find(|stage| stage.to_string().eq_ignore_ascii_case(name.trim()))
+    }
+
     pub fn all() -> Vec<Stage> {
         vec![
             Stage::PreValidation,
@@ -130,6 +137,13 @@ mod tests {
         assert_eq!(Stage::Exec.default_dependencies(), vec![Stage::Build, Stage::Install]);
     }
 
+    #[test]
+    fn test_stage_from_name() {
+        assert_eq!(Stage::from_name("PostBuild"), Some(Stage::PostBuild));
+        assert_eq!(Stage::from_name(" install "), Some(Stage::Install));
+        assert_eq!(Stage::from_name("Lint"), None);
+    }
+
     #[test]
     fn test_stage_display() {
         assert_eq!(format!("{}", Stage::Build), "Build");
@@ -1,4 +1,7 @@
+use std::io;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 use tokio::sync::mpsc;
 
 use crate::{
@@ -34,11 +37,11 @@ pub enum StageStatus {
     Skipped,
 }
 
+#[derive(Clone)]
 pub struct StageContext {
-    builder: Builder,
+    planner: StagePlanner,
     log_tx: mpsc::UnboundedSender<LogEntry>,
     step_tx: mpsc::UnboundedSender<StepUpdate>,
-    executable_info: Option<ExecutableInfo>,
 }
 
 impl StageContext {
@@ -46,22 +49,33 @@ impl StageContext {
         builder: Builder,
         log_tx: mpsc::UnboundedSender<LogEntry>,
         step_tx: mpsc::UnboundedSender<StepUpdate>,
+    ) -> Self {
+        Self::from_planner(StagePlanner::new(builder), log_tx, step_tx)
+    }
+
+    pub fn from_planner(
+        planner: StagePlanner,
+        log_tx: mpsc::UnboundedSender<LogEntry>,
+        step_tx: mpsc::UnboundedSender<StepUpdate>,
     ) -> Self {
         Self {
-            builder,
+            planner,
             log_tx,
             step_tx,
-            executable_info: None,
         }
     }
 
     pub fn with_executable(mut self, exec_info: ExecutableInfo) -> Self {
-        self.executable_info = Some(exec_info);
+        self.planner = self.planner.with_executable(exec_info);
         self
     }
 
+    pub fn planner(&self) -> &StagePlanner {
+        &self.planner
+    }
+
     pub fn builder(&self) -> &Builder {
-        &self.builder
+        self.planner.builder()
     }
 
     pub fn log_tx(&self) -> mpsc::UnboundedSender<LogEntry> {
@@ -72,42 +86,94 @@ impl StageContext {
         self.step_tx.clone()
     }
 
+    pub fn executable_info(&self) -> Option<&ExecutableInfo> {
+        self.planner.executable_info()
+    }
+
+    pub fn generate_steps_for_stage(&self, stage: Stage) -> Vec<BuildStep> {
+        self.planner.generate_steps_for_stage(stage)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StagePlanner {
+    builder: Builder,
+    executable_info: Option<ExecutableInfo>,
+}
+
+impl StagePlanner {
+    pub fn new(builder: Builder) -> Self {
+        Self {
+            builder,
+            executable_info: None,
+        }
+    }
+
+    pub fn with_executable(mut self, exec_info: ExecutableInfo) -> Self {
+        self.executable_info = Some(exec_info);
+        self
+    }
+
+    pub fn builder(&self) -> &Builder {
+        &self.builder
+    }
+
     pub fn executable_info(&self) -> Option<&ExecutableInfo> {
         self.executable_info.as_ref()
     }
 
     pub fn generate_steps_for_stage(&self, stage: Stage) -> Vec<BuildStep> {
+        if let Some(steps) = self
+            .executable_info
+            .as_ref()
+            .and_then(|exec_info| self.generate_scoped_steps(stage, exec_info))
+        {
+            return steps;
+        }
+
         match stage {
-            Stage::PreValidation => self.generate_prevalidation_steps(),
             Stage::Configure => self.generate_configure_steps(),
             Stage::Build => self.generate_build_steps(),
             Stage::Install => self.generate_install_steps(),
             Stage::Clean => self.generate_clean_steps(),
             Stage::PostBuild => self.generate_postbuild_steps(),
             Stage::Test => self.generate_test_steps(),
-            Stage::Exec => {
+            Stage::PreValidation | Stage::Exec => {
                 vec![]
             }
         }
     }
 
-    fn generate_prevalidation_steps(&self) -> Vec<BuildStep> {
-        vec![]
+    fn generate_scoped_steps(
+        &self,
+        stage: Stage,
+        exec_info: &ExecutableInfo,
+    ) -> Option<Vec<BuildStep>> {
+        self.builder
+            .generate_scoped_step(stage, exec_info)
+            .map(|step| vec![step])
     }
 
     fn generate_configure_steps(&self) -> Vec<BuildStep> {
         let mut steps = Vec::new();
+        let smart_configure = self.builder.root().config.build.smart_configure;
 
         for target in self.builder.targets() {
-            let (build_dir, _install_dir) = self
+            let (build_dir, install_dir) = self
                 .builder
                 .get_dirs(target.path.clone(), self.builder.preset());
 
+            if smart_configure && configure_up_to_date(&target.path, &build_dir, &install_dir) {
+                continue;
+            }
+
             let build_dir_str = build_dir.display().to_string();
-            let source_dir = target.path.display().to_string();
 
-            let cmake = self.builder.cmake();
-            steps.push(cmake.configure_step("CMake", source_dir, build_dir_str));
+            steps.push(
+                self.builder
+                    .configure_target_step("CMake", &target.path, build_dir_str)
+                    .with_target(&target.dir_name()),
+            );
         }
 
         steps
@@ -125,7 +191,11 @@ impl StageContext {
             let target_name = target.config.build.name.as_deref().unwrap_or("project");
 
             let cmake = self.builder.cmake();
-            steps.push(cmake.build_step(target_name, build_dir_str));
+            steps.push(
+                cmake
+                    .build_step(target_name, build_dir_str)
+                    .with_target(&target.dir_name()),
+            );
         }
 
         steps
@@ -144,7 +214,11 @@ impl StageContext {
                 let install_dir_str = install_dir.display().to_string();
 
                 let cmake = self.builder.cmake();
-                steps.push(cmake.install_step("artifacts", build_dir_str, install_dir_str));
+                steps.push(
+                    cmake
+                        .install_step("artifacts", build_dir_str, install_dir_str)
+                        .with_target(&target.dir_name()),
+                );
             }
         }
 
@@ -156,16 +230,64 @@ impl StageContext {
     }
 
     fn generate_postbuild_steps(&self) -> Vec<BuildStep> {
-        vec![]
+        self.builder.generate_postbuild_all()
     }
 
     fn generate_test_steps(&self) -> Vec<BuildStep> {
-        vec![]
+        self.builder.generate_test_all()
     }
 }
 
 pub type SharedStageContext = Arc<Mutex<StageContext>>;
 
+const CONFIGURE_INPUTS: [&str; 4] = [
+    "CMakeLists.txt",
+    "CMakePresets.json",
+    "CMakeUserPresets.json",
+    "ignis.toml",
+];
+
+pub fn configure_up_to_date(source_dir: &Path, build_dir: &Path, install_dir: &Path) -> bool {
+    let Ok(cache_time) = build_dir
+        .join("CMakeCache.txt")
+        .metadata()
+        .and_then(|m| m.modified())
+    else {
+        return false;
+    };
+
+    match newest_configure_input(source_dir, install_dir) {
+        Ok(Some(newest)) => newest < cache_time,
+        _ => false,
+    }
+}
+
+fn newest_configure_input(dir: &Path, install_dir: &Path) -> io::Result<Option<SystemTime>> {
+    let mut newest = None;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        let modified = if entry.file_type()?.is_dir() {
+            // Build trees are full of generated *.cmake files; they never feed configure.
+            if name.starts_with('.') || path == install_dir || path.join("CMakeCache.txt").exists()
+            {
+                continue;
+            }
+            newest_configure_input(&path, install_dir)?
+        } else if CONFIGURE_INPUTS.contains(&name.as_str()) || name.ends_with(".cmake") {
+            Some(entry.metadata()?.modified()?)
+        } else {
+            continue;
+        };
+        newest = newest.max(modified);
+    }
+
+    Ok(newest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +303,17 @@ mod tests {
         Builder::new(root, "debug".to_string())
     }
 
+    fn editor_exec_info() -> ExecutableInfo {
+        ExecutableInfo {
+            path: std::path::PathBuf::from("/tmp/test/builds/debug/editor"),
+            name: "editor".to_string(),
+            project_dir: std::path::PathBuf::from("/tmp/test"),
+            build_dir: std::path::PathBuf::from("/tmp/test/builds/debug"),
+            install_dir: std::path::PathBuf::from("/tmp/test/install"),
+            args: Vec::new(),
+        }
+    }
+
     #[test]
     fn test_stage_result_success() {
         let result = StageResult::Success {
@@ -214,20 +347,145 @@ mod tests {
 
         let context = StageContext::new(builder, log_tx, step_tx);
         assert!(context.executable_info().is_none());
+
+        let scoped = context.with_executable(editor_exec_info()).clone();
+        assert_eq!(
+            scoped.planner().executable_info().map(|e| e.name.as_str()),
+            Some("editor")
+        );
+        assert_eq!(
+            scoped.generate_steps_for_stage(Stage::Build)[0].commands,
+            scoped.planner().generate_steps_for_stage(Stage::Build)[0].commands
+        );
     }
 
     #[test]
     fn test_generate_steps_for_stage() {
         let builder = create_test_builder();
-        let (log_tx, _log_rx) = mpsc::unbounded_channel();
-        let (step_tx, _step_rx) = mpsc::unbounded_channel();
+        let planner = StagePlanner::new(builder);
 
-        let context = StageContext::new(builder, log_tx, step_tx);
-
-        let steps = context.generate_steps_for_stage(Stage::PreValidation);
+        let steps = planner.generate_steps_for_stage(Stage::PreValidation);
         assert_eq!(steps.len(), 0);
 
-        let steps = context.generate_steps_for_stage(Stage::Exec);
+        let steps = planner.generate_steps_for_stage(Stage::Exec);
         assert_eq!(steps.len(), 0);
     }
+
+    #[test]
+    fn test_executable_scopes_build_stages() {
+        let planner = StagePlanner::new(create_test_builder()).with_executable(editor_exec_info());
+
+        let steps = planner.generate_steps_for_stage(Stage::Build);
+        assert_eq!(steps.len(), 1);
+        assert!(steps[0]
+            .commands
+            .windows(2)
+            .any(|pair| pair == ["--target", "editor"]));
+        assert_eq!(planner.generate_steps_for_stage(Stage::Configure).len(), 1);
+        assert_eq!(planner.generate_steps_for_stage(Stage::Install).len(), 1);
+    }
+
+    #[test]
+    fn test_generated_steps_carry_target_dir_name() {
+        let target = Target {
+            path: std::path::PathBuf::from("/tmp/astra/engine"),
+            kind: crate::target::TargetKind::Installer,
+            config: Config::default(),
+        };
+        let builder = Builder::new(target.clone(), "debug".to_string()).with_targets(vec![target]);
+        let planner = StagePlanner::new(builder);
+
+        for stage in [Stage::Configure, Stage::Build, Stage::Install] {
+            let steps = planner.generate_steps_for_stage(stage);
+            assert_eq!(steps.len(), 1);
+            assert_eq!(steps[0].target.as_deref(), Some("engine"));
+        }
+    }
+
+    #[test]
+    fn test_configure_up_to_date_compares_cache_against_inputs() {
+        let touch = |path: &Path, secs: u64| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let file = std::fs::File::create(path).unwrap();
+            let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            file.set_modified(time).unwrap();
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path();
+        let build_dir = source.join("builds").join("debug");
+        let install_dir = source.join("install");
+
+        touch(&source.join("CMakeLists.txt"), 1_000);
+        touch(&source.join("cmake").join("deps.cmake"), 1_500);
+        touch(&source.join("src").join("main.cpp"), 9_000);
+        assert!(!configure_up_to_date(source, &build_dir, &install_dir));
+
+        touch(&build_dir.join("CMakeCache.txt"), 2_000);
+        touch(&build_dir.join("CMakeFiles").join("generated.cmake"), 3_000);
+        touch(
+            &install_dir
+                .join("lib")
+                .join("cmake")
+                .join("AppConfig.cmake"),
+            3_000,
+        );
+        assert!(configure_up_to_date(source, &build_dir, &install_dir));
+
+        touch(&source.join("cmake").join("deps.cmake"), 2_500);
+        assert!(!configure_up_to_date(source, &build_dir, &install_dir));
+    }
+
+    #[test]
+    fn test_generate_test_steps_requires_ctest_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let build_dir = dir.path().join("builds").join("debug");
+        std::fs::create_dir_all(&build_dir).unwrap();
+
+        let root = Target {
+            path: dir.path().to_path_buf(),
+            kind: crate::target::TargetKind::Root,
+            config: Config::default(),
+        };
+        let builder = Builder::new(root.clone(), "debug".to_string()).with_targets(vec![root]);
+        let planner = StagePlanner::new(builder);
+
+        assert!(planner.generate_steps_for_stage(Stage::Test).is_empty());
+
+        std::fs::write(build_dir.join("CTestTestfile.cmake"), "").unwrap();
+        let steps = planner.generate_steps_for_stage(Stage::Test);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(
+            steps[0].commands,
+            vec![
+                "ctest".to_string(),
+                "--test-dir".to_string(),
+                build_dir.display().to_string(),
+                "--output-on-failure".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_postbuild_steps_run_in_build_dir() {
+        let mut config = Config::default();
+        config.stages.post_build.commands = vec![
+            vec!["cp".to_string(), "app".to_string(), "/tmp/dist".to_string()],
+            vec![],
+        ];
+        let root = Target {
+            path: std::path::PathBuf::from("/tmp/test"),
+            kind: crate::target::TargetKind::Root,
+            config,
+        };
+        let builder = Builder::new(root.clone(), "debug".to_string()).with_targets(vec![root]);
+        let planner = StagePlanner::new(builder);
+
+        let steps = planner.generate_steps_for_stage(Stage::PostBuild);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].commands, vec!["cp", "app", "/tmp/dist"]);
+        assert_eq!(
+            steps[0].working_dir,
+            Some(std::path::PathBuf::from("/tmp/test/builds/debug"))
+        );
+    }
 }
@@ -6,9 +6,12 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 
 use crate::{
+    builder::BuildStep,
     dependency_graph::StageDependencyGraph,
-    executor,
-    parser::CompilerOutputParser,
+    executor::{self, SkippedStep, StepUpdate},
+    metrics_server::{self, MetricsSnapshot},
+    parser::{CompilerOutputParser, LogComponent, LogEntry, LogLevel},
+    prevalidation,
     stage::Stage,
     stage_context::{StageContext, StageResult, StageStatus},
     tui::App,
@@ -72,10 +75,12 @@ impl StageRunner {
         let storage_path = root.config.storage_path();
         let max_builds = root.config.history.max_builds;
 
-        let build_history =
-            BuildHistory::new(storage_path, max_builds).context("Failed to load build history")?;
+        let build_history = BuildHistory::new(storage_path, max_builds)
+            .context("Failed to load build history")?
+            .with_max_age_days(root.config.history.max_age_days);
 
-        let resource_monitor = ResourceMonitor::new();
+        let (alert_tx, alert_rx) = mpsc::unbounded_channel();
+        let resource_monitor = ResourceMonitor::new().with_alerts(&root.config.monitor, alert_tx);
         resource_monitor.clone().start_monitoring();
 
         let app = App::new(
@@ -84,7 +89,8 @@ impl StageRunner {
             step_rx,
             resource_monitor,
             self.ctx.builder().clone(),
-        );
+        )
+        .with_alerts(alert_rx);
 
         Ok((app, log_tx, step_tx))
     }
@@ -101,12 +107,22 @@ impl StageRunner {
             statuses.insert(stage, StageStatus::Running);
         }
 
-        if stage == Stage::Exec {
+        let dry_run = context.builder().dry_run();
+
+        if stage == Stage::PreValidation && !dry_run {
+            return Ok(self.execute_prevalidation(context, start).await);
+        }
+
+        if stage == Stage::Exec && !dry_run {
             if let Some(exec_info) = context.executable_info() {
                 let result = executor::execute_program(
                     exec_info.clone(),
                     context.log_tx(),
                     context.step_tx(),
+                    &context.builder().root().config.exec.process_env(),
+                    executor::ProgramOptions::default()
+                        .with_timeout(context.builder().root().config.exec.timeout())
+                        .with_metric_tick(executor::METRIC_TICK),
                 )
                 .await?;
 
@@ -176,19 +192,84 @@ impl StageRunner {
             return Ok(stage_result);
         }
 
-        let mut parser = CompilerOutputParser::new();
         let log_tx = context.log_tx();
         let step_tx = context.step_tx();
+        let build_config = &context.builder().root().config.build;
 
-        let execution_results = executor::execute_steps(
-            steps,
-            move |line| {
-                let entry = parser.parse_line(&line);
-                let _ = log_tx.send(entry);
-            },
-            step_tx,
-        )
-        .await?;
+        if stage == Stage::Build {
+            let jobs = match context.builder().cmake().jobs() {
+                Some(jobs) => format!("--parallel {}", jobs),
+                None => "--parallel (generator default)".to_string(),
+            };
+            let message = format!("Build parallelism: {}", jobs);
+            let _ = log_tx.send(LogEntry::new(
+                LogLevel::Info,
+                message.clone(),
+                message,
+                LogComponent::Build,
+                0,
+            ));
+        }
+
+        let retry = build_config.retry_policy()?.with_log(log_tx.clone());
+        let execution_results = if dry_run {
+            executor::dry_run_steps(
+                steps,
+                |line| {
+                    let _ = log_tx.send(LogEntry::new(
+                        LogLevel::Info,
+                        line.clone(),
+                        line,
+                        LogComponent::Build,
+                        0,
+                    ));
+                },
+                &step_tx,
+            )
+        } else if stage == Stage::Build && build_config.parallel_targets && steps.len() > 1 {
+            executor::execute_steps_parallel(
+                steps,
+                |step| {
+                    let mut parser = CompilerOutputParser::new();
+                    let log_tx = log_tx.clone();
+                    let prefix = step
+                        .target
+                        .clone()
+                        .unwrap_or_else(|| step.description.clone());
+                    move |line: String| {
+                        let entry = parser.parse_line(&line).with_prefix(&prefix);
+                        let _ = log_tx.send(entry);
+                    }
+                },
+                step_tx,
+                build_config.max_parallel_targets,
+                build_config.step_timeout(),
+                &context.builder().build_env(),
+                &retry,
+            )
+            .await?
+        } else {
+            let mut parser = CompilerOutputParser::new();
+            let output_tx = log_tx.clone();
+            executor::execute_steps(
+                steps,
+                move |line| {
+                    let entry = parser.parse_line(&line);
+                    let _ = output_tx.send(entry);
+                },
+                step_tx,
+                build_config.step_timeout(),
+                &context.builder().build_env(),
+                &retry,
+            )
+            .await?
+        };
+
+        if let Some(limit) = build_config.step_timeout() {
+            for _ in execution_results.iter().filter(|r| r.timed_out) {
+                let _ = log_tx.send(executor::timeout_entry(limit));
+            }
+        }
 
         let duration = start.elapsed().as_secs_f64();
         let steps_executed = execution_results.len();
@@ -230,6 +311,72 @@ impl StageRunner {
         Ok(stage_result)
     }
 
+    async fn execute_prevalidation(&self, context: &StageContext, start: Instant) -> StageResult {
+        let description = Stage::PreValidation.metadata().description;
+        let step_tx = context.step_tx();
+        let log_tx = context.log_tx();
+        let step = BuildStep::new(description, Vec::new());
+        let _ = step_tx.send(StepUpdate::Started(step.clone(), Instant::now()));
+
+        let checks = prevalidation::run_checks(context.builder()).await;
+        for (index, check) in checks.iter().enumerate() {
+            let _ = log_tx.send(check.to_log_entry(index));
+        }
+
+        let failures: Vec<&str> = checks
+            .iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.message.as_str())
+            .collect();
+        let duration = start.elapsed().as_secs_f64();
+        let failure_reason = if failures.is_empty() {
+            None
+        } else {
+            Some(format!("Pre-validation failed: {}", failures.join("; ")))
+        };
+
+        let _ = step_tx.send(StepUpdate::Finished(
+            step,
+            executor::ExecutionResult {
+                success: failure_reason.is_none(),
+                duration,
+                stdout: vec![],
+                stderr: vec![],
+                exit_code: None,
+                failure_reason: failure_reason.clone(),
+                timed_out: false,
+            },
+            Instant::now(),
+        ));
+
+        let (stage_result, status) = match failure_reason {
+            None => (
+                StageResult::Success {
+                    duration,
+                    steps_executed: checks.len(),
+                },
+                StageStatus::Completed,
+            ),
+            Some(error) => (
+                StageResult::Failed {
+                    error,
+                    duration,
+                    steps_executed: checks.len(),
+                },
+                StageStatus::Failed,
+            ),
+        };
+
+        {
+            let mut results = self.results.lock().unwrap();
+            results.insert(Stage::PreValidation, stage_result.clone());
+            let mut statuses = self.statuses.lock().unwrap();
+            statuses.insert(Stage::PreValidation, status);
+        }
+
+        stage_result
+    }
+
     pub async fn execute_stages_concurrent(
         &self,
         stages: Vec<Stage>,
@@ -246,10 +393,7 @@ impl StageRunner {
             };
 
             let handle = tokio::spawn(async move {
-                let new_ctx = {
-                    let ctx = context_clone.lock().unwrap();
-                    StageContext::new(ctx.builder().clone(), ctx.log_tx(), ctx.step_tx())
-                };
+                let new_ctx = context_clone.lock().unwrap().clone();
 
                 let result = runner.execute_stage(stage, &new_ctx).await?;
                 Ok((stage, result))
@@ -272,7 +416,9 @@ impl StageRunner {
         stages: Vec<Stage>,
         context: StageContext,
     ) -> Result<HashMap<Stage, StageResult>, anyhow::Error> {
-        let graph = StageDependencyGraph::from_stages(stages);
+        let graph =
+            StageDependencyGraph::from_config(stages, &context.builder().root().config.stages)
+                .context("Invalid stages.stage_dependencies")?;
 
         let layers = graph
             .topological_sort()
@@ -282,22 +428,17 @@ impl StageRunner {
         let mut all_results = HashMap::new();
 
         for layer in layers {
-            let previous_failures = {
+            let failed_stage = {
                 let results = self.results.lock().unwrap();
                 results
-                    .values()
-                    .any(|r| matches!(r, StageResult::Failed { .. }))
+                    .iter()
+                    .find(|(_, r)| matches!(r, StageResult::Failed { .. }))
+                    .map(|(stage, _)| *stage)
             };
 
-            if previous_failures {
+            if let Some(failed_stage) = failed_stage {
                 for stage in layer {
-                    let skip_result = StageResult::Skipped {
-                        reason: "Previous stage failed".to_string(),
-                    };
-                    let mut results = self.results.lock().unwrap();
-                    results.insert(stage, skip_result.clone());
-                    let mut statuses = self.statuses.lock().unwrap();
-                    statuses.insert(stage, StageStatus::Skipped);
+                    let skip_result = self.skip_stage(stage, failed_stage, &context);
                     all_results.insert(stage, skip_result);
                 }
                 continue;
@@ -311,17 +452,21 @@ impl StageRunner {
                     .await?;
                 all_results.extend(layer_results);
             } else {
+                let mut failed_stage = None;
                 for stage in layer {
-                    let new_ctx = {
-                        let ctx = context.lock().unwrap();
-                        StageContext::new(ctx.builder().clone(), ctx.log_tx(), ctx.step_tx())
-                    };
+                    if let Some(failed_stage) = failed_stage {
+                        let skip_result = self.skip_stage(stage, failed_stage, &context);
+                        all_results.insert(stage, skip_result);
+                        continue;
+                    }
+
+                    let new_ctx = context.lock().unwrap().clone();
 
                     let result = self.execute_stage(stage, &new_ctx).await?;
                     all_results.insert(stage, result);
 
                     if matches!(all_results.get(&stage), Some(StageResult::Failed { .. })) {
-                        break;
+                        failed_stage = Some(stage);
                     }
                 }
             }
@@ -330,6 +475,33 @@ impl StageRunner {
         Ok(all_results)
     }
 
+    fn skip_stage(
+        &self,
+        stage: Stage,
+        failed_stage: Stage,
+        context: &Arc<Mutex<StageContext>>,
+    ) -> StageResult {
+        let reason = format!("{} failed", failed_stage);
+        let skip_result = StageResult::Skipped {
+            reason: reason.clone(),
+        };
+
+        {
+            let mut results = self.results.lock().unwrap();
+            results.insert(stage, skip_result.clone());
+            let mut statuses = self.statuses.lock().unwrap();
+            statuses.insert(stage, StageStatus::Skipped);
+        }
+
+        let step_tx = context.lock().unwrap().step_tx();
+        let _ = step_tx.send(StepUpdate::Skipped(SkippedStep {
+            description: stage.metadata().description,
+            reason,
+        }));
+
+        skip_result
+    }
+
     pub fn get_status(&self, stage: Stage) -> Option<StageStatus> {
         let statuses = self.statuses.lock().unwrap();
         statuses.get(&stage).cloned()
@@ -358,7 +530,7 @@ impl ExecRunner {
     pub async fn run(
         &mut self,
         builder: &Builder,
-        selected_exec: ExecutableInfo,
+        mut selected_exec: ExecutableInfo,
     ) -> Result<(), anyhow::Error> {
         let mut resume_mode = self.ctx.should_resume(&selected_exec);
 
@@ -370,7 +542,7 @@ impl ExecRunner {
             };
 
             let (returned_app, exec_action, build_action) = self
-                .handle_mode(builder, selected_exec.clone(), resume_mode, app_to_use)
+                .handle_mode(builder, &mut selected_exec, resume_mode, app_to_use)
                 .await?;
 
             if let Some(build_action) = build_action {
@@ -408,7 +580,7 @@ impl ExecRunner {
     async fn handle_mode(
         &self,
         builder: &Builder,
-        exec_info: ExecutableInfo,
+        exec_info: &mut ExecutableInfo,
         resume_mode: bool,
         cached_app: Option<App>,
     ) -> Result<(Option<App>, ExecAction, Option<BuildAction>), anyhow::Error> {
@@ -417,6 +589,9 @@ impl ExecRunner {
         if resume_mode && cached_app.is_some() {
             let mut app = cached_app.unwrap();
             app.run().await?;
+            if let Some(args) = app.take_exec_args_change() {
+                exec_info.args = args;
+            }
             let action = app.get_exec_action().unwrap_or(ExecAction::QuitToBuild);
             let build_action = app.get_build_action();
 
@@ -428,21 +603,59 @@ impl ExecRunner {
             let (log_tx, log_rx) = mpsc::unbounded_channel();
             let (step_tx, step_rx) = mpsc::unbounded_channel();
 
-            let resource_monitor = ResourceMonitor::new();
+            let (alert_tx, alert_rx) = mpsc::unbounded_channel();
+            let resource_monitor =
+                ResourceMonitor::new().with_alerts(&builder.root().config.monitor, alert_tx);
             let _monitor_handle = resource_monitor.clone().start_monitoring();
 
+            let (kill_tx, kill_rx) = mpsc::unbounded_channel();
+
             let mut app = App::new_exec_mode(
                 log_rx,
                 step_rx,
                 resource_monitor,
                 exec_info.clone(),
                 builder.clone(),
-            );
+            )
+            .with_kill_switch(kill_tx)
+            .with_alerts(alert_rx);
+
+            let mut metrics_server = None;
+            if let Some(port) = builder.root().config.exec.metrics_port {
+                let snapshot = MetricsSnapshot::default();
+                app = app.with_metrics_snapshot(Arc::clone(&snapshot));
+
+                let log_tx = log_tx.clone();
+                metrics_server = Some(tokio::spawn(async move {
+                    if let Err(e) = metrics_server::serve(port, snapshot).await {
+                        let message = format!("Metrics server stopped: {}", e);
+                        let _ = log_tx.send(LogEntry::new(
+                            LogLevel::Error,
+                            message.clone(),
+                            message,
+                            LogComponent::Other("system".to_string()),
+                            0,
+                        ));
+                    }
+                }));
+            }
 
-            let exec_handle =
-                tokio::spawn(
-                    async move { executor::execute_program(exec_info, log_tx, step_tx).await },
-                );
+            let timeout = builder.root().config.exec.timeout();
+            let env = builder.root().config.exec.process_env();
+            let program = exec_info.clone();
+            let exec_handle = tokio::spawn(async move {
+                executor::execute_program(
+                    program,
+                    log_tx,
+                    step_tx,
+                    &env,
+                    executor::ProgramOptions::default()
+                        .with_timeout(timeout)
+                        .with_metric_tick(executor::METRIC_TICK)
+                        .with_kill_switch(kill_rx),
+                )
+                .await
+            });
 
             let app_handle = tokio::spawn(async move {
                 app.run().await?;
@@ -451,8 +664,14 @@ impl ExecRunner {
 
             let _exec_result = exec_handle.await??;
             let mut app = app_handle.await??;
+            if let Some(server) = metrics_server {
+                server.abort();
+            }
 
             app.finalize_exec()?;
+            if let Some(args) = app.take_exec_args_change() {
+                exec_info.args = args;
+            }
 
             let action = app.get_exec_action().unwrap_or(ExecAction::QuitToBuild);
             let build_action = app.get_build_action();
@@ -584,7 +803,14 @@ impl ExecRunner {
     ) -> Result<bool, anyhow::Error> {
         let target_steps = builder.generate_build_target_steps(exec_info);
         for step in target_steps {
-            let result = executor::execute_step(step.commands, |_| {}, None).await?;
+            let result = executor::execute_step(
+                step.commands,
+                |_| {},
+                None,
+                builder.root().config.build.step_timeout(),
+                &builder.build_env(),
+            )
+            .await?;
 
             if !result.success {
                 eprintln!("Build failed for target: {}", exec_info.name);
@@ -600,7 +826,14 @@ impl ExecRunner {
         exec_info: &ExecutableInfo,
     ) -> Result<bool, anyhow::Error> {
         let clean_cmd = builder.generate_clean_target_command(exec_info);
-        executor::execute_step(clean_cmd, |_| {}, None).await?;
+        executor::execute_step(
+            clean_cmd,
+            |_| {},
+            None,
+            builder.root().config.build.step_timeout(),
+            &builder.build_env(),
+        )
+        .await?;
 
         self.build_target(builder, exec_info).await
     }
@@ -616,7 +849,7 @@ impl ExecRunner {
         match build_action {
             BuildAction::Rebuild => self.build_target(builder, exec_info).await,
             BuildAction::Clean => self.clean_and_build_target(builder, exec_info).await,
-            BuildAction::Quit => Ok(false),
+            BuildAction::Install | BuildAction::Quit => Ok(false),
         }
     }
 }
@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::history::BuildStepResult;
+
+const TRACE_PID: u32 = 1;
+const UNTARGETED: &str = "ignis";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceArgs {
+    Thread {
+        name: String,
+    },
+    Step {
+        target: String,
+        success: bool,
+        errors: usize,
+        warnings: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TraceEvent {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cat: Option<&'static str>,
+    pub ph: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u64>,
+    pub pid: u32,
+    pub tid: usize,
+    pub args: TraceArgs,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeTrace {
+    pub trace_events: Vec<TraceEvent>,
+    pub display_time_unit: &'static str,
+}
+
+fn micros(secs: f64) -> u64 {
+    (secs.max(0.0) * 1_000_000.0).round() as u64
+}
+
+impl ChromeTrace {
+    pub fn from_steps(steps: &[BuildStepResult]) -> Self {
+        let mut targets: Vec<&str> = Vec::new();
+        let mut events = Vec::new();
+
+        for step in steps {
+            let target = step.target.as_deref().unwrap_or(UNTARGETED);
+            let tid = match targets.iter().position(|t| *t == target) {
+                Some(index) => index + 1,
+                None => {
+                    targets.push(target);
+                    targets.len()
+                }
+            };
+
+            events.push(TraceEvent {
+                name: step.description.clone(),
+                cat: Some("build"),
+                ph: "X",
+                ts: Some(micros(step.start_offset)),
+                dur: Some(micros(step.duration)),
+                pid: TRACE_PID,
+                tid,
+                args: TraceArgs::Step {
+                    target: target.to_string(),
+                    success: step.success,
+                    errors: step.error_count,
+                    warnings: step.warning_count,
+                },
+            });
+        }
+
+        let threads = targets
+            .iter()
+            .enumerate()
+            .map(|(index, target)| TraceEvent {
+                name: "thread_name".to_string(),
+                cat: None,
+                ph: "M",
+                ts: None,
+                dur: None,
+                pid: TRACE_PID,
+                tid: index + 1,
+                args: TraceArgs::Thread {
+                    name: target.to_string(),
+                },
+            });
+
+        Self {
+            trace_events: threads.chain(events).collect(),
+            display_time_unit: "ms",
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub fn write_trace(path: &Path, steps: &[BuildStepResult]) -> Result<()> {
+    let content = ChromeTrace::from_steps(steps).to_json()?;
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write trace to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(description: &str, target: Option<&str>, start: f64, duration: f64) -> BuildStepResult {
+        BuildStepResult {
+            description: description.to_string(),
+            duration,
+            success: true,
+            error_count: 0,
+            warning_count: 1,
+            commands: Vec::new(),
+            target: target.map(str::to_string),
+            start_offset: start,
+        }
+    }
+
+    #[test]
+    fn test_trace_emits_duration_events_per_target() {
+        let steps = [
+            step("Checking tools", None, 0.0, 0.25),
+            step("Building engine", Some("engine"), 0.25, 12.5),
+            step("Building editor", Some("editor"), 0.25, 3.0),
+            step("Installing artifacts", Some("engine"), 12.75, 1.0),
+        ];
+
+        let json = ChromeTrace::from_steps(&steps).to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let events = value["traceEvents"].as_array().unwrap();
+        assert_eq!(value["displayTimeUnit"], "ms");
+        assert_eq!(events.len(), 7);
+
+        let threads: Vec<_> = events.iter().filter(|e| e["ph"] == "M").collect();
+        assert_eq!(threads.len(), 3);
+        assert_eq!(threads[1]["name"], "thread_name");
+        assert_eq!(threads[1]["args"]["name"], "engine");
+        assert!(threads[1].get("ts").is_none());
+
+        let spans: Vec<_> = events.iter().filter(|e| e["ph"] == "X").collect();
+        assert_eq!(spans.len(), 4);
+        assert_eq!(spans[1]["name"], "Building engine");
+        assert_eq!(spans[1]["cat"], "build");
+        assert_eq!(spans[1]["ts"], 250_000);
+        assert_eq!(spans[1]["dur"], 12_500_000);
+        assert_eq!(spans[1]["pid"], 1);
+        assert_eq!(spans[1]["tid"], spans[3]["tid"]);
+        assert_ne!(spans[1]["tid"], spans[2]["tid"]);
+        assert_eq!(spans[0]["args"]["target"], "ignis");
+        assert_eq!(spans[2]["args"]["warnings"], 1);
+    }
+}
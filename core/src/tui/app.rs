@@ -1,21 +1,34 @@
-use super::input::{handle_key_event, InputAction};
+use super::input::{handle_key_event, resolve_sequence_timeout, InputAction};
 use super::keybinding_manager::{KeyBindingManager, KeyPress};
-use super::tabs::console::ConsoleTab;
-use super::tabs::history::HistoryTab;
-use super::tabs::performance::PerformanceTab;
-use super::tabs::summary::SummaryTab;
+use super::pacer::RenderPacer;
+use super::progress;
+use super::tabs::console::{ConsoleTab, PREVIOUS_BUILD_DIVIDER_TAG, PREVIOUS_BUILD_TAG};
+use super::tabs::history::{history_rows, HistoryTab};
+use super::tabs::performance::{ChartViewport, PerformanceTab, ResourceChart};
+use super::tabs::summary::{StepSort, SummaryTab};
 use super::tabs::warnings::WarningsTab;
-use super::tabs::TabId;
+use super::tabs::{TabId, TabSet};
 use super::vim::{CommandResult, InputMode, VimCommandMode};
-use crate::builder::{Builder, ExecutableInfo};
+use crate::builder::{
+    available_presets, resolve_preset, shell_join, BuildStep, Builder, ExecutableInfo,
+};
+use crate::clean;
 use crate::editor::{copy_to_clipboard, Editor};
-use crate::executor::{MetricHistory, StepUpdate};
+use crate::executor::{MetricHistory, SkippedStep, StepUpdate};
 use crate::history::{
-    BuildHistory, BuildHistoryEntry, BuildStepResult, ExecutionHistory, ExecutionHistoryEntry,
+    detect_regression, is_incremental, BuildHistory, BuildHistoryEntry, BuildStepResult,
+    ExecutionHistory, ExecutionHistoryEntry,
+};
+use crate::logger::LogFile;
+use crate::metrics_server::MetricsSnapshot;
+use crate::monitor::{
+    detect_advisories, samples_to_csv, ResourceAlert, ResourceMonitor, StageWindow,
 };
-use crate::monitor::ResourceMonitor;
-use crate::parser::entry::{LogEntry, LogLevel};
-use crate::parser::filters::LogFilter;
+use crate::notification::{notify_build_complete, BuildOutcome};
+use crate::parser::entry::{LogComponent, LogEntry, LogLevel};
+use crate::parser::filters::{FilterSpec, LogFilter};
+use crate::stage::Stage;
+use crate::trace::write_trace;
 use anyhow::{Context, Result};
 use crossterm::{
     event::{self, Event},
@@ -27,19 +40,116 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{block::Title, Block, BorderType, Borders, ListState, Paragraph},
+    widgets::{block::Title, Block, BorderType, Borders, ListState, Paragraph, TableState},
     Frame, Terminal,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+
+const ALERT_FLASH: Duration = Duration::from_secs(3);
+const QUIT_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildAction {
     Quit,
     Rebuild,
     Clean,
+    Install,
+}
+
+impl BuildAction {
+    pub fn stages(&self) -> Vec<Stage> {
+        match self {
+            BuildAction::Rebuild | BuildAction::Clean => {
+                vec![
+                    Stage::PreValidation,
+                    Stage::Configure,
+                    Stage::Build,
+                    Stage::Test,
+                    Stage::Install,
+                    Stage::PostBuild,
+                ]
+            }
+            BuildAction::Install => vec![Stage::Install],
+            BuildAction::Quit => Vec::new(),
+        }
+    }
+}
+
+fn configured_tab_set(config: &crate::Config) -> (TabSet, Option<String>) {
+    match TabSet::from_names(&config.display.tabs) {
+        Ok(tab_set) => (tab_set, None),
+        Err(e) => (TabSet::default(), Some(format!("display.tabs: {}", e))),
+    }
+}
+
+fn configured_keybindings(config: &crate::Config) -> (KeyBindingManager, Option<String>) {
+    let leader_key = KeyPress::from_string(&config.keybindings.leader_key)
+        .unwrap_or_else(|| KeyPress::from_char(' '));
+
+    let mut manager = KeyBindingManager::new(
+        leader_key,
+        config.keybindings.sequence_timeout_ms,
+        config.keybindings.enable_leader,
+    );
+    let warnings = manager.apply_config(&config.keybindings);
+    let warning = (!warnings.is_empty()).then(|| warnings.join("; "));
+
+    (manager, warning)
+}
+
+fn join_errors(errors: [Option<String>; 2]) -> Option<String> {
+    errors
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| format!("{}; {}", a, b))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LevelCounts {
+    lines: usize,
+    fatal: usize,
+    errors: usize,
+    warnings: usize,
+}
+
+impl LevelCounts {
+    fn from_entries<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> Self {
+        entries
+            .into_iter()
+            .fold(Self::default(), |mut counts, entry| {
+                counts.lines += 1;
+                match entry.level {
+                    LogLevel::Fatal => counts.fatal += 1,
+                    LogLevel::Error => counts.errors += 1,
+                    LogLevel::Warning => counts.warnings += 1,
+                    LogLevel::Debug | LogLevel::Info => {}
+                }
+                counts
+            })
+    }
+}
+
+struct RunningStep {
+    step: BuildStep,
+    started_at: Instant,
+    start_offset: f64,
+}
+
+fn count_text(shown: Option<usize>, total: usize) -> String {
+    match shown {
+        Some(shown) => format!("{} of {}", shown, total),
+        None => total.to_string(),
+    }
+}
+
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,13 +165,31 @@ pub enum ExecAction {
     Kill,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewState {
+    pub search_pattern: Option<String>,
+    pub filter: Option<FilterSpec>,
+}
+
 pub struct App {
     current_tab: TabId,
+    tab_set: TabSet,
     log_entries: Vec<LogEntry>,
+    previous_log_count: usize,
+    previous_logs_collapsed: bool,
     build_steps: Vec<BuildStepResult>,
+    skipped_steps: Vec<SkippedStep>,
     build_complete: bool,
+    fatal_seen: bool,
+    completion_notified: bool,
+    quit_requested_at: Option<Instant>,
+    pending_clean: Option<(PathBuf, u64, Instant)>,
     build_duration: Option<f64>,
-    current_step: Option<String>,
+    last_step_finished_at: Option<Instant>,
+    regression_note: Option<String>,
+    cmake_cache_existed: bool,
+    running_steps: Vec<RunningStep>,
+    stage_windows: Vec<StageWindow>,
     steps_completed: usize,
     total_steps: usize,
     start_time: Instant,
@@ -75,11 +203,32 @@ pub struct App {
     build_menu_open: bool,
     exec_menu_open: bool,
     exec_menu_selection: usize,
+    preset_picker_open: bool,
+    preset_picker_selection: usize,
+    preset_change: Option<String>,
+    exec_args_change: Option<Vec<String>>,
+    command_error: Option<String>,
+    build_note: Option<String>,
     build_action: Option<BuildAction>,
     resource_monitor: ResourceMonitor,
     console_scroll_state: ListState,
+    console_selection: Option<usize>,
+    next_entry_id: usize,
     warnings_scroll_state: ListState,
+    errors_scroll_state: ListState,
+    summary_scroll_state: TableState,
+    history_scroll_state: TableState,
+    history_diff_selection: Vec<usize>,
+    trend_include_failed: bool,
+    history_query: Option<String>,
     auto_scroll: bool,
+    unseen_log_count: usize,
+    relative_numbers: bool,
+    show_slowest_steps: bool,
+    step_sort: StepSort,
+    chart_viewport: ChartViewport,
+    resource_chart: ResourceChart,
+    redraw_requested: bool,
     console_viewport_height: u16,
     cached_filtered_log_count: usize,
     filter_cache_dirty: bool,
@@ -88,7 +237,13 @@ pub struct App {
     exec_info: Option<ExecutableInfo>,
     exec_logs: Vec<LogEntry>,
     exec_metrics: HashMap<String, MetricHistory>,
+    metric_groups: Vec<String>,
+    metrics_snapshot: Option<MetricsSnapshot>,
+    watch_rx: Option<watch::Receiver<u64>>,
     exec_pid: Option<u32>,
+    kill_tx: Option<mpsc::UnboundedSender<()>>,
+    alert_rx: Option<mpsc::UnboundedReceiver<ResourceAlert>>,
+    last_alert_at: Option<Instant>,
     exec_start_time: Option<Instant>,
     exec_duration: Option<f64>,
     exec_complete: bool,
@@ -116,22 +271,30 @@ impl App {
             config.editor.vscode_integration,
         );
 
-        let leader_key = KeyPress::from_string(&config.keybindings.leader_key)
-            .unwrap_or_else(|| KeyPress::from_char(' '));
-
-        let keybinding_manager = KeyBindingManager::new(
-            leader_key,
-            config.keybindings.sequence_timeout_ms,
-            config.keybindings.enable_leader,
-        );
+        let (keybinding_manager, binding_error) = configured_keybindings(config);
+        let (tab_set, tab_error) = configured_tab_set(&root.config);
+        let command_error = join_errors([tab_error, binding_error]);
+        let cmake_cache_existed = builder.has_cmake_cache();
 
         Self {
-            current_tab: TabId::Console,
+            current_tab: tab_set.first(),
+            tab_set,
             log_entries: Vec::new(),
+            previous_log_count: 0,
+            previous_logs_collapsed: true,
             build_steps: Vec::new(),
+            skipped_steps: Vec::new(),
             build_complete: false,
+            fatal_seen: false,
+            completion_notified: false,
+            quit_requested_at: None,
+            pending_clean: None,
             build_duration: None,
-            current_step: None,
+            last_step_finished_at: None,
+            regression_note: None,
+            cmake_cache_existed,
+            running_steps: Vec::new(),
+            stage_windows: Vec::new(),
             steps_completed: 0,
             total_steps: 0,
             start_time: Instant::now(),
@@ -145,11 +308,32 @@ impl App {
             build_menu_open: false,
             exec_menu_open: false,
             exec_menu_selection: 0,
+            preset_picker_open: false,
+            preset_picker_selection: 0,
+            preset_change: None,
+            exec_args_change: None,
+            command_error,
+            build_note: None,
             build_action: None,
             resource_monitor,
             console_scroll_state: ListState::default(),
+            console_selection: None,
+            next_entry_id: 0,
             warnings_scroll_state: ListState::default(),
+            errors_scroll_state: ListState::default(),
+            summary_scroll_state: TableState::default(),
+            history_scroll_state: TableState::default(),
+            history_diff_selection: Vec::new(),
+            trend_include_failed: false,
+            history_query: None,
             auto_scroll: true,
+            unseen_log_count: 0,
+            relative_numbers: config.display.relative_numbers,
+            show_slowest_steps: false,
+            step_sort: StepSort::default(),
+            chart_viewport: ChartViewport::default(),
+            resource_chart: ResourceChart::default(),
+            redraw_requested: false,
             console_viewport_height: 20,
             cached_filtered_log_count: 0,
             filter_cache_dirty: true,
@@ -158,7 +342,13 @@ impl App {
             exec_info: None,
             exec_logs: Vec::new(),
             exec_metrics: HashMap::new(),
+            metric_groups: config.exec.metric_groups.clone(),
             exec_pid: None,
+            kill_tx: None,
+            alert_rx: None,
+            metrics_snapshot: None,
+            watch_rx: None,
+            last_alert_at: None,
             exec_start_time: None,
             exec_duration: None,
             exec_complete: false,
@@ -180,27 +370,45 @@ impl App {
 
         terminal.clear()?;
 
-        let mut last_render = Instant::now();
-        let render_throttle = Duration::from_millis(100);
+        let display = &self.builder.root().config.display;
+        let mut pacer = RenderPacer::new(
+            Duration::from_millis(display.refresh_ms),
+            display.adaptive_refresh,
+        );
 
         loop {
-            if last_render.elapsed() >= render_throttle {
+            if self.redraw_requested {
+                terminal.clear()?;
+                self.redraw_requested = false;
                 terminal.draw(|f| self.render(f))?;
-                last_render = Instant::now();
+                pacer.mark_rendered(Instant::now());
+            } else if pacer.should_render(Instant::now()) {
+                terminal.draw(|f| self.render(f))?;
+                pacer.mark_rendered(Instant::now());
             }
 
-            if event::poll(Duration::from_millis(50))? {
+            if event::poll(pacer.poll_timeout(Instant::now()))? {
                 if let Event::Key(key) = event::read()? {
+                    pacer.mark_input();
                     if self.handle_key(key).await? {
                         break;
                     }
                 }
+            } else if self.flush_pending_sequence().await? {
+                break;
             }
 
             match self.mode {
                 AppMode::Build => self.process_build_updates()?,
                 AppMode::Exec => self.process_exec_updates()?,
             }
+            self.process_alerts();
+            self.notify_if_complete();
+
+            if self.watch_triggered() {
+                self.build_action = Some(BuildAction::Rebuild);
+                break;
+            }
 
             if self.build_complete || self.exec_complete {
                 tokio::time::sleep(Duration::from_millis(10)).await;
@@ -211,41 +419,82 @@ impl App {
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
 
-        Ok(())
+        self.resource_monitor.kill_tracked().await;
+        self.auto_save_logs()?;
+        self.auto_save_perf()
     }
 
     fn handle_step_update(&mut self, update: StepUpdate) {
         match update {
-            StepUpdate::Started(description) => {
-                self.current_step = Some(description);
-                self.total_steps = self.total_steps.max(self.steps_completed + 1);
+            StepUpdate::Started(step, started_at) => {
+                self.running_steps.push(RunningStep {
+                    step,
+                    started_at,
+                    start_offset: self.resource_monitor.offset_of(started_at),
+                });
+                self.total_steps = self
+                    .total_steps
+                    .max(self.steps_completed + self.running_steps.len());
             }
             StepUpdate::Progress(_msg) => {}
-            StepUpdate::Finished(result) => {
+            StepUpdate::Finished(finished_step, result, finished_at) => {
+                let running = self
+                    .running_steps
+                    .iter()
+                    .position(|running| {
+                        running.step.description == finished_step.description
+                            && running.step.target == finished_step.target
+                    })
+                    .map(|index| self.running_steps.remove(index));
+                let start_offset = running
+                    .as_ref()
+                    .map(|running| {
+                        running
+                            .started_at
+                            .saturating_duration_since(self.start_time)
+                            .as_secs_f64()
+                    })
+                    .unwrap_or_default();
                 let error_count = self
-                    .log_entries
+                    .current_build_logs()
                     .iter()
-                    .filter(|e| e.level == LogLevel::Error)
+                    .filter(|e| matches!(e.level, LogLevel::Error | LogLevel::Fatal))
                     .count();
                 let warning_count = self
-                    .log_entries
+                    .current_build_logs()
                     .iter()
                     .filter(|e| e.level == LogLevel::Warning)
                     .count();
 
+                let window_start = running.as_ref().map_or(0.0, |running| running.start_offset);
+                let commands = finished_step.argv();
                 let step = BuildStepResult {
-                    description: self.current_step.clone().unwrap_or_default(),
+                    description: finished_step.description,
                     duration: result.duration,
                     success: result.success,
                     error_count,
                     warning_count,
+                    commands,
+                    target: finished_step.target,
+                    start_offset,
                 };
 
+                self.stage_windows.push(StageWindow {
+                    description: step.description.clone(),
+                    start: window_start,
+                    end: self.resource_monitor.offset_of(finished_at),
+                });
                 self.build_steps.push(step);
                 self.steps_completed += 1;
+                self.last_step_finished_at = Some(finished_at);
 
                 if !result.success {
                     self.build_complete = true;
+                    self.build_duration = Some(
+                        finished_at
+                            .saturating_duration_since(self.start_time)
+                            .as_secs_f64(),
+                    );
                 }
             }
             StepUpdate::ProcessStarted(pid) => {
@@ -254,21 +503,54 @@ impl App {
             StepUpdate::ProcessFinished(pid) => {
                 self.resource_monitor.remove_pid(pid);
             }
+            StepUpdate::Skipped(skipped) => {
+                self.skipped_steps.push(skipped);
+            }
             StepUpdate::Metric(_) => {}
         }
     }
 
+    fn collapse_repeat(&mut self, entry: &LogEntry) -> bool {
+        if !self.builder.root().config.display.collapse_duplicates {
+            return false;
+        }
+
+        let logs = match self.mode {
+            AppMode::Build => &mut self.log_entries[self.previous_log_count..],
+            AppMode::Exec => &mut self.exec_logs[..],
+        };
+        match logs.last_mut() {
+            Some(last) if last.is_repeat_of(entry) => {
+                last.repeat_count += entry.repeat_count;
+                last.timestamp = entry.timestamp;
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn process_build_updates(&mut self) -> Result<()> {
         let mut logs_changed = false;
-        while let Ok(entry) = self.log_rx.try_recv() {
+        while let Ok(mut entry) = self.log_rx.try_recv() {
+            logs_changed = true;
+            if self.collapse_repeat(&entry) {
+                continue;
+            }
+            if !self.auto_scroll {
+                self.unseen_log_count += 1;
+            }
+            if entry.level == LogLevel::Fatal {
+                self.fatal_seen = true;
+            }
+            self.assign_entry_id(&mut entry);
             let max_log_lines = self.builder.root().config.display.max_log_lines;
             if self.log_entries.len() < max_log_lines {
                 self.log_entries.push(entry);
             } else {
                 self.log_entries.remove(0);
+                self.previous_log_count = self.previous_log_count.saturating_sub(1);
                 self.log_entries.push(entry);
             }
-            logs_changed = true;
         }
         if logs_changed {
             self.filter_cache_dirty = true;
@@ -290,7 +572,15 @@ impl App {
 
         if channel_closed && !self.build_complete {
             self.build_complete = true;
-            self.build_duration = Some(self.start_time.elapsed().as_secs_f64());
+            let finished_at = self.last_step_finished_at.unwrap_or_else(Instant::now);
+            let duration = finished_at
+                .saturating_duration_since(self.start_time)
+                .as_secs_f64();
+            self.build_duration = Some(duration);
+
+            if !self.build_steps.is_empty() && self.build_steps.iter().all(|s| s.success) {
+                self.regression_note = self.check_regression(duration);
+            }
         }
 
         Ok(())
@@ -298,7 +588,15 @@ impl App {
 
     fn process_exec_updates(&mut self) -> Result<()> {
         let mut logs_changed = false;
-        while let Ok(entry) = self.log_rx.try_recv() {
+        while let Ok(mut entry) = self.log_rx.try_recv() {
+            logs_changed = true;
+            if self.collapse_repeat(&entry) {
+                continue;
+            }
+            if !self.auto_scroll {
+                self.unseen_log_count += 1;
+            }
+            self.assign_entry_id(&mut entry);
             let max_log_lines = self.builder.root().config.display.max_log_lines;
             if self.exec_logs.len() < max_log_lines {
                 self.exec_logs.push(entry);
@@ -306,7 +604,6 @@ impl App {
                 self.exec_logs.remove(0);
                 self.exec_logs.push(entry);
             }
-            logs_changed = true;
         }
         if logs_changed {
             self.filter_cache_dirty = true;
@@ -327,23 +624,26 @@ impl App {
                     self.exec_duration = self.exec_start_time.map(|t| t.elapsed().as_secs_f64());
                 }
                 Ok(StepUpdate::Metric(metric)) => {
-                    let key = format!("{}:{}", metric.category, metric.key);
+                    let key = metric.series_key();
 
                     if let Some(value) = metric.parse_numeric_value() {
-                        self.exec_metrics
+                        let history = self
+                            .exec_metrics
                             .entry(key.clone())
-                            .or_insert_with(|| {
-                                MetricHistory::new(
-                                    metric.category.clone(),
-                                    metric.key.clone(),
-                                    metric.metric_type(),
-                                    metric.visualization(),
-                                )
-                            })
-                            .add_value(value, metric.timestamp);
+                            .or_insert_with(|| MetricHistory::from_metric(&metric));
+                        if metric.range.is_some() {
+                            history.range = metric.range;
+                        }
+                        history.add_value(value, metric.timestamp);
+
+                        if let Some(snapshot) = &self.metrics_snapshot {
+                            if let Ok(mut snapshot) = snapshot.lock() {
+                                snapshot.insert(key, history.clone());
+                            }
+                        }
                     }
                 }
-                Ok(StepUpdate::Finished(result)) => {
+                Ok(StepUpdate::Finished(_, result, _)) => {
                     self.exec_exit_code = result.exit_code;
                     self.exec_failure_reason = result.failure_reason;
                 }
@@ -364,7 +664,23 @@ impl App {
         Ok(())
     }
 
+    async fn flush_pending_sequence(&mut self) -> Result<bool> {
+        let action = resolve_sequence_timeout(&mut self.vim_mode, &self.keybinding_manager);
+        if matches!(action, InputAction::None) {
+            return Ok(false);
+        }
+
+        match self.mode {
+            AppMode::Build => self.handle_build_action(action).await,
+            AppMode::Exec => self.handle_exec_action(action).await,
+        }
+    }
+
     async fn handle_key(&mut self, key: event::KeyEvent) -> Result<bool> {
+        if key.code == event::KeyCode::Esc && self.quit_requested_at.take().is_some() {
+            return Ok(false);
+        }
+
         match self.mode {
             AppMode::Build => self.handle_build_key(key).await,
             AppMode::Exec => self.handle_exec_key(key).await,
@@ -372,6 +688,12 @@ impl App {
     }
 
     async fn handle_build_key(&mut self, key: event::KeyEvent) -> Result<bool> {
+        self.command_error = None;
+
+        if self.preset_picker_open {
+            return self.handle_preset_picker_key(key).await;
+        }
+
         if self.exec_menu_open {
             let should_quit = self.handle_exec_menu_key(key).await?;
             if should_quit {
@@ -397,26 +719,105 @@ impl App {
             is_command,
             is_search,
         );
+        self.handle_build_action(action).await
+    }
 
+    async fn handle_build_action(&mut self, action: InputAction) -> Result<bool> {
         match action {
-            InputAction::Quit => return Ok(true),
-            InputAction::SwitchTab(index) => {
-                if let Some(tab) = TabId::from_index(index) {
-                    self.current_tab = tab;
+            InputAction::Quit => Ok(self.confirm_quit()),
+            InputAction::OpenExecMenu => {
+                self.exec_menu_open = !self.exec_menu_open;
+                self.exec_menu_selection = 0;
+                Ok(false)
+            }
+            InputAction::CleanBuild => {
+                self.build_action = Some(BuildAction::Clean);
+                Ok(true)
+            }
+            InputAction::Rebuild => {
+                self.build_action = Some(BuildAction::Rebuild);
+                Ok(true)
+            }
+            InputAction::TogglePreviousLogs => {
+                self.toggle_previous_logs();
+                Ok(false)
+            }
+            action => self.handle_common_action(action).await,
+        }
+    }
+
+    async fn handle_exec_key(&mut self, key: event::KeyEvent) -> Result<bool> {
+        if self.build_menu_open {
+            let should_quit = self.handle_build_menu_key(key).await?;
+            if should_quit {
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        let is_command = self.vim_mode.mode == InputMode::Command;
+        let is_search = self.vim_mode.mode == InputMode::Search;
+
+        let action = handle_key_event(
+            key,
+            &mut self.vim_mode,
+            &self.keybinding_manager,
+            is_command,
+            is_search,
+        );
+        self.handle_exec_action(action).await
+    }
+
+    async fn handle_exec_action(&mut self, action: InputAction) -> Result<bool> {
+        match action {
+            InputAction::Quit => {
+                if !self.confirm_quit() {
+                    return Ok(false);
+                }
+                self.exec_action = Some(ExecAction::QuitToBuild);
+                Ok(true)
+            }
+            InputAction::RestartExec => {
+                if !self.exec_complete {
+                    return Ok(false);
                 }
+                self.exec_action = Some(ExecAction::Restart);
+                Ok(true)
             }
-            InputAction::NextTab => {
-                self.current_tab = self.current_tab.next();
+            InputAction::KillExec => {
+                if self.exec_complete {
+                    return Ok(false);
+                }
+                self.kill_program();
+                self.exec_action = Some(ExecAction::Kill);
+                Ok(true)
             }
-            InputAction::PrevTab => {
-                self.current_tab = self.current_tab.prev();
+            action => self.handle_common_action(action).await,
+        }
+    }
+
+    async fn handle_common_action(&mut self, action: InputAction) -> Result<bool> {
+        match action {
+            InputAction::SwitchTab(index) => {
+                if let Some(tab) = self.tab_set.from_index(index) {
+                    self.current_tab = tab;
+                }
             }
+            InputAction::NextTab => self.cycle_tab(true, 1),
+            InputAction::PrevTab => self.cycle_tab(false, 1),
+            InputAction::NextTabCount(count) => self.cycle_tab(true, count),
+            InputAction::PrevTabCount(count) => self.cycle_tab(false, count),
             InputAction::EnterCommand => self.vim_mode.enter_command_mode(),
             InputAction::EnterSearch => self.vim_mode.enter_search_mode(),
             InputAction::ExecuteCommand => {
                 if let Some(result) = self.vim_mode.execute_command() {
                     self.handle_command_result(result).await?;
                 }
+                if self.mode == AppMode::Build
+                    && (self.preset_change.is_some() || self.build_action.is_some())
+                {
+                    return Ok(true);
+                }
             }
             InputAction::ExecuteSearch => {
                 if let Some(result) = self.vim_mode.execute_search() {
@@ -426,41 +827,41 @@ impl App {
             InputAction::CancelInput => self.vim_mode.exit_to_normal(),
             InputAction::InsertChar(c) => self.vim_mode.push_char(c),
             InputAction::Backspace => self.vim_mode.pop_char(),
-            InputAction::NextSearch => {
-                if self.current_tab == TabId::Console && self.search_pattern.is_some() {
-                    let current = self.console_scroll_state.selected().unwrap_or(0);
-                    if let Some(next_idx) = self.find_next_search_match(current) {
-                        self.console_scroll_state.select(Some(next_idx));
-                        self.auto_scroll = false;
-                    }
-                }
-            }
-            InputAction::PrevSearch => {
-                if self.current_tab == TabId::Console && self.search_pattern.is_some() {
-                    let current = self.console_scroll_state.selected().unwrap_or(0);
-                    if let Some(prev_idx) = self.find_prev_search_match(current) {
-                        self.console_scroll_state.select(Some(prev_idx));
-                        self.auto_scroll = false;
-                    }
-                }
-            }
+            InputAction::NextSearch => self.jump_to_search_match(true, 1),
+            InputAction::PrevSearch => self.jump_to_search_match(false, 1),
+            InputAction::NextSearchCount(count) => self.jump_to_search_match(true, count),
+            InputAction::PrevSearchCount(count) => self.jump_to_search_match(false, count),
             InputAction::OpenFile => self.open_current_file()?,
+            InputAction::NextDiagnostic => self.jump_to_diagnostic(true),
+            InputAction::PrevDiagnostic => self.jump_to_diagnostic(false),
+            InputAction::NextError => self.jump_to_level(LogLevel::Error, true),
+            InputAction::PrevError => self.jump_to_level(LogLevel::Error, false),
+            InputAction::NextWarning => self.jump_to_level(LogLevel::Warning, true),
+            InputAction::PrevWarning => self.jump_to_level(LogLevel::Warning, false),
             InputAction::YankLine => self.yank_current_line()?,
             InputAction::OpenBuildMenu => {
                 self.build_menu_open = !self.build_menu_open;
             }
-            InputAction::OpenExecMenu => {
-                self.exec_menu_open = !self.exec_menu_open;
-                self.exec_menu_selection = 0;
-            }
             InputAction::ScrollUp => {
                 if self.current_tab == TabId::Console {
                     self.scroll_console_up(1);
+                } else if self.current_tab == TabId::History {
+                    self.scroll_history(-1);
+                } else if self.is_diagnostics_tab() {
+                    self.scroll_warnings(-1);
+                } else if self.current_tab == TabId::Summary {
+                    self.scroll_summary(-1);
                 }
             }
             InputAction::ScrollDown => {
                 if self.current_tab == TabId::Console {
                     self.scroll_console_down(1);
+                } else if self.current_tab == TabId::History {
+                    self.scroll_history(1);
+                } else if self.is_diagnostics_tab() {
+                    self.scroll_warnings(1);
+                } else if self.current_tab == TabId::Summary {
+                    self.scroll_summary(1);
                 }
             }
             InputAction::ScrollPageUp => {
@@ -516,11 +917,23 @@ impl App {
             InputAction::ScrollUpCount(count) => {
                 if self.current_tab == TabId::Console {
                     self.scroll_console_up(count);
+                } else if self.current_tab == TabId::History {
+                    self.scroll_history(-(count as isize));
+                } else if self.is_diagnostics_tab() {
+                    self.scroll_warnings(-(count as isize));
+                } else if self.current_tab == TabId::Summary {
+                    self.scroll_summary(-(count as isize));
                 }
             }
             InputAction::ScrollDownCount(count) => {
                 if self.current_tab == TabId::Console {
                     self.scroll_console_down(count);
+                } else if self.current_tab == TabId::History {
+                    self.scroll_history(count as isize);
+                } else if self.is_diagnostics_tab() {
+                    self.scroll_warnings(count as isize);
+                } else if self.current_tab == TabId::Summary {
+                    self.scroll_summary(count as isize);
                 }
             }
             InputAction::ScrollPageUpCount(count) => {
@@ -554,252 +967,400 @@ impl App {
             InputAction::WriteLogs => {
                 self.write_logs(None)?;
             }
-            InputAction::CleanBuild => {
-                self.build_action = Some(BuildAction::Clean);
-                return Ok(true);
-            }
-            InputAction::Rebuild => {
-                self.build_action = Some(BuildAction::Rebuild);
-                return Ok(true);
-            }
             InputAction::ShowHelp => {
                 self.show_help();
             }
-            InputAction::RestartExec | InputAction::KillExec => {}
-            InputAction::None => {}
-        }
-
-        Ok(false)
-    }
-
-    async fn handle_exec_key(&mut self, key: event::KeyEvent) -> Result<bool> {
-        if self.build_menu_open {
-            let should_quit = self.handle_build_menu_key(key).await?;
-            if should_quit {
-                return Ok(true);
+            InputAction::ToggleRelativeNumbers => {
+                self.relative_numbers = !self.relative_numbers;
             }
-            return Ok(false);
-        }
-
-        let is_command = self.vim_mode.mode == InputMode::Command;
-        let is_search = self.vim_mode.mode == InputMode::Search;
-
-        if !is_command && !is_search {
-            match key.code {
-                event::KeyCode::Char('r') | event::KeyCode::Char('R') => {
-                    if self.exec_complete {
-                        self.exec_action = Some(ExecAction::Restart);
-                        return Ok(true);
-                    }
-                    return Ok(false);
-                }
-                event::KeyCode::Char('k') | event::KeyCode::Char('K') => {
-                    if !self.exec_complete {
-                        if let Some(pid) = self.exec_pid {
-                            #[cfg(unix)]
-                            {
-                                use nix::sys::signal::{kill, Signal};
-                                use nix::unistd::Pid;
-                                let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
-                            }
-                        }
-                        self.exec_action = Some(ExecAction::Kill);
-                        return Ok(true);
-                    }
-                    return Ok(false);
+            InputAction::ToggleFollow => self.toggle_follow(),
+            InputAction::HistoryDiff => {
+                if self.current_tab == TabId::History {
+                    self.toggle_history_diff_mark();
                 }
-                _ => {}
-            }
-        }
-
-        let action = handle_key_event(
-            key,
-            &mut self.vim_mode,
-            &self.keybinding_manager,
-            is_command,
-            is_search,
-        );
-
-        match action {
-            InputAction::Quit => {
-                self.exec_action = Some(ExecAction::QuitToBuild);
-                return Ok(true);
             }
-            InputAction::SwitchTab(index) => {
-                if let Some(tab) = TabId::from_index(index) {
-                    self.current_tab = tab;
+            InputAction::ToggleSlowestSteps => {
+                if self.current_tab == TabId::Performance {
+                    self.show_slowest_steps = !self.show_slowest_steps;
                 }
             }
-            InputAction::NextTab => {
-                self.current_tab = self.current_tab.next();
-            }
-            InputAction::PrevTab => {
-                self.current_tab = self.current_tab.prev();
-            }
-            InputAction::EnterCommand => self.vim_mode.enter_command_mode(),
-            InputAction::EnterSearch => self.vim_mode.enter_search_mode(),
-            InputAction::ExecuteCommand => {
-                if let Some(result) = self.vim_mode.execute_command() {
-                    self.handle_command_result(result).await?;
+            InputAction::ToggleTrendFailures => {
+                if self.current_tab == TabId::History {
+                    self.trend_include_failed = !self.trend_include_failed;
                 }
             }
-            InputAction::ExecuteSearch => {
-                if let Some(result) = self.vim_mode.execute_search() {
-                    self.handle_command_result(result).await?;
+            InputAction::CycleStepSort => {
+                if self.current_tab == TabId::Summary {
+                    self.step_sort = self.step_sort.next();
                 }
             }
-            InputAction::CancelInput => self.vim_mode.exit_to_normal(),
-            InputAction::InsertChar(c) => self.vim_mode.push_char(c),
-            InputAction::Backspace => self.vim_mode.pop_char(),
-            InputAction::NextSearch => {
-                if self.current_tab == TabId::Console && self.search_pattern.is_some() {
-                    let current = self.console_scroll_state.selected().unwrap_or(0);
-                    if let Some(next_idx) = self.find_next_search_match(current) {
-                        self.console_scroll_state.select(Some(next_idx));
-                        self.auto_scroll = false;
-                    }
+            InputAction::PanChartLeft | InputAction::PanChartRight => {
+                if self.current_tab == TabId::Performance {
+                    let span = self.chart_span();
+                    self.chart_viewport
+                        .pan(action == InputAction::PanChartRight, span);
                 }
             }
-            InputAction::PrevSearch => {
-                if self.current_tab == TabId::Console && self.search_pattern.is_some() {
-                    let current = self.console_scroll_state.selected().unwrap_or(0);
-                    if let Some(prev_idx) = self.find_prev_search_match(current) {
-                        self.console_scroll_state.select(Some(prev_idx));
-                        self.auto_scroll = false;
-                    }
+            InputAction::ZoomChartIn => {
+                if self.current_tab == TabId::Performance {
+                    self.chart_viewport.zoom_in();
                 }
             }
-            InputAction::ScrollUp => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_up(1);
+            InputAction::ZoomChartOut => {
+                if self.current_tab == TabId::Performance {
+                    self.chart_viewport.zoom_out();
                 }
             }
-            InputAction::ScrollDown => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_down(1);
+            InputAction::CycleResourceChart => {
+                if self.current_tab == TabId::Performance {
+                    self.resource_chart = self.resource_chart.next();
                 }
             }
-            InputAction::ScrollPageUp => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_page_up();
-                }
+            InputAction::Redraw => {
+                self.redraw_requested = true;
             }
-            InputAction::ScrollPageDown => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_page_down();
-                }
+            InputAction::Quit
+            | InputAction::OpenExecMenu
+            | InputAction::CleanBuild
+            | InputAction::Rebuild
+            | InputAction::TogglePreviousLogs
+            | InputAction::RestartExec
+            | InputAction::KillExec
+            | InputAction::None => {}
+        }
+
+        Ok(false)
+    }
+
+    async fn handle_command_result(&mut self, result: CommandResult) -> Result<()> {
+        match result {
+            CommandResult::Quit => return Ok(()),
+            CommandResult::WriteLogs(file) => {
+                self.write_logs(file)?;
             }
-            InputAction::ScrollHalfPageUp => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_half_page_up();
-                }
+            CommandResult::WritePerf(file) => {
+                self.write_perf(file)?;
             }
-            InputAction::ScrollHalfPageDown => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_half_page_down();
-                }
+            CommandResult::WriteTrace(file) => {
+                let path = file.unwrap_or_else(|| {
+                    format!(
+                        "build_trace_{}.json",
+                        chrono::Local::now().format("%Y%m%d_%H%M%S")
+                    )
+                });
+                write_trace(std::path::Path::new(&path), &self.build_steps)?;
             }
-            InputAction::ScrollToTop => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_top();
-                }
+            CommandResult::ApplyFilter(filter) => {
+                self.active_filter = Some(filter);
+                self.filter_cache_dirty = true;
             }
-            InputAction::ScrollToBottom => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_bottom();
-                }
+            CommandResult::ClearFilter if self.is_build_history_tab() => {
+                self.set_history_query(None);
             }
-            InputAction::ScrollToMiddle => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_middle();
-                }
+            CommandResult::ClearFilter => {
+                self.active_filter = None;
+                self.filter_cache_dirty = true;
             }
-            InputAction::ScrollToViewportTop => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_viewport_top();
-                }
+            CommandResult::Search(pattern, _) if self.is_build_history_tab() => {
+                self.set_history_query(Some(pattern));
             }
-            InputAction::ScrollToViewportMiddle => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_viewport_middle();
-                }
+            CommandResult::Search(pattern, filter) => {
+                self.search_pattern = Some(pattern);
+                self.active_filter = Some(filter);
+                self.filter_cache_dirty = true;
             }
-            InputAction::ScrollToViewportBottom => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_to_viewport_bottom();
-                }
+            CommandResult::GotoLine(line_number) => {
+                self.goto_line(line_number);
             }
-            InputAction::ScrollUpCount(count) => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_up(count);
-                }
+            CommandResult::ClearPreviousLogs => {
+                self.clear_previous_logs();
             }
-            InputAction::ScrollDownCount(count) => {
-                if self.current_tab == TabId::Console {
-                    self.scroll_console_down(count);
-                }
+            CommandResult::SetRelativeNumbers(enabled) => {
+                self.relative_numbers = enabled;
             }
-            InputAction::ScrollPageUpCount(count) => {
-                if self.current_tab == TabId::Console {
-                    for _ in 0..count {
-                        self.scroll_console_page_up();
-                    }
-                }
+            CommandResult::ToggleRelativeNumbers => {
+                self.relative_numbers = !self.relative_numbers;
             }
-            InputAction::ScrollPageDownCount(count) => {
-                if self.current_tab == TabId::Console {
-                    for _ in 0..count {
-                        self.scroll_console_page_down();
-                    }
-                }
+            CommandResult::SetPreset(name) => {
+                self.switch_preset(&name);
             }
-            InputAction::ScrollHalfPageUpCount(count) => {
-                if self.current_tab == TabId::Console {
-                    for _ in 0..count {
-                        self.scroll_console_half_page_up();
-                    }
-                }
+            CommandResult::OpenPresetPicker => {
+                self.open_preset_picker();
             }
-            InputAction::ScrollHalfPageDownCount(count) => {
-                if self.current_tab == TabId::Console {
-                    for _ in 0..count {
-                        self.scroll_console_half_page_down();
-                    }
-                }
+            CommandResult::SetNote(note) => {
+                self.set_build_note(note);
             }
-            InputAction::OpenBuildMenu => {
-                self.build_menu_open = !self.build_menu_open;
+            CommandResult::SetExecArgs(args) => {
+                self.set_exec_args(args);
+            }
+            CommandResult::ToggleMetricGroup(category) => {
+                self.toggle_metric_group(category);
+            }
+            CommandResult::InstallOnly => {
+                self.request_install_only();
             }
-            _ => {}
         }
+        Ok(())
+    }
 
-        Ok(false)
+    fn request_install_only(&mut self) {
+        if self.mode != AppMode::Build {
+            self.command_error = Some("Install can only be run in build mode".to_string());
+            return;
+        }
+
+        let targets = self.builder.targets();
+        let has_build = !targets.is_empty()
+            && targets.iter().all(|target| {
+                let (build_dir, _) = self
+                    .builder
+                    .get_dirs(target.path.clone(), self.builder.preset());
+                build_dir.exists()
+            });
+
+        if has_build {
+            self.build_action = Some(BuildAction::Install);
+        } else {
+            self.command_error =
+                Some("No previous build found, run a full build before installing".to_string());
+        }
     }
 
-    async fn handle_command_result(&mut self, result: CommandResult) -> Result<()> {
-        match result {
-            CommandResult::Quit => return Ok(()),
-            CommandResult::WriteLogs(file) => {
-                self.write_logs(file)?;
-            }
-            CommandResult::ApplyFilter(filter) => {
-                self.active_filter = Some(filter);
-                self.filter_cache_dirty = true;
-            }
-            CommandResult::ClearFilter => {
-                self.active_filter = None;
-                self.filter_cache_dirty = true;
-            }
-            CommandResult::Search(pattern, filter) => {
-                self.search_pattern = Some(pattern);
-                self.active_filter = Some(filter);
-                self.filter_cache_dirty = true;
+    fn set_exec_args(&mut self, args: Vec<String>) {
+        if self.mode != AppMode::Exec || self.exec_info.is_none() {
+            self.command_error = Some("Arguments can only be set in exec mode".to_string());
+            return;
+        }
+
+        self.exec_args_change = Some(args);
+    }
+
+    fn toggle_metric_group(&mut self, category: String) {
+        if let Some(pos) = self.metric_groups.iter().position(|c| *c == category) {
+            self.metric_groups.remove(pos);
+        } else {
+            self.metric_groups.push(category);
+        }
+    }
+
+    pub fn with_kill_switch(mut self, kill_tx: mpsc::UnboundedSender<()>) -> Self {
+        self.kill_tx = Some(kill_tx);
+        self
+    }
+
+    pub fn with_metrics_snapshot(mut self, snapshot: MetricsSnapshot) -> Self {
+        self.metrics_snapshot = Some(snapshot);
+        self
+    }
+
+    pub fn with_alerts(mut self, alert_rx: mpsc::UnboundedReceiver<ResourceAlert>) -> Self {
+        self.alert_rx = Some(alert_rx);
+        self
+    }
+
+    pub fn with_watch(mut self, watch_rx: watch::Receiver<u64>) -> Self {
+        self.watch_rx = Some(watch_rx);
+        self
+    }
+
+    fn watch_triggered(&mut self) -> bool {
+        let Some(watch_rx) = self.watch_rx.as_mut() else {
+            return false;
+        };
+
+        if watch_rx.has_changed().unwrap_or(false) {
+            watch_rx.borrow_and_update();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn process_alerts(&mut self) {
+        let Some(alert_rx) = self.alert_rx.as_mut() else {
+            return;
+        };
+
+        let mut alerts = Vec::new();
+        while let Ok(alert) = alert_rx.try_recv() {
+            alerts.push(alert);
+        }
+
+        for alert in alerts {
+            let message = alert.to_string();
+            let mut entry = LogEntry::new(
+                LogLevel::Warning,
+                message.clone(),
+                message,
+                LogComponent::Other("monitor".to_string()),
+                0,
+            )
+            .with_tags(vec!["alert".to_string()]);
+            self.assign_entry_id(&mut entry);
+
+            match self.mode {
+                AppMode::Build => self.log_entries.push(entry),
+                AppMode::Exec => self.exec_logs.push(entry),
             }
-            CommandResult::GotoLine(line_number) => {
-                self.goto_line(line_number);
+            self.filter_cache_dirty = true;
+            self.last_alert_at = Some(Instant::now());
+        }
+    }
+
+    fn build_outcome(&self) -> Option<BuildOutcome> {
+        if self.mode != AppMode::Build || !self.build_complete || self.completion_notified {
+            return None;
+        }
+
+        let error_count = self
+            .current_build_logs()
+            .iter()
+            .filter(|e| matches!(e.level, LogLevel::Error | LogLevel::Fatal))
+            .count();
+        Some(BuildOutcome {
+            success: !self.fatal_seen && self.build_steps.iter().all(|s| s.success),
+            duration: self.build_duration.unwrap_or_default(),
+            preset: self.builder.preset().to_string(),
+            error_count,
+        })
+    }
+
+    fn notify_if_complete(&mut self) {
+        let Some(outcome) = self.build_outcome() else {
+            return;
+        };
+        self.completion_notified = true;
+
+        if self.builder.root().config.display.notify_on_complete {
+            notify_build_complete(&outcome);
+        }
+    }
+
+    fn alert_flash_on(&self) -> bool {
+        self.last_alert_at.is_some_and(|at| {
+            let elapsed = at.elapsed();
+            elapsed < ALERT_FLASH && (elapsed.as_millis() / 250) % 2 == 0
+        })
+    }
+
+    fn is_running(&self) -> bool {
+        match self.mode {
+            AppMode::Build => !self.build_complete,
+            AppMode::Exec => !self.exec_complete,
+        }
+    }
+
+    fn confirm_quit(&mut self) -> bool {
+        if !self.is_running() {
+            return true;
+        }
+
+        let confirmed = !self.builder.root().config.display.confirm_quit
+            || self
+                .quit_requested_at
+                .is_some_and(|at| at.elapsed() < QUIT_CONFIRM_WINDOW);
+        if !confirmed {
+            self.quit_requested_at = Some(Instant::now());
+            return false;
+        }
+
+        self.quit_requested_at = None;
+        if self.mode == AppMode::Exec {
+            self.kill_program();
+        }
+        true
+    }
+
+    fn quit_prompt(&self) -> Option<&'static str> {
+        self.quit_requested_at
+            .filter(|at| at.elapsed() < QUIT_CONFIRM_WINDOW)
+            .map(|_| match self.mode {
+                AppMode::Build => "Build running — press q again to abort, Esc to cancel",
+                AppMode::Exec => "Program running — press q again to stop it, Esc to cancel",
+            })
+    }
+
+    fn kill_program(&mut self) {
+        // With a kill switch the executor owns the child and terminates it the same way
+        // on every platform; the raw SIGTERM below is only a fallback for Unix.
+        if let Some(kill_tx) = &self.kill_tx {
+            let _ = kill_tx.send(());
+        } else if let Some(_pid) = self.exec_pid {
+            #[cfg(unix)]
+            {
+                use nix::sys::signal::{kill, Signal};
+                use nix::unistd::Pid;
+                let _ = kill(Pid::from_raw(_pid as i32), Signal::SIGTERM);
             }
         }
-        Ok(())
+
+        if let Some(pid) = self.exec_pid.take() {
+            self.resource_monitor.remove_pid(pid);
+        }
+    }
+
+    pub fn take_exec_args_change(&mut self) -> Option<Vec<String>> {
+        self.exec_args_change.take()
+    }
+
+    fn is_build_history_tab(&self) -> bool {
+        self.mode == AppMode::Build && self.current_tab == TabId::History
+    }
+
+    fn set_history_query(&mut self, query: Option<String>) {
+        self.history_query = query;
+        let has_rows = !self.visible_history_rows().is_empty();
+        self.history_scroll_state.select(has_rows.then_some(0));
+    }
+
+    fn set_build_note(&mut self, note: Option<String>) {
+        if self.mode != AppMode::Build || !self.build_complete {
+            self.command_error =
+                Some("Notes can only be added once the build is complete".to_string());
+            return;
+        }
+
+        self.build_note = note;
+    }
+
+    fn available_presets(&self) -> Vec<String> {
+        available_presets(&self.builder.root().config, &self.builder.root().path)
+    }
+
+    fn switch_preset(&mut self, name: &str) {
+        if self.mode != AppMode::Build {
+            self.command_error = Some("Presets can only be switched in build mode".to_string());
+            return;
+        }
+
+        match resolve_preset(name, &self.available_presets()) {
+            Ok(preset) if preset == self.builder.preset() => {}
+            Ok(preset) => self.preset_change = Some(preset),
+            Err(e) => self.command_error = Some(e.to_string()),
+        }
+    }
+
+    fn open_preset_picker(&mut self) {
+        if self.mode != AppMode::Build {
+            self.command_error = Some("Presets can only be switched in build mode".to_string());
+            return;
+        }
+
+        self.preset_picker_selection = self
+            .available_presets()
+            .iter()
+            .position(|p| p == self.builder.preset())
+            .unwrap_or(0);
+        self.build_menu_open = false;
+        self.preset_picker_open = true;
+    }
+
+    pub fn take_preset_change(&mut self) -> Option<String> {
+        self.preset_change.take()
+    }
+
+    pub fn open_build_menu(&mut self) {
+        self.build_menu_open = true;
     }
 
     fn write_logs(&self, file: Option<String>) -> Result<()> {
@@ -822,7 +1383,10 @@ impl App {
 
         let content = logs
             .iter()
-            .map(|e| e.raw_line.clone())
+            .map(|e| match e.repeat_count {
+                1 => e.raw_line.clone(),
+                count => format!("{} (x{})", e.raw_line, count),
+            })
             .collect::<Vec<_>>()
             .join("\n");
 
@@ -832,13 +1396,208 @@ impl App {
         Ok(())
     }
 
-    fn open_current_file(&self) -> Result<()> {
-        let logs = match self.mode {
-            AppMode::Build => &self.log_entries,
+    fn perf_file_name(&self) -> String {
+        let prefix = match self.mode {
+            AppMode::Build => "build_perf",
+            AppMode::Exec => "exec_perf",
+        };
+        format!(
+            "{}_{}.csv",
+            prefix,
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        )
+    }
+
+    fn write_perf(&self, file: Option<String>) -> Result<()> {
+        let path = file.unwrap_or_else(|| self.perf_file_name());
+        let content = samples_to_csv(&self.resource_monitor.get_stats().samples);
+
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write resource samples to {}", path))?;
+
+        Ok(())
+    }
+
+    fn auto_save_logs(&self) -> Result<()> {
+        let config = &self.builder.root().config;
+        if !config.logs.auto_save {
+            return Ok(());
+        }
+
+        let runs = [
+            ("build_log", self.current_build_logs()),
+            ("exec_log", self.exec_logs.as_slice()),
+        ];
+        for (prefix, logs) in runs {
+            if logs.is_empty() {
+                continue;
+            }
+
+            let mut file = LogFile::create(
+                &config.log_directory(),
+                prefix,
+                config.logs.max_file_size_kb * 1024,
+            )?;
+            for entry in logs {
+                file.write_line(&entry.raw_line)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn auto_save_perf(&self) -> Result<()> {
+        let config = &self.builder.root().config;
+        if !config.logs.auto_save_perf {
+            return Ok(());
+        }
+
+        let dir = config.log_directory();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(self.perf_file_name());
+        self.write_perf(Some(path.to_string_lossy().to_string()))
+    }
+
+    fn selected_entry(&self) -> Option<&LogEntry> {
+        if self.is_diagnostics_tab() {
+            let selected = self.diagnostics_state().selected()?;
+            return self.diagnostics().get(selected).copied();
+        }
+
+        let logs = self.console_logs();
+        match self.selected_console_position() {
+            Some(position) => self.console_view().get(position).map(|&raw| &logs[raw]),
+            None => logs.last(),
+        }
+    }
+
+    fn warning_logs(&self) -> &[LogEntry] {
+        match self.mode {
+            AppMode::Build => self.current_build_logs(),
             AppMode::Exec => &self.exec_logs,
+        }
+    }
+
+    fn is_diagnostics_tab(&self) -> bool {
+        matches!(self.current_tab, TabId::Warnings | TabId::Errors)
+    }
+
+    fn diagnostics(&self) -> Vec<&LogEntry> {
+        if self.current_tab == TabId::Errors {
+            WarningsTab::errors(self.warning_logs())
+        } else {
+            WarningsTab::diagnostics(self.warning_logs())
+        }
+    }
+
+    fn diagnostics_state(&self) -> &ListState {
+        if self.current_tab == TabId::Errors {
+            &self.errors_scroll_state
+        } else {
+            &self.warnings_scroll_state
+        }
+    }
+
+    fn select_diagnostic(&mut self, index: usize) {
+        if self.current_tab == TabId::Errors {
+            self.errors_scroll_state.select(Some(index));
+        } else {
+            self.warnings_scroll_state.select(Some(index));
+        }
+    }
+
+    fn navigable_diagnostics(&self) -> Vec<usize> {
+        self.diagnostics()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.file_path.is_some())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn diagnostic_position(&self) -> Option<(usize, usize)> {
+        let navigable = self.navigable_diagnostics();
+        if navigable.is_empty() {
+            return None;
+        }
+
+        let current = self
+            .diagnostics_state()
+            .selected()
+            .and_then(|selected| navigable.iter().position(|&i| i == selected))
+            .map_or(0, |pos| pos + 1);
+        Some((current, navigable.len()))
+    }
+
+    fn jump_to_diagnostic(&mut self, forward: bool) {
+        if !self.is_diagnostics_tab() {
+            return;
+        }
+
+        let navigable = self.navigable_diagnostics();
+        let (Some(&first), Some(&last)) = (navigable.first(), navigable.last()) else {
+            return;
+        };
+
+        let target = match self.diagnostics_state().selected() {
+            None if forward => first,
+            None => last,
+            Some(selected) if forward => navigable
+                .iter()
+                .copied()
+                .find(|&i| i > selected)
+                .unwrap_or(first),
+            Some(selected) => navigable
+                .iter()
+                .rev()
+                .copied()
+                .find(|&i| i < selected)
+                .unwrap_or(last),
         };
+        self.select_diagnostic(target);
+    }
+
+    fn scroll_warnings(&mut self, delta: isize) {
+        let count = self.diagnostics().len();
+        if count == 0 {
+            return;
+        }
+
+        let selected = self.diagnostics_state().selected().unwrap_or(0) as isize;
+        let new_selected = (selected + delta).clamp(0, count as isize - 1);
+        self.select_diagnostic(new_selected as usize);
+    }
+
+    fn scroll_summary(&mut self, delta: isize) {
+        let count = self.build_steps.len();
+        if count == 0 {
+            return;
+        }
+
+        let selected = self.summary_scroll_state.selected().unwrap_or(0) as isize;
+        let new_selected = (selected + delta).clamp(0, count as isize - 1);
+        self.summary_scroll_state
+            .select(Some(new_selected as usize));
+    }
+
+    fn chart_span(&self) -> f64 {
+        let samples = self.resource_monitor.get_stats().samples;
+        match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) => last.timestamp - first.timestamp,
+            _ => 0.0,
+        }
+    }
+
+    fn selected_step(&self) -> Option<&BuildStepResult> {
+        let selected = self.summary_scroll_state.selected()?;
+        self.step_sort
+            .sorted(&self.build_steps)
+            .get(selected)
+            .copied()
+    }
 
-        if let Some(entry) = logs.last() {
+    fn open_current_file(&self) -> Result<()> {
+        if let Some(entry) = self.selected_entry() {
             if let Some(file_path) = &entry.file_path {
                 self.editor.open_file(
                     std::path::Path::new(file_path),
@@ -850,39 +1609,196 @@ impl App {
         Ok(())
     }
 
-    fn yank_current_line(&self) -> Result<()> {
-        let logs = match self.mode {
-            AppMode::Build => &self.log_entries,
-            AppMode::Exec => &self.exec_logs,
-        };
+    fn yank_text(&self) -> Option<String> {
+        if self.current_tab == TabId::Summary {
+            self.selected_step()
+                .filter(|s| !s.commands.is_empty())
+                .map(|step| shell_join(&step.commands))
+        } else {
+            self.selected_entry().map(|entry| entry.raw_line.clone())
+        }
+    }
 
-        if let Some(entry) = logs.last() {
-            copy_to_clipboard(&entry.raw_line)?;
+    fn yank_current_line(&self) -> Result<()> {
+        if let Some(text) = self.yank_text() {
+            copy_to_clipboard(&text)?;
         }
         Ok(())
     }
 
     fn show_help(&self) {}
 
-    fn refresh_filter_cache(&mut self) {
-        let logs = match self.mode {
-            AppMode::Build => &self.log_entries,
+    fn visible_log_start(&self) -> usize {
+        if self.previous_logs_collapsed {
+            self.previous_log_count.saturating_sub(1)
+        } else {
+            0
+        }
+    }
+
+    fn console_logs(&self) -> &[LogEntry] {
+        match self.mode {
+            AppMode::Build => &self.log_entries[self.visible_log_start()..],
             AppMode::Exec => &self.exec_logs,
-        };
+        }
+    }
 
-        self.cached_filtered_log_count = if let Some(filter) = &self.active_filter {
+    fn current_build_logs(&self) -> &[LogEntry] {
+        &self.log_entries[self.previous_log_count..]
+    }
+
+    fn status_counts(&self) -> (LevelCounts, Option<LevelCounts>) {
+        let logs = self.current_build_logs();
+        let shown = self
+            .active_filter
+            .as_ref()
+            .map(|filter| LevelCounts::from_entries(logs.iter().filter(|e| filter.matches(e))));
+        (LevelCounts::from_entries(logs), shown)
+    }
+
+    fn refresh_filter_cache(&mut self) {
+        let logs = self.console_logs();
+
+        let count = if let Some(filter) = &self.active_filter {
             logs.iter().filter(|e| filter.matches(e)).count()
         } else {
             logs.len()
         };
-        self.filter_cache_dirty = false;
+        self.cached_filtered_log_count = count;
+        self.filter_cache_dirty = false;
+    }
+
+    fn toggle_previous_logs(&mut self) {
+        if self.previous_log_count == 0 {
+            return;
+        }
+
+        self.previous_logs_collapsed = !self.previous_logs_collapsed;
+        self.filter_cache_dirty = true;
+    }
+
+    fn clear_previous_logs(&mut self) {
+        if self.previous_log_count == 0 {
+            return;
+        }
+
+        self.log_entries.drain(..self.previous_log_count);
+        self.previous_log_count = 0;
+        self.filter_cache_dirty = true;
+    }
+
+    pub fn retain_previous_logs(&mut self, previous: Vec<LogEntry>) {
+        let display = &self.builder.root().config.display;
+        if !display.keep_previous_logs || previous.is_empty() || display.max_log_lines < 2 {
+            return;
+        }
+
+        let keep = previous.len().min(display.max_log_lines - 1);
+        let skip = previous.len() - keep;
+
+        let message = format!(
+            "──── previous build ({} lines, za: expand/collapse, :clearold: drop) ────",
+            keep
+        );
+        let divider = LogEntry::new(
+            LogLevel::Info,
+            message.clone(),
+            message,
+            LogComponent::Other("ignis".to_string()),
+            0,
+        )
+        .with_tags(vec![PREVIOUS_BUILD_DIVIDER_TAG.to_string()]);
+
+        let mut retained: Vec<LogEntry> = previous
+            .into_iter()
+            .skip(skip)
+            .map(|mut entry| {
+                entry.tags.push(PREVIOUS_BUILD_TAG.to_string());
+                entry
+            })
+            .collect();
+        retained.push(divider);
+        for entry in retained.iter_mut() {
+            self.assign_entry_id(entry);
+        }
+
+        self.previous_log_count = retained.len();
+        retained.append(&mut self.log_entries);
+        self.log_entries = retained;
+        self.filter_cache_dirty = true;
+    }
+
+    pub fn snapshot_view_state(&self) -> ViewState {
+        ViewState {
+            search_pattern: self.search_pattern.clone(),
+            filter: self.active_filter.as_ref().and_then(|filter| filter.spec()),
+        }
+    }
+
+    pub fn restore_view_state(&mut self, state: ViewState) {
+        self.search_pattern = state.search_pattern;
+        self.active_filter = state.filter.and_then(|spec| spec.build());
+        self.filter_cache_dirty = true;
+    }
+
+    pub fn build_steps(&self) -> &[BuildStepResult] {
+        &self.build_steps
+    }
+
+    pub fn take_log_entries(&mut self) -> Vec<LogEntry> {
+        let mut entries = std::mem::take(&mut self.log_entries);
+        let current = entries.split_off(self.previous_log_count);
+        self.previous_log_count = 0;
+        self.filter_cache_dirty = true;
+        current
+    }
+
+    fn assign_entry_id(&mut self, entry: &mut LogEntry) {
+        entry.entry_id = self.next_entry_id;
+        self.next_entry_id += 1;
+    }
+
+    fn console_view(&self) -> Vec<usize> {
+        let logs = self.console_logs();
+        match &self.active_filter {
+            Some(filter) => (0..logs.len())
+                .filter(|&i| filter.matches(&logs[i]))
+                .collect(),
+            None => (0..logs.len()).collect(),
+        }
+    }
+
+    fn selected_console_position(&self) -> Option<usize> {
+        let id = self.console_selection?;
+        let view = self.console_view();
+        if view.is_empty() {
+            return None;
+        }
+
+        let Some(raw) = self.console_logs().iter().position(|e| e.entry_id == id) else {
+            return Some(0);
+        };
+
+        Some(
+            view.iter()
+                .position(|&i| i >= raw)
+                .unwrap_or(view.len() - 1),
+        )
+    }
+
+    fn select_console_position(&mut self, position: usize) {
+        let view = self.console_view();
+        let Some(&raw) = view.get(position.min(view.len().saturating_sub(1))) else {
+            return;
+        };
+        self.console_selection = Some(self.console_logs()[raw].entry_id);
     }
 
     fn scroll_console_up(&mut self, amount: usize) {
         self.auto_scroll = false;
-        let selected = self.console_scroll_state.selected().unwrap_or(0);
+        let selected = self.selected_console_position().unwrap_or(0);
         let new_selected = selected.saturating_sub(amount);
-        self.console_scroll_state.select(Some(new_selected));
+        self.select_console_position(new_selected);
     }
 
     fn scroll_console_down(&mut self, amount: usize) {
@@ -895,7 +1811,7 @@ impl App {
             return;
         }
 
-        let selected = self.console_scroll_state.selected().unwrap_or(0);
+        let selected = self.selected_console_position().unwrap_or(0);
         let new_selected = (selected + amount).min(count.saturating_sub(1));
 
         if new_selected >= count.saturating_sub(1) {
@@ -904,12 +1820,12 @@ impl App {
             self.auto_scroll = false;
         }
 
-        self.console_scroll_state.select(Some(new_selected));
+        self.select_console_position(new_selected);
     }
 
     fn scroll_console_to_top(&mut self) {
         self.auto_scroll = false;
-        self.console_scroll_state.select(Some(0));
+        self.select_console_position(0);
     }
 
     fn scroll_console_to_bottom(&mut self) {
@@ -919,8 +1835,7 @@ impl App {
         }
         let count = self.cached_filtered_log_count;
         if count > 0 {
-            self.console_scroll_state
-                .select(Some(count.saturating_sub(1)));
+            self.select_console_position(count.saturating_sub(1));
         }
     }
 
@@ -943,7 +1858,7 @@ impl App {
         }
 
         let target_line = line_number.saturating_sub(1).min(count.saturating_sub(1));
-        self.console_scroll_state.select(Some(target_line));
+        self.select_console_position(target_line);
         self.auto_scroll = false;
     }
 
@@ -974,45 +1889,105 @@ impl App {
         let count = self.cached_filtered_log_count;
         if count > 0 {
             let middle = count / 2;
-            self.console_scroll_state.select(Some(middle));
+            self.select_console_position(middle);
             self.auto_scroll = false;
         }
     }
 
     fn scroll_console_to_viewport_top(&mut self) {
-        let selected = self.console_scroll_state.selected().unwrap_or(0);
+        let selected = self.selected_console_position().unwrap_or(0);
         let viewport_height = self.console_viewport_height.saturating_sub(2) as usize;
         let target = selected.saturating_sub(viewport_height);
-        self.console_scroll_state.select(Some(target));
+        self.select_console_position(target);
         self.auto_scroll = false;
     }
 
     fn scroll_console_to_viewport_middle(&mut self) {
-        let selected = self.console_scroll_state.selected().unwrap_or(0);
+        let selected = self.selected_console_position().unwrap_or(0);
         let half_viewport = (self.console_viewport_height / 2) as usize;
         let target = selected.saturating_sub(half_viewport / 2);
-        self.console_scroll_state.select(Some(target));
+        self.select_console_position(target);
         self.auto_scroll = false;
     }
 
     fn scroll_console_to_viewport_bottom(&mut self) {
-        let selected = self.console_scroll_state.selected().unwrap_or(0);
+        let selected = self.selected_console_position().unwrap_or(0);
         let viewport_height = self.console_viewport_height.saturating_sub(2) as usize;
         if self.filter_cache_dirty {
             self.refresh_filter_cache();
         }
         let count = self.cached_filtered_log_count;
         let target = (selected + viewport_height).min(count.saturating_sub(1));
-        self.console_scroll_state.select(Some(target));
+        self.select_console_position(target);
         self.auto_scroll = false;
     }
 
+    fn cycle_tab(&mut self, forward: bool, count: usize) {
+        for _ in 0..count {
+            self.current_tab = if forward {
+                self.tab_set.next(self.current_tab)
+            } else {
+                self.tab_set.prev(self.current_tab)
+            };
+        }
+    }
+
+    fn jump_to_search_match(&mut self, forward: bool, count: usize) {
+        if self.current_tab != TabId::Console || self.search_pattern.is_none() {
+            return;
+        }
+
+        let start = self.selected_console_position().unwrap_or(0);
+        let mut current = start;
+        for _ in 0..count {
+            let found = if forward {
+                self.find_next_search_match(current)
+            } else {
+                self.find_prev_search_match(current)
+            };
+            match found {
+                Some(idx) => current = idx,
+                None => break,
+            }
+        }
+
+        if current != start {
+            self.select_console_position(current);
+            self.auto_scroll = false;
+        }
+    }
+
+    fn jump_to_level(&mut self, level: LogLevel, forward: bool) {
+        if self.current_tab != TabId::Console {
+            return;
+        }
+
+        if let Some(position) = self.find_level_match(level, forward) {
+            self.select_console_position(position);
+            self.auto_scroll = false;
+        }
+    }
+
+    fn find_level_match(&self, level: LogLevel, forward: bool) -> Option<usize> {
+        let logs = self.console_logs();
+        let view = self.console_view();
+        let start = self.selected_console_position();
+        let is_match = |position: &usize| match level {
+            LogLevel::Error => logs[view[*position]].level >= LogLevel::Error,
+            _ => logs[view[*position]].level == level,
+        };
+
+        if forward {
+            let from = start.map_or(0, |start| start + 1);
+            (from..view.len()).find(is_match)
+        } else {
+            (0..start.unwrap_or(view.len())).rev().find(is_match)
+        }
+    }
+
     fn find_next_search_match(&self, start_from: usize) -> Option<usize> {
         let pattern = self.search_pattern.as_ref()?;
-        let logs = match self.mode {
-            AppMode::Build => &self.log_entries,
-            AppMode::Exec => &self.exec_logs,
-        };
+        let logs = self.console_logs();
 
         if let Some(filter) = &self.active_filter {
             let mut current_idx = 0;
@@ -1042,10 +2017,7 @@ impl App {
 
     fn find_prev_search_match(&self, start_from: usize) -> Option<usize> {
         let pattern = self.search_pattern.as_ref()?;
-        let logs = match self.mode {
-            AppMode::Build => &self.log_entries,
-            AppMode::Exec => &self.exec_logs,
-        };
+        let logs = self.console_logs();
 
         if let Some(filter) = &self.active_filter {
             let mut matches = Vec::new();
@@ -1075,17 +2047,87 @@ impl App {
         }
     }
 
+    fn visible_history_rows(&self) -> Vec<usize> {
+        history_rows(self.build_history.entries(), self.history_query.as_deref())
+    }
+
+    fn scroll_history(&mut self, delta: isize) {
+        let count = self.visible_history_rows().len();
+        if count == 0 {
+            return;
+        }
+
+        let selected = self.history_scroll_state.selected().unwrap_or(0) as isize;
+        let new_selected = (selected + delta).clamp(0, count as isize - 1);
+        self.history_scroll_state
+            .select(Some(new_selected as usize));
+    }
+
+    fn toggle_history_diff_mark(&mut self) {
+        let Some(row) = self.history_scroll_state.selected() else {
+            return;
+        };
+        let Some(entry_index) = self.visible_history_rows().get(row).copied() else {
+            return;
+        };
+
+        if let Some(pos) = self
+            .history_diff_selection
+            .iter()
+            .position(|&idx| idx == entry_index)
+        {
+            self.history_diff_selection.remove(pos);
+        } else {
+            if self.history_diff_selection.len() >= 2 {
+                self.history_diff_selection.clear();
+            }
+            self.history_diff_selection.push(entry_index);
+        }
+    }
+
+    fn current_step_elapsed(&self) -> Option<f64> {
+        if self.build_complete {
+            return None;
+        }
+        self.running_steps
+            .last()
+            .map(|running| running.started_at.elapsed().as_secs_f64())
+    }
+
+    fn current_step(&self) -> Option<&str> {
+        self.running_steps
+            .last()
+            .map(|running| running.step.description.as_str())
+    }
+
+    fn current_step_is_slow(&self) -> bool {
+        let limit = self.builder.root().config.display.slow_step_secs as f64;
+        self.current_step_elapsed().is_some_and(|secs| secs > limit)
+    }
+
+    fn toggle_follow(&mut self) {
+        if self.auto_scroll {
+            self.auto_scroll = false;
+        } else {
+            self.unseen_log_count = 0;
+            self.scroll_console_to_bottom();
+        }
+    }
+
     fn update_console_scroll(&mut self) {
         if self.auto_scroll {
+            self.unseen_log_count = 0;
             if self.filter_cache_dirty {
                 self.refresh_filter_cache();
             }
             let count = self.cached_filtered_log_count;
             if count > 0 {
-                self.console_scroll_state
-                    .select(Some(count.saturating_sub(1)));
+                self.select_console_position(count.saturating_sub(1));
             }
         }
+
+        let position = self.selected_console_position();
+        self.console_scroll_state.select(position);
     }
 
     fn render(&mut self, frame: &mut Frame) {
@@ -1122,8 +2164,14 @@ impl App {
                 let exec_name = self
                     .exec_info
                     .as_ref()
-                    .map(|e| e.name.as_str())
-                    .unwrap_or("Unknown");
+                    .map(|e| {
+                        if e.args.is_empty() {
+                            e.name.clone()
+                        } else {
+                            format!("{} {}", e.name, e.args.join(" "))
+                        }
+                    })
+                    .unwrap_or_else(|| "Unknown".to_string());
                 Span::styled(
                     format!(" Ignis Exec: {} ", exec_name),
                     Style::default()
@@ -1133,17 +2181,13 @@ impl App {
             }
         };
 
-        let tabs = [
-            ("[Alt+1] Console", TabId::Console),
-            ("[Alt+2] Summary", TabId::Summary),
-            ("[Alt+3] Performance", TabId::Performance),
-            ("[Alt+4] Warnings", TabId::Warnings),
-            ("[Alt+5] History", TabId::History),
-        ];
-
-        let tab_spans: Vec<Span> = tabs
+        let tab_spans: Vec<Span> = self
+            .tab_set
+            .tabs()
             .iter()
-            .flat_map(|(label, id)| {
+            .enumerate()
+            .flat_map(|(index, id)| {
+                let label = format!("[Alt+{}] {}", index + 1, id.name());
                 let style = if *id == self.current_tab {
                     Style::default()
                         .fg(Color::Yellow)
@@ -1151,7 +2195,7 @@ impl App {
                 } else {
                     Style::default().fg(Color::DarkGray)
                 };
-                vec![Span::raw(" "), Span::styled(*label, style)]
+                vec![Span::raw(" "), Span::styled(label, style)]
             })
             .collect();
 
@@ -1182,37 +2226,83 @@ impl App {
             TabId::Console => {
                 self.console_viewport_height = area.height.saturating_sub(2);
                 self.update_console_scroll();
+                let visible_from = self.visible_log_start();
                 let tab = ConsoleTab::new(
-                    &self.log_entries,
+                    &self.log_entries[visible_from..],
                     self.active_filter.as_ref(),
                     self.search_pattern.as_deref(),
-                );
+                )
+                .with_relative_numbers(self.relative_numbers)
+                .with_follow(self.auto_scroll, self.unseen_log_count)
+                .with_minimap(self.builder.root().config.display.show_minimap);
                 tab.render(frame, area, &mut self.console_scroll_state);
             }
             TabId::Summary => {
-                let tab = SummaryTab::new(&self.build_steps);
-                tab.render(frame, area);
+                let tab = SummaryTab::new(&self.build_steps)
+                    .with_skipped(&self.skipped_steps)
+                    .with_sort(self.step_sort);
+                tab.render(frame, area, &mut self.summary_scroll_state);
             }
             TabId::Performance => {
                 let elapsed = self
                     .build_duration
                     .unwrap_or_else(|| self.start_time.elapsed().as_secs_f64());
                 let resource_stats = self.resource_monitor.get_stats();
+                let slowest_steps = self
+                    .show_slowest_steps
+                    .then_some(self.builder.root().config.display.slowest_steps);
+                let mut windows = self.stage_windows.clone();
+                if !self.build_complete {
+                    windows.extend(self.running_steps.iter().map(|running| StageWindow {
+                        description: running.step.description.clone(),
+                        start: running.start_offset,
+                        end: self.resource_monitor.elapsed(),
+                    }));
+                }
+                let advisories = detect_advisories(
+                    &resource_stats.samples,
+                    &windows,
+                    &self.builder.root().config.monitor,
+                );
                 let tab = PerformanceTab::new(
                     &self.build_steps,
                     elapsed,
                     self.build_complete,
                     resource_stats,
-                );
+                )
+                .with_slowest_steps(slowest_steps)
+                .with_advisories(advisories)
+                .with_viewport(self.chart_viewport)
+                .with_bottom_chart(self.resource_chart);
                 tab.render(frame, area);
             }
             TabId::Warnings => {
-                let tab = WarningsTab::new(&self.log_entries);
+                let position = self.diagnostic_position();
+                let tab = WarningsTab::new(&self.log_entries[self.previous_log_count..])
+                    .with_position(position);
                 tab.render(frame, area, &mut self.warnings_scroll_state);
             }
+            TabId::Errors => {
+                let position = self.diagnostic_position();
+                let tab = WarningsTab::new(&self.log_entries[self.previous_log_count..])
+                    .with_errors_only(true)
+                    .with_position(position);
+                tab.render(frame, area, &mut self.errors_scroll_state);
+            }
             TabId::History => {
-                let tab = HistoryTab::new(self.build_history.entries());
-                tab.render(frame, area);
+                if self.history_scroll_state.selected().is_none()
+                    && !self.visible_history_rows().is_empty()
+                {
+                    self.history_scroll_state.select(Some(0));
+                }
+                let tab = HistoryTab::new(self.build_history.entries())
+                    .with_marked(&self.history_diff_selection)
+                    .with_query(self.history_query.as_deref())
+                    .with_trend(
+                        self.builder.root().config.display.trend_builds,
+                        self.trend_include_failed,
+                    );
+                tab.render(frame, area, &mut self.history_scroll_state);
             }
         }
     }
@@ -1226,11 +2316,15 @@ impl App {
                     &self.exec_logs,
                     self.active_filter.as_ref(),
                     self.search_pattern.as_deref(),
-                );
+                )
+                .with_relative_numbers(self.relative_numbers)
+                .with_follow(self.auto_scroll, self.unseen_log_count)
+                .with_stderr_highlight(self.builder.root().config.display.highlight_stderr)
+                .with_minimap(self.builder.root().config.display.show_minimap);
                 tab.render(frame, area, &mut self.console_scroll_state);
             }
             TabId::Summary => {
-                let tab = SummaryTab::new(&[]);
+                let tab = SummaryTab::new(&[]).with_metric_groups(&self.metric_groups);
                 tab.render_metrics(frame, area, &self.exec_metrics);
             }
             TabId::Performance => {
@@ -1240,13 +2334,23 @@ impl App {
                         .unwrap_or(0.0)
                 });
                 let resource_stats = self.resource_monitor.get_stats();
-                let tab = PerformanceTab::new_runtime(elapsed, self.exec_complete, resource_stats);
+                let tab = PerformanceTab::new_runtime(elapsed, self.exec_complete, resource_stats)
+                    .with_viewport(self.chart_viewport)
+                    .with_bottom_chart(self.resource_chart);
                 tab.render(frame, area);
             }
             TabId::Warnings => {
-                let tab = WarningsTab::new(&self.exec_logs);
+                let position = self.diagnostic_position();
+                let tab = WarningsTab::new(&self.exec_logs).with_position(position);
                 tab.render(frame, area, &mut self.warnings_scroll_state);
             }
+            TabId::Errors => {
+                let position = self.diagnostic_position();
+                let tab = WarningsTab::new(&self.exec_logs)
+                    .with_errors_only(true)
+                    .with_position(position);
+                tab.render(frame, area, &mut self.errors_scroll_state);
+            }
             TabId::History => {
                 if let Some(exec_history) = &self.exec_history {
                     HistoryTab::render_exec_history(exec_history.entries(), frame, area);
@@ -1267,6 +2371,72 @@ impl App {
     }
 
     fn render_build_status(&self, frame: &mut Frame, area: Rect) {
+        if self.preset_picker_open {
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Presets",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+            ];
+
+            for (idx, preset) in self.available_presets().into_iter().enumerate() {
+                let is_selected = idx == self.preset_picker_selection;
+                let is_current = preset == self.builder.preset();
+
+                lines.push(Line::from(vec![
+                    Span::raw(if is_selected { " > " } else { "   " }),
+                    Span::styled(
+                        preset,
+                        if is_selected {
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ),
+                    Span::styled(
+                        if is_current { " (current)" } else { "" },
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+            }
+
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(" [j/k ↓↑] ", Style::default().fg(Color::Cyan)),
+                Span::raw("Navigate"),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    " [Enter] ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Switch preset"),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled(" [Esc] ", Style::default().fg(Color::Cyan)),
+                Span::raw("Close menu"),
+            ]));
+
+            let paragraph = Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(" Preset ").alignment(Alignment::Center)),
+            );
+
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
         if self.exec_menu_open {
             let executables = self.builder.find_executables();
 
@@ -1325,6 +2495,13 @@ impl App {
                                 Style::default().fg(Color::DarkGray),
                             ),
                         ]));
+                        lines.push(Line::from(vec![
+                            Span::raw("     "),
+                            Span::styled(
+                                format!("args: {}", exec_info.args_display()),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                        ]));
                     }
                 }
 
@@ -1413,6 +2590,37 @@ impl App {
                     ),
                 ]),
                 Line::from(""),
+                Line::from(vec![
+                    Span::styled(
+                        " [I] ",
+                        Style::default()
+                            .fg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("Install"),
+                ]),
+                Line::from(vec![
+                    Span::raw("     "),
+                    Span::styled(
+                        "Re-run only the Install stage",
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(
+                        " [P] ",
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("Preset ({})", self.builder.preset())),
+                ]),
+                Line::from(vec![
+                    Span::raw("     "),
+                    Span::styled("Switch build preset", Style::default().fg(Color::DarkGray)),
+                ]),
+                Line::from(""),
                 Line::from(vec![
                     Span::styled(" [Esc] ", Style::default().fg(Color::Cyan)),
                     Span::raw("Close menu"),
@@ -1434,16 +2642,8 @@ impl App {
         let elapsed = self
             .build_duration
             .unwrap_or_else(|| self.start_time.elapsed().as_secs_f64());
-        let error_count = self
-            .log_entries
-            .iter()
-            .filter(|e| e.level == LogLevel::Error)
-            .count();
-        let warning_count = self
-            .log_entries
-            .iter()
-            .filter(|e| e.level == LogLevel::Warning)
-            .count();
+        let step_elapsed = self.current_step_elapsed();
+        let (totals, shown) = self.status_counts();
 
         let percentage = if self.total_steps > 0 {
             (self.steps_completed as f64 / self.total_steps as f64) * 100.0
@@ -1451,20 +2651,26 @@ impl App {
             0.0
         };
 
-        let lines = vec![
+        let mut lines = vec![
             Line::from(vec![
                 Span::styled("Status: ", Style::default().fg(Color::Yellow)),
+                if self.build_complete {
+                    Span::styled("Complete", Style::default().fg(Color::Green))
+                } else if self.fatal_seen {
+                    Span::styled("Failing", Style::default().fg(Color::Magenta))
+                } else {
+                    Span::styled("Building", Style::default().fg(Color::Cyan))
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("Type: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
-                    if self.build_complete {
-                        "Complete"
+                    if self.is_incremental_build() {
+                        "Incremental"
                     } else {
-                        "Building"
+                        "Clean"
                     },
-                    Style::default().fg(if self.build_complete {
-                        Color::Green
-                    } else {
-                        Color::Cyan
-                    }),
+                    Style::default().fg(Color::Cyan),
                 ),
             ]),
             Line::from(""),
@@ -1478,10 +2684,24 @@ impl App {
                     Style::default().fg(Color::Green),
                 ),
             ]),
+            Line::from(Span::styled(
+                progress::bar(percentage / 100.0, 20),
+                Style::default().fg(Color::Green),
+            )),
             Line::from(""),
             Line::from(vec![
                 Span::styled("Current: ", Style::default().fg(Color::Yellow)),
-                Span::raw(self.current_step.as_deref().unwrap_or("Idle")),
+                Span::raw(self.current_step().unwrap_or("Idle")),
+                Span::styled(
+                    step_elapsed
+                        .map(|secs| format!(" ({:.1}s)", secs))
+                        .unwrap_or_default(),
+                    Style::default().fg(if self.current_step_is_slow() {
+                        Color::Yellow
+                    } else {
+                        Color::Cyan
+                    }),
+                ),
             ]),
             Line::from(""),
             Line::from(vec![
@@ -1489,11 +2709,33 @@ impl App {
                 Span::styled(format!("{:.1}s", elapsed), Style::default().fg(Color::Cyan)),
             ]),
             Line::from(""),
+            Line::from(vec![
+                Span::styled("Lines: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    count_text(shown.map(|s| s.lines), totals.lines),
+                    Style::default().fg(if shown.is_some() {
+                        Color::Cyan
+                    } else {
+                        Color::White
+                    }),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Fatal: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    count_text(shown.map(|s| s.fatal), totals.fatal),
+                    Style::default().fg(if totals.fatal > 0 {
+                        Color::Magenta
+                    } else {
+                        Color::Green
+                    }),
+                ),
+            ]),
             Line::from(vec![
                 Span::styled("Errors: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
-                    error_count.to_string(),
-                    Style::default().fg(if error_count > 0 {
+                    count_text(shown.map(|s| s.errors), totals.errors),
+                    Style::default().fg(if totals.errors > 0 {
                         Color::Red
                     } else {
                         Color::Green
@@ -1503,8 +2745,8 @@ impl App {
             Line::from(vec![
                 Span::styled("Warnings: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
-                    warning_count.to_string(),
-                    Style::default().fg(if warning_count > 0 {
+                    count_text(shown.map(|s| s.warnings), totals.warnings),
+                    Style::default().fg(if totals.warnings > 0 {
                         Color::Yellow
                     } else {
                         Color::Green
@@ -1526,6 +2768,25 @@ impl App {
             ]),
         ];
 
+        if let Some(regression) = &self.regression_note {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "⚠ Regression: ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(regression.clone(), Style::default().fg(Color::Red)),
+            ]));
+        }
+
+        if let Some(note) = &self.build_note {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Note: ", Style::default().fg(Color::Yellow)),
+                Span::styled(note.clone(), Style::default().fg(Color::Magenta)),
+            ]));
+        }
+
         let paragraph = Paragraph::new(lines).block(
             Block::default()
                 .borders(Borders::ALL)
@@ -1711,7 +2972,11 @@ impl App {
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Magenta))
+                .border_style(Style::default().fg(if self.alert_flash_on() {
+                    Color::Red
+                } else {
+                    Color::Magenta
+                }))
                 .title(Title::from(" Runtime Status ").alignment(Alignment::Center)),
         );
 
@@ -1719,13 +2984,20 @@ impl App {
     }
 
     fn render_footer(&self, frame: &mut Frame, area: Rect) {
-        let mode_text = match self.mode {
-            AppMode::Build => {
-                if self.exec_menu_open {
+        let mode_text = match (self.mode, self.quit_prompt()) {
+            (_, Some(prompt)) => prompt.to_string(),
+            (AppMode::Build, None) => {
+                if self.preset_picker_open {
+                    "Preset: [j/k/↓/↑]: Navigate | [Enter]: Switch | [Q | Esc]: Close".to_string()
+                } else if let Some(prompt) = self.clean_prompt() {
+                    prompt
+                } else if let Some(error) = &self.command_error {
+                    error.clone()
+                } else if self.exec_menu_open {
                     "Exec Menu: [j/k/↓/↑]: Navigate | [o/Enter]: Open | [c]: Clean | [Q | Esc]: Close Menu"
                         .to_string()
                 } else if self.build_menu_open {
-                    "Build Options: [R]: Rebuild | [C]: Clean | [Q | Esc]: Close Menu".to_string()
+                    "Build Options: [R]: Rebuild | [C]: Clean | [I]: Install | [P]: Preset | [Q | Esc]: Close Menu".to_string()
                 } else {
                     match self.vim_mode.mode {
                         InputMode::Normal => {
@@ -1733,37 +3005,47 @@ impl App {
                                 format!("{}", count)
                             } else if let Some(sequence) = self.vim_mode.get_sequence_display() {
                                 format!("Keys: {} (waiting for next key...)", sequence)
+                            } else if self.watch_rx.is_some() && self.build_complete {
+                                format!("Watching for changes... | q: Quit | b: Build | e: Exec | Alt+[1-{}]: Tabs | :: Cmd | /: Search", self.tab_set.len())
                             } else {
-                                "q: Quit | <Space>: Leader | b: Build | e: Exec | Alt+[1-5]: Tabs | H/L: Tab Nav | :: Cmd | /: Search | [num]j/k/Ctrl+U/D: Nav".to_string()
+                                format!("q: Quit | <Space>: Leader | b: Build | e: Exec | Alt+[1-{}]: Tabs | H/L: Tab Nav | :: Cmd | /: Search | [num]j/k/Ctrl+U/D: Nav", self.tab_set.len())
                             }
                         }
-                        InputMode::Command => format!(":{}", self.vim_mode.input_buffer),
+                        InputMode::Command => self.command_line(),
                         InputMode::Search => format!("/{}", self.vim_mode.input_buffer),
                     }
                 }
             }
-            AppMode::Exec => {
+            (AppMode::Exec, None) => {
                 if self.build_menu_open {
                     "Build Options: [R]: Rebuild | [C]: Clean | [Q | Esc]: Close Menu".to_string()
                 } else {
                     match self.vim_mode.mode {
                         InputMode::Normal => {
                             if self.exec_complete {
-                                "q: Back to Build | r: Restart | b: Build Options | Alt+[1-5]: Tabs"
-                                    .to_string()
+                                format!(
+                                    "q: Back to Build | r: Restart | b: Build Options | Alt+[1-{}]: Tabs",
+                                    self.tab_set.len()
+                                )
                             } else {
-                                "q: Back to Build | k: Kill | b: Build Options | Alt+[1-5]: Tabs"
-                                    .to_string()
+                                format!(
+                                    "q: Back to Build | k: Kill | b: Build Options | Alt+[1-{}]: Tabs",
+                                    self.tab_set.len()
+                                )
                             }
                         }
-                        InputMode::Command => format!(":{}", self.vim_mode.input_buffer),
+                        InputMode::Command => self.command_line(),
                         InputMode::Search => format!("/{}", self.vim_mode.input_buffer),
                     }
                 }
             }
         };
 
-        let style = if self.vim_mode.has_count() {
+        let style = if self.command_error.is_some() {
+            Style::default().fg(Color::Red)
+        } else if self.quit_prompt().is_some() {
+            Style::default().fg(Color::Yellow)
+        } else if self.vim_mode.has_count() {
             Style::default().fg(Color::Green)
         } else if self.vim_mode.pending_sequence.is_some() {
             Style::default().fg(Color::Yellow)
@@ -1781,6 +3063,14 @@ impl App {
         frame.render_widget(footer, area);
     }
 
+    fn command_line(&self) -> String {
+        if self.vim_mode.input_buffer.is_empty() {
+            ":  errors | warnings | noise | all | filter level=<level> | w [file]".to_string()
+        } else {
+            format!(":{}", self.vim_mode.input_buffer)
+        }
+    }
+
     async fn handle_build_menu_key(&mut self, key: event::KeyEvent) -> Result<bool> {
         use crossterm::event::KeyCode;
 
@@ -1789,12 +3079,51 @@ impl App {
                 self.build_action = Some(BuildAction::Rebuild);
                 return Ok(true);
             }
-            KeyCode::Char('c') | KeyCode::Char('C') => {
-                self.build_action = Some(BuildAction::Clean);
-                return Ok(true);
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.build_action = Some(BuildAction::Clean);
+                return Ok(true);
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') if self.mode == AppMode::Build => {
+                self.request_install_only();
+                if self.build_action.is_some() {
+                    return Ok(true);
+                }
+                self.build_menu_open = false;
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') if self.mode == AppMode::Build => {
+                self.open_preset_picker();
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.build_menu_open = false;
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    async fn handle_preset_picker_key(&mut self, key: event::KeyEvent) -> Result<bool> {
+        use crossterm::event::KeyCode;
+
+        let presets = self.available_presets();
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.preset_picker_selection =
+                    (self.preset_picker_selection + 1).min(presets.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.preset_picker_selection = self.preset_picker_selection.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(preset) = presets.get(self.preset_picker_selection) {
+                    self.preset_picker_open = false;
+                    self.switch_preset(preset);
+                    return Ok(self.preset_change.is_some());
+                }
             }
             KeyCode::Char('q') | KeyCode::Esc => {
-                self.build_menu_open = false;
+                self.preset_picker_open = false;
             }
             _ => {}
         }
@@ -1837,9 +3166,11 @@ impl App {
             }
             KeyCode::Char('c') | KeyCode::Char('C') => {
                 if let Some(exec_info) = executables.get(self.exec_menu_selection) {
-                    let _ = std::fs::remove_dir_all(&exec_info.build_dir);
-                    let _ = std::fs::remove_dir_all(&exec_info.install_dir);
-                    self.exec_menu_open = false;
+                    match self.clean_executable(exec_info) {
+                        Ok(true) => self.exec_menu_open = false,
+                        Ok(false) => {}
+                        Err(e) => self.command_error = Some(format!("Clean failed: {:#}", e)),
+                    }
                 }
             }
             KeyCode::Char('q') | KeyCode::Esc => {
@@ -1851,11 +3182,69 @@ impl App {
         Ok(false)
     }
 
+    fn clean_executable(&mut self, exec_info: &ExecutableInfo) -> Result<bool> {
+        let entries = clean::plan(&[exec_info.build_dir.clone(), exec_info.install_dir.clone()])?;
+        let total = clean::total_bytes(&entries);
+
+        let confirmed = self.pending_clean.take().is_some_and(|(dir, _, at)| {
+            dir == exec_info.build_dir && at.elapsed() < QUIT_CONFIRM_WINDOW
+        });
+        let threshold = self.builder.root().config.build.confirm_clean_over_mb;
+        if !confirmed && clean::needs_confirmation(&entries, threshold) {
+            self.pending_clean = Some((exec_info.build_dir.clone(), total, Instant::now()));
+            return Ok(false);
+        }
+
+        let dry_run = self.builder.dry_run();
+        if !dry_run {
+            clean::remove(&entries)?;
+        }
+        let verb = if dry_run { "Would remove" } else { "Removed" };
+        let messages: Vec<String> = if entries.is_empty() {
+            vec![format!("Nothing to clean for {}", exec_info.name)]
+        } else {
+            entries
+                .iter()
+                .map(|e| format!("{} {}", verb, e.describe()))
+                .collect()
+        };
+        for message in messages {
+            let mut entry = LogEntry::new(
+                LogLevel::Info,
+                message.clone(),
+                message,
+                LogComponent::Other("clean".to_string()),
+                0,
+            );
+            self.assign_entry_id(&mut entry);
+            self.log_entries.push(entry);
+        }
+        self.filter_cache_dirty = true;
+        Ok(true)
+    }
+
+    fn clean_prompt(&self) -> Option<String> {
+        self.pending_clean
+            .as_ref()
+            .filter(|(_, _, at)| at.elapsed() < QUIT_CONFIRM_WINDOW)
+            .map(|(dir, bytes, _)| {
+                format!(
+                    "Remove {} in {}? Press c again to confirm",
+                    clean::format_size(*bytes),
+                    dir.display()
+                )
+            })
+    }
+
     pub fn get_build_action(&self) -> Option<BuildAction> {
         self.build_action
     }
 
     pub fn finalize_build(&mut self) -> Result<()> {
+        if self.builder.dry_run() {
+            return Ok(());
+        }
+
         let total_duration = self.start_time.elapsed().as_secs_f64();
         let mut entry = BuildHistoryEntry::new(self.builder.preset().to_string());
 
@@ -1864,11 +3253,45 @@ impl App {
         }
 
         entry.finalize(total_duration);
+        entry.note = self.build_note.clone();
+        entry.compile_steps = self.compile_steps();
+        entry.incremental = self.is_incremental_build();
+        self.regression_note = if entry.success {
+            self.check_regression(entry.duration)
+        } else {
+            None
+        };
         self.build_history.add_entry(entry)?;
 
         Ok(())
     }
 
+    fn compile_steps(&self) -> usize {
+        self.current_build_logs()
+            .iter()
+            .filter(|e| e.tags.iter().any(|tag| tag == "progress"))
+            .count()
+    }
+
+    fn is_incremental_build(&self) -> bool {
+        is_incremental(
+            self.build_history.entries(),
+            self.builder.preset(),
+            self.cmake_cache_existed,
+            self.compile_steps(),
+        )
+    }
+
+    fn check_regression(&self, duration: f64) -> Option<String> {
+        detect_regression(
+            self.build_history.entries(),
+            self.builder.preset(),
+            self.is_incremental_build(),
+            duration,
+            self.builder.root().config.history.regression_threshold_pct,
+        )
+    }
+
     pub fn finalize_exec(&mut self) -> Result<()> {
         if let (Some(exec_info), Some(exec_history)) =
             (self.exec_info.as_ref(), self.exec_history.as_mut())
@@ -1903,6 +3326,7 @@ impl App {
             entry.metric_count = self.exec_metrics.len();
             entry.log_count = self.exec_logs.len();
             entry.failure_reason = self.exec_failure_reason.clone();
+            entry.args = exec_info.args.clone();
 
             exec_history.add_entry(entry)?;
         }
@@ -1923,29 +3347,39 @@ impl App {
             root.config.editor.vscode_integration,
         );
 
-        let leader_key = KeyPress::from_string(&root.config.keybindings.leader_key)
-            .unwrap_or_else(|| KeyPress::from_char(' '));
-
-        let keybinding_manager = KeyBindingManager::new(
-            leader_key,
-            root.config.keybindings.sequence_timeout_ms,
-            root.config.keybindings.enable_leader,
-        );
+        let (keybinding_manager, binding_error) = configured_keybindings(&root.config);
+        let keybinding_manager = keybinding_manager.with_exec_mode();
 
         let storage_path = root.config.storage_path();
 
         let build_history = BuildHistory::new(storage_path.clone(), root.config.history.max_builds)
-            .unwrap_or_else(|_| BuildHistory::new(std::path::PathBuf::new(), 10).unwrap());
+            .unwrap_or_else(|_| BuildHistory::new(std::path::PathBuf::new(), 10).unwrap())
+            .with_max_age_days(root.config.history.max_age_days);
 
         let exec_history = ExecutionHistory::new(storage_path, root.config.history.max_builds).ok();
 
+        let (tab_set, tab_error) = configured_tab_set(&root.config);
+        let command_error = join_errors([tab_error, binding_error]);
+
         Self {
-            current_tab: TabId::Console,
+            current_tab: tab_set.first(),
+            tab_set,
             log_entries: Vec::new(),
+            previous_log_count: 0,
+            previous_logs_collapsed: true,
             build_steps: Vec::new(),
+            skipped_steps: Vec::new(),
             build_complete: false,
+            fatal_seen: false,
+            completion_notified: false,
+            quit_requested_at: None,
+            pending_clean: None,
             build_duration: None,
-            current_step: None,
+            last_step_finished_at: None,
+            regression_note: None,
+            cmake_cache_existed: false,
+            running_steps: Vec::new(),
+            stage_windows: Vec::new(),
             steps_completed: 0,
             total_steps: 0,
             start_time: Instant::now(),
@@ -1959,11 +3393,32 @@ impl App {
             build_menu_open: false,
             exec_menu_open: false,
             exec_menu_selection: 0,
+            preset_picker_open: false,
+            preset_picker_selection: 0,
+            preset_change: None,
+            exec_args_change: None,
+            command_error,
+            build_note: None,
             build_action: None,
             resource_monitor,
             console_scroll_state: ListState::default(),
+            console_selection: None,
+            next_entry_id: 0,
             warnings_scroll_state: ListState::default(),
+            errors_scroll_state: ListState::default(),
+            summary_scroll_state: TableState::default(),
+            history_scroll_state: TableState::default(),
+            history_diff_selection: Vec::new(),
+            trend_include_failed: false,
+            history_query: None,
             auto_scroll: true,
+            unseen_log_count: 0,
+            relative_numbers: root.config.display.relative_numbers,
+            show_slowest_steps: false,
+            step_sort: StepSort::default(),
+            chart_viewport: ChartViewport::default(),
+            resource_chart: ResourceChart::default(),
+            redraw_requested: false,
             console_viewport_height: 20,
             cached_filtered_log_count: 0,
             filter_cache_dirty: true,
@@ -1972,7 +3427,13 @@ impl App {
             exec_info: Some(exec_info),
             exec_logs: Vec::new(),
             exec_metrics: HashMap::new(),
+            metric_groups: root.config.exec.metric_groups.clone(),
             exec_pid: None,
+            kill_tx: None,
+            alert_rx: None,
+            metrics_snapshot: None,
+            watch_rx: None,
+            last_alert_at: None,
             exec_start_time: Some(Instant::now()),
             exec_duration: None,
             exec_complete: false,
@@ -1993,3 +3454,1038 @@ impl App {
         self.selected_executable.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::ExecutionResult;
+    use crate::parser::{CompositeFilter, LevelFilter};
+    use crate::target::{Target, TargetKind};
+    use crate::Config;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    // Keeps the app's history directory alive for as long as the test holds the app.
+    struct TestApp {
+        app: App,
+        _dir: tempfile::TempDir,
+    }
+
+    impl TestApp {
+        fn map(self, f: impl FnOnce(App) -> App) -> Self {
+            Self {
+                app: f(self.app),
+                _dir: self._dir,
+            }
+        }
+    }
+
+    impl std::ops::Deref for TestApp {
+        type Target = App;
+
+        fn deref(&self) -> &App {
+            &self.app
+        }
+    }
+
+    impl std::ops::DerefMut for TestApp {
+        fn deref_mut(&mut self) -> &mut App {
+            &mut self.app
+        }
+    }
+
+    fn test_config(dir: &tempfile::TempDir) -> Config {
+        let mut config = Config::default();
+        config.history.storage_path = dir.path().join("build_history.json").display().to_string();
+        config
+    }
+
+    fn create_test_app() -> TestApp {
+        create_test_app_with_steps().0
+    }
+
+    fn create_test_app_with_steps() -> (TestApp, mpsc::UnboundedSender<StepUpdate>) {
+        let (app, _log_tx, step_tx) = create_test_app_with_logs(Config::default());
+        (app, step_tx)
+    }
+
+    fn create_exec_test_app() -> TestApp {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Target {
+            path: std::path::PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config: test_config(&dir),
+        };
+        let exec_info = ExecutableInfo {
+            path: std::path::PathBuf::from("/tmp/test/builds/debug/game"),
+            name: "game".to_string(),
+            project_dir: std::path::PathBuf::from("/tmp/test"),
+            build_dir: std::path::PathBuf::from("/tmp/test/builds/debug"),
+            install_dir: std::path::PathBuf::from("/tmp/test/install"),
+            args: Vec::new(),
+        };
+        let (_log_tx, log_rx) = mpsc::unbounded_channel();
+        let (_step_tx, step_rx) = mpsc::unbounded_channel();
+        let app = App::new_exec_mode(
+            log_rx,
+            step_rx,
+            ResourceMonitor::new(),
+            exec_info,
+            Builder::new(root, "debug".to_string()),
+        );
+        TestApp { app, _dir: dir }
+    }
+
+    fn create_test_app_with_logs(
+        config: Config,
+    ) -> (
+        TestApp,
+        mpsc::UnboundedSender<LogEntry>,
+        mpsc::UnboundedSender<StepUpdate>,
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Target {
+            path: std::path::PathBuf::from("/tmp/test"),
+            kind: TargetKind::Root,
+            config,
+        };
+        let history_path = dir.path().join("build_history.json");
+        let build_history = BuildHistory::new(history_path, 10).unwrap();
+        let (log_tx, log_rx) = mpsc::unbounded_channel();
+        let (step_tx, step_rx) = mpsc::unbounded_channel();
+        let app = App::new(
+            build_history,
+            log_rx,
+            step_rx,
+            ResourceMonitor::new(),
+            Builder::new(root, "debug".to_string()),
+        );
+        (TestApp { app, _dir: dir }, log_tx, step_tx)
+    }
+
+    fn info_entry(message: &str) -> LogEntry {
+        LogEntry::new(
+            LogLevel::Info,
+            message.to_string(),
+            message.to_string(),
+            LogComponent::Build,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_toggle_follow_counts_unseen_lines() {
+        let (mut app, log_tx, _step_tx) = create_test_app_with_logs(Config::default());
+        for i in 0..3 {
+            log_tx.send(info_entry(&format!("line {}", i))).unwrap();
+        }
+        app.process_build_updates().unwrap();
+        app.update_console_scroll();
+        assert_eq!(app.selected_console_position(), Some(2));
+
+        app.toggle_follow();
+        assert!(!app.auto_scroll);
+        for i in 3..7 {
+            log_tx.send(info_entry(&format!("line {}", i))).unwrap();
+        }
+        app.process_build_updates().unwrap();
+        app.update_console_scroll();
+        assert_eq!(app.unseen_log_count, 4);
+        assert_eq!(app.selected_console_position(), Some(2));
+
+        app.toggle_follow();
+        assert!(app.auto_scroll);
+        assert_eq!(app.unseen_log_count, 0);
+        assert_eq!(app.selected_console_position(), Some(6));
+    }
+
+    #[test]
+    fn test_fatal_entry_marks_build_failing() {
+        let (mut app, log_tx, step_tx) = create_test_app_with_logs(Config::default());
+        log_tx
+            .send(info_entry("[1/3] Building CXX object main.cpp.o"))
+            .unwrap();
+        app.process_build_updates().unwrap();
+        assert!(!app.build_complete);
+
+        let mut parser = crate::parser::CompilerOutputParser::new();
+        log_tx
+            .send(parser.parse_line("src/main.cpp:1:10: fatal error: 'foo.h' file not found"))
+            .unwrap();
+        app.process_build_updates().unwrap();
+
+        assert!(app.fatal_seen);
+        assert!(!app.build_complete);
+        assert!(app.build_duration.is_none());
+        assert_eq!(app.log_entries[1].level, LogLevel::Fatal);
+
+        drop(step_tx);
+        app.process_build_updates().unwrap();
+        assert!(app.build_complete);
+        assert!(!app.build_outcome().unwrap().success);
+    }
+
+    #[test]
+    fn test_build_outcome_reported_once_on_completion() {
+        let (mut app, log_tx, step_tx) = create_test_app_with_logs(Config::default());
+        assert!(app.build_outcome().is_none());
+
+        let mut parser = crate::parser::CompilerOutputParser::new();
+        for line in [
+            "src/a.cpp:1:1: error: expected ';'",
+            "src/b.cpp:1:10: fatal error: 'foo.h' file not found",
+        ] {
+            log_tx.send(parser.parse_line(line)).unwrap();
+        }
+        drop(step_tx);
+        app.process_build_updates().unwrap();
+
+        let outcome = app.build_outcome().unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.error_count, 2);
+        assert_eq!(outcome.preset, "debug");
+
+        app.notify_if_complete();
+        assert!(app.completion_notified);
+        assert!(app.build_outcome().is_none());
+    }
+
+    #[test]
+    fn test_collapse_duplicates_counts_repeats() {
+        let mut config = Config::default();
+        config.display.collapse_duplicates = true;
+        let (mut app, log_tx, _step_tx) = create_test_app_with_logs(config);
+
+        let lines = [
+            (LogLevel::Info, "frame"),
+            (LogLevel::Info, "frame"),
+            (LogLevel::Info, "frame"),
+            (LogLevel::Error, "boom"),
+            (LogLevel::Error, "boom"),
+            (LogLevel::Info, "frame"),
+            (LogLevel::Warning, "frame"),
+        ];
+        for (level, message) in lines {
+            log_tx
+                .send(LogEntry::new(
+                    level,
+                    message.to_string(),
+                    message.to_string(),
+                    LogComponent::Build,
+                    0,
+                ))
+                .unwrap();
+        }
+        app.process_build_updates().unwrap();
+
+        let rows: Vec<(&str, usize)> = app
+            .log_entries
+            .iter()
+            .map(|e| (e.message.as_str(), e.repeat_count))
+            .collect();
+        assert_eq!(
+            rows,
+            [("frame", 3), ("boom", 2), ("frame", 1), ("frame", 1)]
+        );
+
+        app.search_pattern = Some("boom".to_string());
+        assert_eq!(app.find_next_search_match(0), Some(1));
+
+        app.goto_line(3);
+        assert_eq!(app.selected_entry().unwrap().level, LogLevel::Info);
+        assert_eq!(app.selected_console_position(), Some(2));
+
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        app.filter_cache_dirty = true;
+        app.goto_line(1);
+        assert_eq!(app.selected_entry().unwrap().repeat_count, 2);
+
+        for line in [4, 4, 9] {
+            let entry = LogEntry::new(
+                LogLevel::Error,
+                "boom".to_string(),
+                "boom".to_string(),
+                LogComponent::Build,
+                0,
+            );
+            log_tx
+                .send(entry.with_location("src/a.cpp".to_string(), Some(line), None))
+                .unwrap();
+        }
+        app.process_build_updates().unwrap();
+
+        let located: Vec<(Option<usize>, usize)> = app.log_entries[4..]
+            .iter()
+            .map(|e| (e.line_number, e.repeat_count))
+            .collect();
+        assert_eq!(located, [(Some(4), 2), (Some(9), 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_quit_while_building_needs_confirmation() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty());
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+
+        let mut app = create_test_app();
+        assert!(!app.handle_key(q).await.unwrap());
+        assert!(app.quit_prompt().unwrap().starts_with("Build running"));
+
+        assert!(!app.handle_key(esc).await.unwrap());
+        assert!(app.quit_prompt().is_none());
+
+        assert!(!app.handle_key(q).await.unwrap());
+        assert!(app.handle_key(q).await.unwrap());
+
+        let mut app = create_test_app();
+        app.build_complete = true;
+        assert!(app.handle_key(q).await.unwrap());
+
+        let mut config = Config::default();
+        config.display.confirm_quit = false;
+        let (mut app, _log_tx, _step_tx) = create_test_app_with_logs(config);
+        assert!(app.handle_key(q).await.unwrap());
+    }
+
+    #[test]
+    fn test_dry_run_clean_keeps_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let build_dir = dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(build_dir.join("game.o"), "obj").unwrap();
+        let exec_info = ExecutableInfo {
+            path: build_dir.join("game"),
+            name: "game".to_string(),
+            project_dir: dir.path().to_path_buf(),
+            build_dir: build_dir.clone(),
+            install_dir: dir.path().join("install"),
+            args: Vec::new(),
+        };
+
+        let mut app = create_test_app();
+        app.builder = app.builder.clone().with_dry_run(true);
+        assert!(app.clean_executable(&exec_info).unwrap());
+
+        assert!(build_dir.join("game.o").exists());
+        assert!(app.log_entries[0].message.starts_with("Would remove"));
+    }
+
+    #[test]
+    fn test_kill_program_uses_kill_switch() {
+        let (kill_tx, mut kill_rx) = mpsc::unbounded_channel();
+        let mut app = create_exec_test_app().map(|app| app.with_kill_switch(kill_tx));
+        app.exec_pid = Some(u32::MAX);
+
+        app.kill_program();
+
+        assert!(kill_rx.try_recv().is_ok());
+        assert!(app.exec_pid.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exec_restart_and_kill_use_keybindings() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::empty());
+        let k = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::empty());
+
+        let (kill_tx, mut kill_rx) = mpsc::unbounded_channel();
+        let mut app = create_exec_test_app().map(|app| app.with_kill_switch(kill_tx));
+        assert!(!app.handle_key(r).await.unwrap());
+        assert!(app.handle_key(k).await.unwrap());
+        assert!(matches!(app.exec_action, Some(ExecAction::Kill)));
+        assert!(kill_rx.try_recv().is_ok());
+
+        let mut app = create_exec_test_app();
+        app.exec_complete = true;
+        assert!(!app.handle_key(k).await.unwrap());
+        assert!(app.handle_key(r).await.unwrap());
+        assert!(matches!(app.exec_action, Some(ExecAction::Restart)));
+
+        let mut app = create_test_app();
+        assert!(!app.handle_key(r).await.unwrap());
+        assert!(app.exec_action.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exec_args_command_requires_exec_mode() {
+        let mut app = create_test_app();
+        app.handle_command_result(CommandResult::SetExecArgs(vec!["--scene".to_string()]))
+            .await
+            .unwrap();
+        assert!(app.command_error.is_some());
+        assert!(app.take_exec_args_change().is_none());
+
+        let mut app = create_exec_test_app();
+        app.handle_command_result(CommandResult::SetExecArgs(vec![
+            "--scene".to_string(),
+            "test.json".to_string(),
+        ]))
+        .await
+        .unwrap();
+        assert!(app.command_error.is_none());
+        assert_eq!(
+            app.take_exec_args_change(),
+            Some(vec!["--scene".to_string(), "test.json".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_switch_preset() {
+        let mut app = create_test_app();
+
+        app.switch_preset("release");
+        assert_eq!(app.take_preset_change().as_deref(), Some("release"));
+        assert!(app.command_error.is_none());
+
+        app.switch_preset("debug");
+        assert!(app.take_preset_change().is_none());
+    }
+
+    #[test]
+    fn test_switch_preset_rejects_unknown() {
+        let mut app = create_test_app();
+
+        app.switch_preset("profile");
+        assert!(app.take_preset_change().is_none());
+        assert!(app.command_error.is_some());
+    }
+
+    fn finished(duration: f64) -> ExecutionResult {
+        ExecutionResult {
+            success: true,
+            duration,
+            stdout: vec![],
+            stderr: vec![],
+            exit_code: Some(0),
+            failure_reason: None,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn test_step_timing_uses_emission_timestamps() {
+        let mut app = create_test_app();
+        let started_at = app.start_time + Duration::from_secs(3);
+        let finished_at = started_at + Duration::from_secs(2);
+
+        app.handle_step_update(StepUpdate::Started(
+            BuildStep::new("Building engine".to_string(), Vec::new()),
+            started_at,
+        ));
+        std::thread::sleep(Duration::from_millis(50));
+        app.handle_step_update(StepUpdate::Finished(
+            BuildStep::new("Building engine".to_string(), Vec::new()),
+            finished(2.0),
+            finished_at,
+        ));
+
+        let window = &app.stage_windows[0];
+        assert!((window.end - window.start - 2.0).abs() < 1e-6);
+        assert!((app.build_steps[0].start_offset - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parallel_steps_finish_out_of_order() {
+        let mut app = create_test_app();
+        let step = |name: &str| BuildStep::new(format!("Building {}", name), Vec::new());
+        let at = |secs: u64| app.start_time + Duration::from_secs(secs);
+        let (engine_start, editor_start, editor_end, engine_end) = (at(1), at(2), at(4), at(6));
+
+        app.handle_step_update(StepUpdate::Started(step("engine"), engine_start));
+        app.handle_step_update(StepUpdate::Started(step("editor"), editor_start));
+        assert_eq!(app.current_step(), Some("Building editor"));
+        assert_eq!(app.total_steps, 2);
+
+        app.handle_step_update(StepUpdate::Finished(
+            step("editor"),
+            finished(2.0),
+            editor_end,
+        ));
+        assert_eq!(app.current_step(), Some("Building engine"));
+        app.handle_step_update(StepUpdate::Finished(
+            step("engine"),
+            finished(5.0),
+            engine_end,
+        ));
+        assert_eq!(app.current_step(), None);
+
+        let offsets: Vec<(&str, f64)> = app
+            .build_steps
+            .iter()
+            .map(|step| (step.description.as_str(), step.start_offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            [("Building editor", 2.0), ("Building engine", 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_step_commands_survive_round_trip() {
+        let mut app = create_test_app();
+        let commands: Vec<String> = ["cmake", "--build", "my build", "--target", "engine"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let step = BuildStep::new("Building engine".to_string(), commands.clone());
+        app.handle_step_update(StepUpdate::Started(
+            step.with_target("engine"),
+            Instant::now(),
+        ));
+        let step = BuildStep::new("Building engine".to_string(), commands.clone());
+        app.handle_step_update(StepUpdate::Finished(
+            step.with_target("engine"),
+            finished(2.0),
+            Instant::now(),
+        ));
+        assert_eq!(app.build_steps[0].commands, commands);
+        assert_eq!(app.build_steps[0].target.as_deref(), Some("engine"));
+
+        app.current_tab = TabId::Summary;
+        assert!(app.selected_step().is_none());
+        app.scroll_summary(3);
+        assert_eq!(app.selected_step().unwrap().commands, commands);
+        assert_eq!(
+            app.yank_text().as_deref(),
+            Some("cmake --build 'my build' --target engine")
+        );
+
+        let json = serde_json::to_string(&app.build_steps[0]).unwrap();
+        let step: BuildStepResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(step.commands, commands);
+
+        let legacy = r#"{"description":"x","duration":1.0,"success":true,
+            "error_count":0,"warning_count":0}"#;
+        let step: BuildStepResult = serde_json::from_str(legacy).unwrap();
+        assert!(step.commands.is_empty());
+    }
+
+    #[test]
+    fn test_current_step_timer_flags_slow_steps() {
+        let mut app = create_test_app();
+        assert_eq!(app.current_step_elapsed(), None);
+
+        let started_at = Instant::now() - Duration::from_secs(5);
+        app.handle_step_update(StepUpdate::Started(
+            BuildStep::new("Configure".to_string(), Vec::new()),
+            started_at,
+        ));
+        assert!(app.current_step_elapsed().unwrap() >= 5.0);
+        assert!(!app.current_step_is_slow());
+        app.handle_step_update(StepUpdate::Finished(
+            BuildStep::new("Configure".to_string(), Vec::new()),
+            finished(5.0),
+            Instant::now(),
+        ));
+
+        let started_at = Instant::now() - Duration::from_secs(61);
+        app.handle_step_update(StepUpdate::Started(
+            BuildStep::new("Build".to_string(), Vec::new()),
+            started_at,
+        ));
+        assert!(app.current_step_is_slow());
+
+        app.handle_step_update(StepUpdate::Finished(
+            BuildStep::new("Build".to_string(), Vec::new()),
+            finished(61.0),
+            Instant::now(),
+        ));
+        assert_eq!(app.current_step_elapsed(), None);
+        assert!(!app.current_step_is_slow());
+    }
+
+    #[tokio::test]
+    async fn test_skipped_stages_are_recorded() {
+        let (mut app, step_tx) = create_test_app_with_steps();
+
+        step_tx
+            .send(StepUpdate::Skipped(SkippedStep {
+                description: "Install project".to_string(),
+                reason: "Build failed".to_string(),
+            }))
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        app.process_build_updates().unwrap();
+
+        assert_eq!(app.skipped_steps.len(), 1);
+        assert_eq!(app.skipped_steps[0].description, "Install project");
+        assert_eq!(app.skipped_steps[0].reason, "Build failed");
+    }
+
+    #[tokio::test]
+    async fn test_build_duration_ignores_drain_delay() {
+        let (mut app, step_tx) = create_test_app_with_steps();
+
+        let started_at = app.start_time;
+        let finished_at = started_at + Duration::from_millis(10);
+        step_tx
+            .send(StepUpdate::Started(
+                BuildStep::new("Building engine".to_string(), Vec::new()),
+                started_at,
+            ))
+            .unwrap();
+        step_tx
+            .send(StepUpdate::Finished(
+                BuildStep::new("Building engine".to_string(), Vec::new()),
+                finished(0.01),
+                finished_at,
+            ))
+            .unwrap();
+        drop(step_tx);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        app.process_build_updates().unwrap();
+
+        assert!(app.build_complete);
+        assert!((app.build_duration.unwrap() - 0.01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_install_action_runs_only_install_stage() {
+        assert_eq!(BuildAction::Install.stages(), vec![Stage::Install]);
+        assert_eq!(
+            BuildAction::Rebuild.stages(),
+            vec![
+                Stage::PreValidation,
+                Stage::Configure,
+                Stage::Build,
+                Stage::Test,
+                Stage::Install,
+                Stage::PostBuild
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled_test_stage_is_skipped() {
+        let mut stages = crate::config::StagesConfig::default();
+        assert!(stages.is_enabled(Stage::Test));
+
+        stages.enabled_stages.retain(|s| s != "Test");
+        assert_eq!(
+            stages.filter_enabled(BuildAction::Rebuild.stages()),
+            vec![
+                Stage::PreValidation,
+                Stage::Configure,
+                Stage::Build,
+                Stage::Install,
+                Stage::PostBuild
+            ]
+        );
+    }
+
+    #[test]
+    fn test_watch_change_triggers_once() {
+        let (watch_tx, watch_rx) = watch::channel(0u64);
+        let mut app = create_test_app().map(|app| app.with_watch(watch_rx));
+
+        assert!(!app.watch_triggered());
+        watch_tx.send_modify(|generation| *generation += 1);
+        assert!(app.watch_triggered());
+        assert!(!app.watch_triggered());
+    }
+
+    #[test]
+    fn test_install_only_requires_previous_build() {
+        let mut app = create_test_app();
+
+        app.request_install_only();
+        assert!(app.get_build_action().is_none());
+        assert!(app.command_error.is_some());
+    }
+
+    #[test]
+    fn test_set_build_note() {
+        let mut app = create_test_app();
+
+        app.set_build_note(Some("toolchain upgrade".to_string()));
+        assert!(app.build_note.is_none());
+        assert!(app.command_error.is_some());
+
+        app.build_complete = true;
+        app.set_build_note(Some("toolchain upgrade".to_string()));
+        assert_eq!(app.build_note.as_deref(), Some("toolchain upgrade"));
+
+        app.set_build_note(None);
+        assert!(app.build_note.is_none());
+    }
+
+    fn push_logs(app: &mut App, levels: &[LogLevel]) {
+        for (i, level) in levels.iter().enumerate() {
+            let mut entry = LogEntry::new(
+                *level,
+                format!("line {}", i),
+                format!("line {}", i),
+                LogComponent::Compiler,
+                0,
+            );
+            app.assign_entry_id(&mut entry);
+            app.log_entries.push(entry);
+        }
+        app.filter_cache_dirty = true;
+    }
+
+    #[test]
+    fn test_console_selection_survives_filter_changes() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Info,
+                LogLevel::Info,
+                LogLevel::Error,
+                LogLevel::Info,
+                LogLevel::Error,
+            ],
+        );
+
+        app.select_console_position(4);
+        let selected_id = app.console_selection;
+        assert_eq!(selected_id, Some(app.log_entries[4].entry_id));
+
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        app.filter_cache_dirty = true;
+        assert_eq!(app.console_selection, selected_id);
+        assert_eq!(app.selected_console_position(), Some(1));
+
+        app.scroll_console_up(1);
+        assert_eq!(app.console_selection, Some(app.log_entries[2].entry_id));
+
+        app.active_filter = None;
+        app.filter_cache_dirty = true;
+        assert_eq!(app.selected_console_position(), Some(2));
+    }
+
+    #[test]
+    fn test_level_jumps_walk_errors_and_warnings() {
+        let mut app = create_test_app();
+        app.current_tab = TabId::Console;
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Info,
+                LogLevel::Warning,
+                LogLevel::Error,
+                LogLevel::Info,
+                LogLevel::Fatal,
+                LogLevel::Warning,
+            ],
+        );
+
+        app.jump_to_level(LogLevel::Error, true);
+        assert_eq!(app.selected_console_position(), Some(2));
+        assert!(!app.auto_scroll);
+
+        app.jump_to_level(LogLevel::Error, true);
+        assert_eq!(app.selected_console_position(), Some(4));
+        app.jump_to_level(LogLevel::Error, true);
+        assert_eq!(app.selected_console_position(), Some(4));
+
+        app.jump_to_level(LogLevel::Warning, false);
+        assert_eq!(app.selected_console_position(), Some(1));
+        app.jump_to_level(LogLevel::Warning, true);
+        assert_eq!(app.selected_console_position(), Some(5));
+
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Warning)));
+        app.filter_cache_dirty = true;
+        app.jump_to_level(LogLevel::Error, false);
+        assert_eq!(app.selected_console_position(), Some(2));
+        assert_eq!(app.selected_entry().unwrap().level, LogLevel::Fatal);
+    }
+
+    #[test]
+    fn test_selected_entry_follows_console_selection_and_filter() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Info,
+                LogLevel::Error,
+                LogLevel::Info,
+                LogLevel::Error,
+                LogLevel::Info,
+            ],
+        );
+
+        assert_eq!(app.selected_entry().unwrap().message, "line 4");
+
+        app.select_console_position(2);
+        assert_eq!(app.selected_entry().unwrap().message, "line 2");
+
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        app.filter_cache_dirty = true;
+        assert_eq!(app.selected_entry().unwrap().message, "line 3");
+
+        app.select_console_position(0);
+        assert_eq!(app.selected_entry().unwrap().message, "line 1");
+    }
+
+    #[test]
+    fn test_status_counts_follow_active_filter() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Info,
+                LogLevel::Warning,
+                LogLevel::Error,
+                LogLevel::Fatal,
+                LogLevel::Warning,
+            ],
+        );
+
+        let (totals, shown) = app.status_counts();
+        assert_eq!(totals.lines, 5);
+        assert_eq!(totals.warnings, 2);
+        assert!(shown.is_none());
+        assert_eq!(count_text(shown.map(|s| s.errors), totals.errors), "1");
+
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        let (totals, shown) = app.status_counts();
+        let shown = shown.unwrap();
+        assert_eq!(
+            (shown.lines, shown.fatal, shown.errors, shown.warnings),
+            (2, 1, 1, 0)
+        );
+        assert_eq!(count_text(Some(shown.warnings), totals.warnings), "0 of 2");
+    }
+
+    #[test]
+    fn test_selected_entry_uses_warnings_tab_selection() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Warning,
+                LogLevel::Info,
+                LogLevel::Warning,
+                LogLevel::Error,
+            ],
+        );
+        app.current_tab = TabId::Warnings;
+        assert!(app.selected_entry().is_none());
+
+        app.scroll_warnings(5);
+        assert_eq!(app.selected_entry().unwrap().message, "line 2");
+
+        app.scroll_warnings(-1);
+        assert_eq!(app.selected_entry().unwrap().message, "line 0");
+    }
+
+    #[test]
+    fn test_errors_tab_lists_errors_by_file_with_its_own_selection() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Error,
+                LogLevel::Warning,
+                LogLevel::Error,
+                LogLevel::Fatal,
+                LogLevel::Error,
+            ],
+        );
+        app.log_entries[0].file_path = Some("src/b.cpp".to_string());
+        app.log_entries[4].file_path = Some("src/a.cpp".to_string());
+
+        app.current_tab = TabId::Errors;
+        let errors: Vec<&str> = app
+            .diagnostics()
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect();
+        assert_eq!(errors, ["line 3", "line 4", "line 0", "line 2"]);
+
+        app.jump_to_diagnostic(true);
+        assert_eq!(app.selected_entry().unwrap().message, "line 4");
+        assert_eq!(app.diagnostic_position(), Some((1, 2)));
+
+        app.current_tab = TabId::Warnings;
+        assert!(app.selected_entry().is_none());
+    }
+
+    #[test]
+    fn test_diagnostic_jump_cycles_through_located_entries() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[
+                LogLevel::Warning,
+                LogLevel::Warning,
+                LogLevel::Error,
+                LogLevel::Warning,
+                LogLevel::Error,
+            ],
+        );
+        for i in [0, 2, 3] {
+            app.log_entries[i].file_path = Some(format!("src/file{}.cpp", i));
+        }
+        app.current_tab = TabId::Warnings;
+
+        let diagnostics: Vec<&str> = app
+            .diagnostics()
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect();
+        assert_eq!(
+            diagnostics,
+            ["line 2", "line 4", "line 0", "line 1", "line 3"]
+        );
+        assert_eq!(app.diagnostic_position(), Some((0, 3)));
+
+        app.jump_to_diagnostic(true);
+        assert_eq!(app.selected_entry().unwrap().message, "line 2");
+        assert_eq!(app.diagnostic_position(), Some((1, 3)));
+
+        app.jump_to_diagnostic(true);
+        app.jump_to_diagnostic(true);
+        assert_eq!(app.selected_entry().unwrap().message, "line 3");
+        assert_eq!(app.diagnostic_position(), Some((3, 3)));
+
+        app.jump_to_diagnostic(true);
+        assert_eq!(app.selected_entry().unwrap().message, "line 2");
+
+        app.jump_to_diagnostic(false);
+        assert_eq!(app.selected_entry().unwrap().message, "line 3");
+    }
+
+    #[test]
+    fn test_filtered_out_selection_moves_to_next_visible_entry() {
+        let mut app = create_test_app();
+        push_logs(
+            &mut app,
+            &[LogLevel::Error, LogLevel::Info, LogLevel::Error],
+        );
+
+        app.select_console_position(1);
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        app.filter_cache_dirty = true;
+
+        assert_eq!(app.selected_console_position(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_scroll_keys_behave_the_same_in_both_modes() {
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty());
+        let levels = [
+            LogLevel::Info,
+            LogLevel::Warning,
+            LogLevel::Info,
+            LogLevel::Error,
+            LogLevel::Info,
+            LogLevel::Warning,
+        ];
+
+        for mut app in [create_test_app(), create_exec_test_app()] {
+            push_logs(&mut app, &levels);
+            app.exec_logs = app.log_entries.clone();
+            app.current_tab = TabId::Console;
+            app.select_console_position(1);
+
+            assert!(!app.handle_key(j).await.unwrap());
+            assert!(!app.handle_key(j).await.unwrap());
+            assert_eq!(app.selected_console_position(), Some(3), "{:?}", app.mode);
+
+            app.current_tab = TabId::Warnings;
+            assert!(!app.handle_key(j).await.unwrap());
+            assert_eq!(
+                app.warnings_scroll_state.selected(),
+                Some(1),
+                "{:?}",
+                app.mode
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_prefix_repeats_search_and_tab_motions() {
+        let press = |c: char| {
+            let modifiers = if c.is_ascii_uppercase() {
+                KeyModifiers::SHIFT
+            } else {
+                KeyModifiers::empty()
+            };
+            KeyEvent::new(KeyCode::Char(c), modifiers)
+        };
+
+        let mut app = create_test_app();
+        push_logs(&mut app, &[LogLevel::Info; 12]);
+        app.current_tab = TabId::Console;
+        app.search_pattern = Some("1".to_string());
+        app.select_console_position(0);
+
+        for c in ['3', 'n'] {
+            assert!(!app.handle_key(press(c)).await.unwrap());
+        }
+        assert_eq!(app.selected_console_position(), Some(11));
+        assert!(!app.vim_mode.has_count());
+
+        for c in ['2', 'N'] {
+            assert!(!app.handle_key(press(c)).await.unwrap());
+        }
+        assert_eq!(app.selected_console_position(), Some(1));
+
+        let start = app.tab_set.position(app.current_tab).unwrap();
+        for c in ['2', 'L'] {
+            assert!(!app.handle_key(press(c)).await.unwrap());
+        }
+        let expected = (start + 2) % app.tab_set.len();
+        assert_eq!(app.tab_set.position(app.current_tab), Some(expected));
+        assert!(!app.vim_mode.has_count());
+    }
+
+    #[test]
+    fn test_view_state_carries_filter_and_search_to_new_app() {
+        let mut app = create_test_app();
+        app.active_filter = Some(Box::new(LevelFilter::new(LogLevel::Error)));
+        app.search_pattern = Some("undefined".to_string());
+
+        let state = app.snapshot_view_state();
+        assert_eq!(state.filter, Some(FilterSpec::Level(LogLevel::Error)));
+        let json = serde_json::to_string(&state).unwrap();
+        let state: ViewState = serde_json::from_str(&json).unwrap();
+
+        let mut rebuilt = create_test_app();
+        push_logs(
+            &mut rebuilt,
+            &[LogLevel::Info, LogLevel::Error, LogLevel::Warning],
+        );
+        rebuilt.restore_view_state(state);
+        assert_eq!(rebuilt.search_pattern.as_deref(), Some("undefined"));
+        assert_eq!(
+            rebuilt
+                .active_filter
+                .as_ref()
+                .map(|filter| filter.description()),
+            Some("level >= ERROR".to_string())
+        );
+        assert_eq!(rebuilt.console_view(), vec![1]);
+
+        rebuilt.restore_view_state(ViewState::default());
+        assert!(rebuilt.active_filter.is_none());
+
+        app.active_filter = Some(Box::new(CompositeFilter::or(vec![
+            Box::new(LevelFilter::only(LogLevel::Info)),
+            Box::new(LevelFilter::only(LogLevel::Warning)),
+        ])));
+        let state = app.snapshot_view_state();
+        assert!(matches!(state.filter, Some(FilterSpec::Any(ref specs)) if specs.len() == 2));
+        rebuilt.restore_view_state(state);
+        assert_eq!(rebuilt.console_view(), vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_l_requests_redraw() {
+        let mut app = create_test_app();
+        app.console_scroll_state.select(Some(3));
+
+        let quit = app
+            .handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL))
+            .await
+            .unwrap();
+
+        assert!(!quit);
+        assert!(app.redraw_requested);
+        assert_eq!(app.console_scroll_state.selected(), Some(3));
+    }
+}
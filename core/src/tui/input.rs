@@ -2,7 +2,7 @@ use crossterm::event::{KeyCode, KeyEvent};
 use super::keybinding_manager::{KeyBindingManager, KeyPress, SequenceMatch};
 use super::vim::VimCommandMode;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputAction {
     Quit,
     SwitchTab(usize),
@@ -18,6 +18,12 @@ pub enum InputAction {
     NextSearch,
     PrevSearch,
     OpenFile,
+    NextDiagnostic,
+    PrevDiagnostic,
+    NextError,
+    PrevError,
+    NextWarning,
+    PrevWarning,
     YankLine,
     OpenBuildMenu,
     OpenExecMenu,
@@ -39,15 +45,130 @@ pub enum InputAction {
     ScrollPageDownCount(usize),
     ScrollHalfPageUpCount(usize),
     ScrollHalfPageDownCount(usize),
+    NextSearchCount(usize),
+    PrevSearchCount(usize),
+    NextTabCount(usize),
+    PrevTabCount(usize),
     WriteLogs,
     CleanBuild,
     Rebuild,
     ShowHelp,
     RestartExec,
     KillExec,
+    TogglePreviousLogs,
+    ToggleRelativeNumbers,
+    ToggleFollow,
+    HistoryDiff,
+    ToggleSlowestSteps,
+    ToggleTrendFailures,
+    CycleStepSort,
+    PanChartLeft,
+    PanChartRight,
+    ZoomChartIn,
+    ZoomChartOut,
+    CycleResourceChart,
+    Redraw,
     None,
 }
 
+const UNIT_ACTIONS: [InputAction; 53] = [
+    InputAction::Quit,
+    InputAction::NextTab,
+    InputAction::PrevTab,
+    InputAction::EnterCommand,
+    InputAction::EnterSearch,
+    InputAction::ExecuteCommand,
+    InputAction::ExecuteSearch,
+    InputAction::CancelInput,
+    InputAction::Backspace,
+    InputAction::NextSearch,
+    InputAction::PrevSearch,
+    InputAction::OpenFile,
+    InputAction::NextDiagnostic,
+    InputAction::PrevDiagnostic,
+    InputAction::NextError,
+    InputAction::PrevError,
+    InputAction::NextWarning,
+    InputAction::PrevWarning,
+    InputAction::YankLine,
+    InputAction::OpenBuildMenu,
+    InputAction::OpenExecMenu,
+    InputAction::ScrollUp,
+    InputAction::ScrollDown,
+    InputAction::ScrollPageUp,
+    InputAction::ScrollPageDown,
+    InputAction::ScrollHalfPageUp,
+    InputAction::ScrollHalfPageDown,
+    InputAction::ScrollToTop,
+    InputAction::ScrollToBottom,
+    InputAction::ScrollToMiddle,
+    InputAction::ScrollToViewportTop,
+    InputAction::ScrollToViewportMiddle,
+    InputAction::ScrollToViewportBottom,
+    InputAction::WriteLogs,
+    InputAction::CleanBuild,
+    InputAction::Rebuild,
+    InputAction::ShowHelp,
+    InputAction::RestartExec,
+    InputAction::KillExec,
+    InputAction::TogglePreviousLogs,
+    InputAction::ToggleRelativeNumbers,
+    InputAction::ToggleFollow,
+    InputAction::HistoryDiff,
+    InputAction::ToggleSlowestSteps,
+    InputAction::ToggleTrendFailures,
+    InputAction::CycleStepSort,
+    InputAction::PanChartLeft,
+    InputAction::PanChartRight,
+    InputAction::ZoomChartIn,
+    InputAction::ZoomChartOut,
+    InputAction::CycleResourceChart,
+    InputAction::Redraw,
+    InputAction::None,
+];
+
+impl InputAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some((name, arg)) = name.split_once(':') {
+            let n: usize = arg.trim().parse().ok()?;
+            return match name {
+                "SwitchTab" => n.checked_sub(1).map(Self::SwitchTab),
+                "ScrollUpCount" => Some(Self::ScrollUpCount(n)),
+                "ScrollDownCount" => Some(Self::ScrollDownCount(n)),
+                "ScrollPageUpCount" => Some(Self::ScrollPageUpCount(n)),
+                "ScrollPageDownCount" => Some(Self::ScrollPageDownCount(n)),
+                "ScrollHalfPageUpCount" => Some(Self::ScrollHalfPageUpCount(n)),
+                "ScrollHalfPageDownCount" => Some(Self::ScrollHalfPageDownCount(n)),
+                "NextSearchCount" => Some(Self::NextSearchCount(n)),
+                "PrevSearchCount" => Some(Self::PrevSearchCount(n)),
+                "NextTabCount" => Some(Self::NextTabCount(n)),
+                "PrevTabCount" => Some(Self::PrevTabCount(n)),
+                _ => None,
+            };
+        }
+
+        UNIT_ACTIONS.into_iter().find(|action| action.name() == name)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::SwitchTab(index) => format!("SwitchTab:{}", index + 1),
+            Self::ScrollUpCount(n) => format!("ScrollUpCount:{}", n),
+            Self::ScrollDownCount(n) => format!("ScrollDownCount:{}", n),
+            Self::ScrollPageUpCount(n) => format!("ScrollPageUpCount:{}", n),
+            Self::ScrollPageDownCount(n) => format!("ScrollPageDownCount:{}", n),
+            Self::ScrollHalfPageUpCount(n) => format!("ScrollHalfPageUpCount:{}", n),
+            Self::ScrollHalfPageDownCount(n) => format!("ScrollHalfPageDownCount:{}", n),
+            Self::NextSearchCount(n) => format!("NextSearchCount:{}", n),
+            Self::PrevSearchCount(n) => format!("PrevSearchCount:{}", n),
+            Self::NextTabCount(n) => format!("NextTabCount:{}", n),
+            Self::PrevTabCount(n) => format!("PrevTabCount:{}", n),
+            Self::InsertChar(c) => format!("InsertChar:{}", c),
+            action => format!("{:?}", action),
+        }
+    }
+}
+
 pub fn handle_key_event(
     key: KeyEvent,
     vim_mode: &mut VimCommandMode,
@@ -103,6 +224,12 @@ pub fn handle_key_event(
         return InputAction::None;
     }
 
+    if let Some(action) = keybindings.match_exec_key(&key_press) {
+        let count = vim_mode.get_count();
+        vim_mode.clear_count();
+        return apply_count_to_action(action, count);
+    }
+
     let sequence = vec![key_press.clone()];
     match keybindings.match_sequence(&sequence) {
         SequenceMatch::Complete(action) => {
@@ -127,6 +254,33 @@ pub fn handle_key_event(
     InputAction::None
 }
 
+// A lone key that both starts a sequence and has a binding of its own fires
+// that binding once the sequence times out, like vim's timeoutlen.
+pub fn resolve_sequence_timeout(
+    vim_mode: &mut VimCommandMode,
+    keybindings: &KeyBindingManager,
+) -> InputAction {
+    if !vim_mode.is_sequence_timeout(keybindings.get_sequence_timeout()) {
+        return InputAction::None;
+    }
+
+    let keys = vim_mode
+        .pending_sequence
+        .take()
+        .map(|pending| pending.keys)
+        .unwrap_or_default();
+    let count = vim_mode.get_count();
+    vim_mode.clear_count();
+
+    match keys.as_slice() {
+        [key] => keybindings
+            .match_single_key(key)
+            .map(|action| apply_count_to_action(action, count))
+            .unwrap_or(InputAction::None),
+        _ => InputAction::None,
+    }
+}
+
 fn handle_input_mode(key: KeyEvent, is_command: bool) -> InputAction {
     match key.code {
         KeyCode::Enter => {
@@ -151,6 +305,187 @@ fn apply_count_to_action(action: InputAction, count: usize) -> InputAction {
         InputAction::ScrollPageDown => InputAction::ScrollPageDownCount(count),
         InputAction::ScrollHalfPageUp => InputAction::ScrollHalfPageUpCount(count),
         InputAction::ScrollHalfPageDown => InputAction::ScrollHalfPageDownCount(count),
+        InputAction::NextSearch => InputAction::NextSearchCount(count),
+        InputAction::PrevSearch => InputAction::PrevSearchCount(count),
+        InputAction::NextTab => InputAction::NextTabCount(count),
+        InputAction::PrevTab => InputAction::PrevTabCount(count),
         _ => action,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::keybinding_manager::PendingSequence;
+    use std::time::{Duration, Instant};
+
+    fn expire(vim_mode: &mut VimCommandMode) {
+        let pending = vim_mode.pending_sequence.as_mut().unwrap();
+        pending.timestamp = Instant::now() - Duration::from_millis(1500);
+    }
+
+    #[test]
+    fn test_sequence_timeout_fires_standalone_binding() {
+        let mut keybindings = KeyBindingManager::new(KeyPress::from_char(' '), 1000, true);
+        keybindings.add_single_key_binding(KeyPress::from_char('z'), InputAction::ScrollDown);
+        let mut vim_mode = VimCommandMode::new();
+
+        vim_mode.push_count_digit('3');
+        vim_mode.start_sequence(KeyPress::from_char('z'));
+        assert!(matches!(
+            resolve_sequence_timeout(&mut vim_mode, &keybindings),
+            InputAction::None
+        ));
+        assert!(vim_mode.pending_sequence.is_some());
+
+        expire(&mut vim_mode);
+        assert!(matches!(
+            resolve_sequence_timeout(&mut vim_mode, &keybindings),
+            InputAction::ScrollDownCount(3)
+        ));
+        assert!(vim_mode.pending_sequence.is_none());
+        assert!(!vim_mode.has_count());
+    }
+
+    #[test]
+    fn test_sequence_timeout_clears_unbound_prefix() {
+        let keybindings = KeyBindingManager::new(KeyPress::from_char(' '), 1000, true);
+        let mut vim_mode = VimCommandMode::new();
+
+        vim_mode.pending_sequence = Some(PendingSequence::new(KeyPress::from_char('g')));
+        expire(&mut vim_mode);
+        assert!(matches!(
+            resolve_sequence_timeout(&mut vim_mode, &keybindings),
+            InputAction::None
+        ));
+        assert!(vim_mode.pending_sequence.is_none());
+    }
+
+    #[test]
+    fn test_exec_keys_win_over_sequences_in_exec_mode() {
+        let k = KeyEvent::new(KeyCode::Char('k'), crossterm::event::KeyModifiers::empty());
+        let kk = vec![KeyPress::from_char('k'), KeyPress::from_char('k')];
+
+        let mut keybindings = KeyBindingManager::default();
+        keybindings.add_vim_sequence(kk.clone(), InputAction::ScrollToTop);
+        let mut vim_mode = VimCommandMode::new();
+        assert!(matches!(
+            handle_key_event(k, &mut vim_mode, &keybindings, false, false),
+            InputAction::None
+        ));
+        assert!(vim_mode.pending_sequence.is_some());
+
+        let mut keybindings = KeyBindingManager::default().with_exec_mode();
+        keybindings.add_vim_sequence(kk, InputAction::ScrollToTop);
+        let mut vim_mode = VimCommandMode::new();
+        assert!(matches!(
+            handle_key_event(k, &mut vim_mode, &keybindings, false, false),
+            InputAction::KillExec
+        ));
+        assert!(vim_mode.pending_sequence.is_none());
+    }
+
+    #[test]
+    fn test_unit_actions_cover_every_unit_variant() {
+        // Adding a variant breaks this match. Give unit variants the next index
+        // before `None` and list them in UNIT_ACTIONS too.
+        let index = |action: InputAction| -> Option<usize> {
+            match action {
+                InputAction::Quit => Some(0),
+                InputAction::NextTab => Some(1),
+                InputAction::PrevTab => Some(2),
+                InputAction::EnterCommand => Some(3),
+                InputAction::EnterSearch => Some(4),
+                InputAction::ExecuteCommand => Some(5),
+                InputAction::ExecuteSearch => Some(6),
+                InputAction::CancelInput => Some(7),
+                InputAction::Backspace => Some(8),
+                InputAction::NextSearch => Some(9),
+                InputAction::PrevSearch => Some(10),
+                InputAction::OpenFile => Some(11),
+                InputAction::NextDiagnostic => Some(12),
+                InputAction::PrevDiagnostic => Some(13),
+                InputAction::NextError => Some(14),
+                InputAction::PrevError => Some(15),
+                InputAction::NextWarning => Some(16),
+                InputAction::PrevWarning => Some(17),
+                InputAction::YankLine => Some(18),
+                InputAction::OpenBuildMenu => Some(19),
+                InputAction::OpenExecMenu => Some(20),
+                InputAction::ScrollUp => Some(21),
+                InputAction::ScrollDown => Some(22),
+                InputAction::ScrollPageUp => Some(23),
+                InputAction::ScrollPageDown => Some(24),
+                InputAction::ScrollHalfPageUp => Some(25),
+                InputAction::ScrollHalfPageDown => Some(26),
+                InputAction::ScrollToTop => Some(27),
+                InputAction::ScrollToBottom => Some(28),
+                InputAction::ScrollToMiddle => Some(29),
+                InputAction::ScrollToViewportTop => Some(30),
+                InputAction::ScrollToViewportMiddle => Some(31),
+                InputAction::ScrollToViewportBottom => Some(32),
+                InputAction::WriteLogs => Some(33),
+                InputAction::CleanBuild => Some(34),
+                InputAction::Rebuild => Some(35),
+                InputAction::ShowHelp => Some(36),
+                InputAction::RestartExec => Some(37),
+                InputAction::KillExec => Some(38),
+                InputAction::TogglePreviousLogs => Some(39),
+                InputAction::ToggleRelativeNumbers => Some(40),
+                InputAction::ToggleFollow => Some(41),
+                InputAction::HistoryDiff => Some(42),
+                InputAction::ToggleSlowestSteps => Some(43),
+                InputAction::ToggleTrendFailures => Some(44),
+                InputAction::CycleStepSort => Some(45),
+                InputAction::PanChartLeft => Some(46),
+                InputAction::PanChartRight => Some(47),
+                InputAction::ZoomChartIn => Some(48),
+                InputAction::ZoomChartOut => Some(49),
+                InputAction::CycleResourceChart => Some(50),
+                InputAction::Redraw => Some(51),
+                InputAction::None => Some(52),
+                InputAction::SwitchTab(_)
+                | InputAction::InsertChar(_)
+                | InputAction::ScrollUpCount(_)
+                | InputAction::ScrollDownCount(_)
+                | InputAction::ScrollPageUpCount(_)
+                | InputAction::ScrollPageDownCount(_)
+                | InputAction::ScrollHalfPageUpCount(_)
+                | InputAction::ScrollHalfPageDownCount(_)
+                | InputAction::NextSearchCount(_)
+                | InputAction::PrevSearchCount(_)
+                | InputAction::NextTabCount(_)
+                | InputAction::PrevTabCount(_) => None,
+            }
+        };
+
+        let mut indices: Vec<usize> = UNIT_ACTIONS.iter().filter_map(|a| index(*a)).collect();
+        indices.sort();
+        assert_eq!(indices, (0..UNIT_ACTIONS.len()).collect::<Vec<_>>());
+        assert_eq!(index(InputAction::None), Some(UNIT_ACTIONS.len() - 1));
+    }
+
+    #[test]
+    fn test_action_names_round_trip() {
+        for action in UNIT_ACTIONS {
+            assert_eq!(InputAction::from_name(&action.name()), Some(action));
+        }
+
+        for action in [
+            InputAction::SwitchTab(0),
+            InputAction::SwitchTab(4),
+            InputAction::ScrollUpCount(3),
+            InputAction::ScrollHalfPageDownCount(2),
+            InputAction::NextSearchCount(3),
+            InputAction::PrevTabCount(2),
+        ] {
+            assert_eq!(InputAction::from_name(&action.name()), Some(action));
+        }
+
+        assert_eq!(InputAction::from_name("SwitchTab:2"), Some(InputAction::SwitchTab(1)));
+        assert_eq!(InputAction::from_name("SwitchTab:0"), None);
+        assert_eq!(InputAction::from_name("ScrollUp:x"), None);
+        assert_eq!(InputAction::from_name("scrolltotop"), None);
+        assert_eq!(InputAction::from_name("InsertChar:a"), None);
+    }
+}
@@ -1,4 +1,5 @@
 use super::input::InputAction;
+use crate::config::KeybindingsConfig;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashMap;
 use std::time::Instant;
@@ -34,14 +35,32 @@ impl KeyPress {
             "Backslash" => Some(Self::new(KeyCode::Char('\\'), KeyModifiers::empty())),
             "Enter" => Some(Self::new(KeyCode::Enter, KeyModifiers::empty())),
             "Esc" => Some(Self::new(KeyCode::Esc, KeyModifiers::empty())),
-            s if s.len() == 1 => {
+            s if s.chars().count() == 1 => {
                 let c = s.chars().next()?;
-                Some(Self::from_char(c))
+                Some(Self::from_typed_char(c))
             }
             _ => None,
         }
     }
 
+    pub fn sequence_from_string(s: &str) -> Option<KeySequence> {
+        if let Some(key) = Self::from_string(s) {
+            return Some(vec![key]);
+        }
+        if s.is_empty() {
+            return None;
+        }
+        Some(s.chars().map(Self::from_typed_char).collect())
+    }
+
+    fn from_typed_char(c: char) -> Self {
+        if c.is_ascii_uppercase() {
+            Self::new(KeyCode::Char(c), KeyModifiers::SHIFT)
+        } else {
+            Self::from_char(c)
+        }
+    }
+
     pub fn to_display_string(&self) -> String {
         match &self.code {
             KeyCode::Char(' ') => "<Space>".to_string(),
@@ -69,6 +88,8 @@ pub struct KeyBindingManager {
     leader_bindings: HashMap<KeyPress, InputAction>,
     vim_sequences: HashMap<KeySequence, InputAction>,
     single_key_bindings: HashMap<KeyPress, InputAction>,
+    exec_key_bindings: HashMap<KeyPress, InputAction>,
+    exec_mode: bool,
     enable_leader: bool,
 }
 
@@ -84,6 +105,8 @@ impl KeyBindingManager {
             leader_bindings: HashMap::new(),
             vim_sequences: HashMap::new(),
             single_key_bindings: HashMap::new(),
+            exec_key_bindings: HashMap::new(),
+            exec_mode: false,
             enable_leader,
         };
 
@@ -91,6 +114,52 @@ impl KeyBindingManager {
         manager
     }
 
+    pub fn apply_config(&mut self, config: &KeybindingsConfig) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let mut leader_bindings: Vec<_> = config.leader_bindings.iter().collect();
+        leader_bindings.sort();
+        for (key, name) in leader_bindings {
+            let Some(action) = InputAction::from_name(name) else {
+                warnings.push(format!(
+                    "keybindings.leader_bindings.{}: unknown action '{}'",
+                    key, name
+                ));
+                continue;
+            };
+            match KeyPress::from_string(key) {
+                Some(key) => self.add_leader_binding(key, action),
+                None => warnings.push(format!(
+                    "keybindings.leader_bindings: cannot parse key '{}'",
+                    key
+                )),
+            }
+        }
+
+        let mut vim_sequences: Vec<_> = config.vim_sequences.iter().collect();
+        vim_sequences.sort();
+        for (keys, name) in vim_sequences {
+            let Some(action) = InputAction::from_name(name) else {
+                warnings.push(format!(
+                    "keybindings.vim_sequences.{}: unknown action '{}'",
+                    keys, name
+                ));
+                continue;
+            };
+            match KeyPress::sequence_from_string(keys) {
+                Some(sequence) => self.add_vim_sequence(sequence, action),
+                None => warnings.push("keybindings.vim_sequences: empty key sequence".to_string()),
+            }
+        }
+
+        warnings
+    }
+
+    pub fn with_exec_mode(mut self) -> Self {
+        self.exec_mode = true;
+        self
+    }
+
     fn setup_default_bindings(&mut self) {
         if self.enable_leader {
             self.leader_bindings.insert(
@@ -125,12 +194,40 @@ impl KeyBindingManager {
                 KeyPress::from_char('?'),
                 InputAction::ShowHelp,
             );
+            self.leader_bindings.insert(
+                KeyPress::from_char('n'),
+                InputAction::ToggleRelativeNumbers,
+            );
         }
 
         self.vim_sequences.insert(
             vec![KeyPress::from_char('g'), KeyPress::from_char('f')],
             InputAction::OpenFile,
         );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char(']'), KeyPress::from_char('q')],
+            InputAction::NextDiagnostic,
+        );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char('['), KeyPress::from_char('q')],
+            InputAction::PrevDiagnostic,
+        );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char(']'), KeyPress::from_char('e')],
+            InputAction::NextError,
+        );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char('['), KeyPress::from_char('e')],
+            InputAction::PrevError,
+        );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char(']'), KeyPress::from_char('w')],
+            InputAction::NextWarning,
+        );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char('['), KeyPress::from_char('w')],
+            InputAction::PrevWarning,
+        );
         self.vim_sequences.insert(
             vec![KeyPress::from_char('y'), KeyPress::from_char('y')],
             InputAction::YankLine,
@@ -151,6 +248,10 @@ impl KeyBindingManager {
             vec![KeyPress::from_char('z'), KeyPress::from_char('b')],
             InputAction::ScrollToViewportBottom,
         );
+        self.vim_sequences.insert(
+            vec![KeyPress::from_char('z'), KeyPress::from_char('a')],
+            InputAction::TogglePreviousLogs,
+        );
 
         self.single_key_bindings.insert(
             KeyPress::from_char('q'),
@@ -184,6 +285,14 @@ impl KeyBindingManager {
             KeyPress::new(KeyCode::Char('5'), KeyModifiers::ALT),
             InputAction::SwitchTab(4),
         );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('6'), KeyModifiers::ALT),
+            InputAction::SwitchTab(5),
+        );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Enter, KeyModifiers::empty()),
+            InputAction::OpenFile,
+        );
         self.single_key_bindings.insert(
             KeyPress::from_char(':'),
             InputAction::EnterCommand,
@@ -196,10 +305,58 @@ impl KeyBindingManager {
             KeyPress::from_char('n'),
             InputAction::NextSearch,
         );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('d'),
+            InputAction::HistoryDiff,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('s'),
+            InputAction::ToggleSlowestSteps,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('f'),
+            InputAction::ToggleTrendFailures,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('o'),
+            InputAction::CycleStepSort,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Left, KeyModifiers::empty()),
+            InputAction::PanChartLeft,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Right, KeyModifiers::empty()),
+            InputAction::PanChartRight,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('+'),
+            InputAction::ZoomChartIn,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('+'), KeyModifiers::SHIFT),
+            InputAction::ZoomChartIn,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('='),
+            InputAction::ZoomChartIn,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::from_char('-'),
+            InputAction::ZoomChartOut,
+        );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('T'), KeyModifiers::SHIFT),
+            InputAction::CycleResourceChart,
+        );
         self.single_key_bindings.insert(
             KeyPress::new(KeyCode::Char('N'), KeyModifiers::SHIFT),
             InputAction::PrevSearch,
         );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('F'), KeyModifiers::SHIFT),
+            InputAction::ToggleFollow,
+        );
         self.single_key_bindings.insert(
             KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
             InputAction::ScrollToBottom,
@@ -256,6 +413,10 @@ impl KeyBindingManager {
             KeyPress::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
             InputAction::Quit,
         );
+        self.single_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+            InputAction::Redraw,
+        );
         self.single_key_bindings.insert(
             KeyPress::new(KeyCode::Char('L'), KeyModifiers::SHIFT),
             InputAction::NextTab,
@@ -264,6 +425,23 @@ impl KeyBindingManager {
             KeyPress::new(KeyCode::Char('H'), KeyModifiers::SHIFT),
             InputAction::PrevTab,
         );
+
+        self.exec_key_bindings.insert(
+            KeyPress::from_char('r'),
+            InputAction::RestartExec,
+        );
+        self.exec_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('R'), KeyModifiers::SHIFT),
+            InputAction::RestartExec,
+        );
+        self.exec_key_bindings.insert(
+            KeyPress::from_char('k'),
+            InputAction::KillExec,
+        );
+        self.exec_key_bindings.insert(
+            KeyPress::new(KeyCode::Char('K'), KeyModifiers::SHIFT),
+            InputAction::KillExec,
+        );
     }
 
     pub fn is_leader_key(&self, key: &KeyPress) -> bool {
@@ -300,8 +478,16 @@ impl KeyBindingManager {
         SequenceMatch::NoMatch
     }
 
+    pub fn match_exec_key(&self, key: &KeyPress) -> Option<InputAction> {
+        if !self.exec_mode {
+            return None;
+        }
+        self.exec_key_bindings.get(key).copied()
+    }
+
     pub fn match_single_key(&self, key: &KeyPress) -> Option<InputAction> {
-        self.single_key_bindings.get(key).copied()
+        self.match_exec_key(key)
+            .or_else(|| self.single_key_bindings.get(key).copied())
     }
 
     pub fn get_sequence_timeout(&self) -> u64 {
@@ -319,6 +505,10 @@ impl KeyBindingManager {
     pub fn add_single_key_binding(&mut self, key: KeyPress, action: InputAction) {
         self.single_key_bindings.insert(key, action);
     }
+
+    pub fn add_exec_key_binding(&mut self, key: KeyPress, action: InputAction) {
+        self.exec_key_bindings.insert(key, action);
+    }
 }
 
 impl Default for KeyBindingManager {
@@ -418,6 +608,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_bindings_override_defaults() {
+        let mut config = KeybindingsConfig::default();
+        config.leader_bindings.insert("g".to_string(), "Quit".to_string());
+        config.leader_bindings.insert("f".to_string(), "Rebuild".to_string());
+        config.leader_bindings.insert("x".to_string(), "Fly".to_string());
+        config.vim_sequences.insert("gq".to_string(), "Quit".to_string());
+        config.vim_sequences.insert("gG".to_string(), "ScrollToBottom".to_string());
+
+        let mut manager = KeyBindingManager::default();
+        let warnings = manager.apply_config(&config);
+        assert_eq!(
+            warnings,
+            vec!["keybindings.leader_bindings.x: unknown action 'Fly'".to_string()]
+        );
+
+        let leader = KeyPress::from_char(' ');
+        let leader_g = vec![leader.clone(), KeyPress::from_char('g')];
+        assert!(matches!(
+            manager.match_sequence(&leader_g),
+            SequenceMatch::Complete(InputAction::Quit)
+        ));
+        let leader_f = vec![leader, KeyPress::from_char('f')];
+        assert!(matches!(
+            manager.match_sequence(&leader_f),
+            SequenceMatch::Complete(InputAction::Rebuild)
+        ));
+
+        let gq = vec![KeyPress::from_char('g'), KeyPress::from_char('q')];
+        assert!(matches!(manager.match_sequence(&gq), SequenceMatch::Complete(InputAction::Quit)));
+        let g_shift_g = vec![
+            KeyPress::from_char('g'),
+            KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+        ];
+        assert!(matches!(
+            manager.match_sequence(&g_shift_g),
+            SequenceMatch::Complete(InputAction::ScrollToBottom)
+        ));
+    }
+
+    #[test]
+    fn test_exec_bindings_are_scoped_to_exec_mode() {
+        let k_key = KeyPress::from_char('k');
+        let r_key = KeyPress::from_char('r');
+
+        let manager = KeyBindingManager::default();
+        assert!(matches!(manager.match_single_key(&k_key), Some(InputAction::ScrollUp)));
+        assert!(manager.match_single_key(&r_key).is_none());
+
+        let mut manager = KeyBindingManager::default().with_exec_mode();
+        assert!(matches!(manager.match_single_key(&k_key), Some(InputAction::KillExec)));
+        assert!(matches!(manager.match_single_key(&r_key), Some(InputAction::RestartExec)));
+
+        manager.add_exec_key_binding(KeyPress::from_char('x'), InputAction::KillExec);
+        manager.add_exec_key_binding(k_key.clone(), InputAction::ScrollUp);
+        let x_key = KeyPress::from_char('x');
+        assert!(matches!(manager.match_single_key(&x_key), Some(InputAction::KillExec)));
+        assert!(matches!(manager.match_single_key(&k_key), Some(InputAction::ScrollUp)));
+    }
+
+    #[test]
+    fn test_ctrl_l_redraw() {
+        let manager = KeyBindingManager::default();
+
+        let ctrl_l = KeyPress::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        match manager.match_single_key(&ctrl_l) {
+            Some(InputAction::Redraw) => {},
+            _ => panic!("Expected redraw action for Ctrl+L"),
+        }
+    }
+
     #[test]
     fn test_key_display() {
         let space = KeyPress::from_char(' ');
@@ -1,8 +1,10 @@
 pub mod app;
 pub mod input;
 pub mod keybinding_manager;
+pub mod pacer;
+pub mod progress;
 pub mod tabs;
 pub mod vim;
 
-pub use app::{App, AppMode, BuildAction, ExecAction};
+pub use app::{restore_terminal, App, AppMode, BuildAction, ExecAction, ViewState};
 pub use vim::{InputMode, VimCommandMode};
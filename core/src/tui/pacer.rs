@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+const MIN_POLL: Duration = Duration::from_millis(1);
+
+pub struct RenderPacer {
+    refresh: Duration,
+    adaptive: bool,
+    last_render: Instant,
+    input_pending: bool,
+}
+
+impl RenderPacer {
+    pub fn new(refresh: Duration, adaptive: bool) -> Self {
+        Self {
+            refresh,
+            adaptive,
+            last_render: Instant::now(),
+            input_pending: false,
+        }
+    }
+
+    pub fn should_render(&self, now: Instant) -> bool {
+        (self.adaptive && self.input_pending)
+            || now.saturating_duration_since(self.last_render) >= self.refresh
+    }
+
+    // Adaptive mode sleeps until the next passive redraw is due, since input
+    // wakes the poll early and is rendered right away. Fixed mode polls at
+    // half the refresh interval so keys land within one frame.
+    pub fn poll_timeout(&self, now: Instant) -> Duration {
+        let timeout = if self.adaptive {
+            self.refresh
+                .saturating_sub(now.saturating_duration_since(self.last_render))
+        } else {
+            self.refresh / 2
+        };
+        timeout.max(MIN_POLL)
+    }
+
+    pub fn mark_input(&mut self) {
+        self.input_pending = true;
+    }
+
+    pub fn mark_rendered(&mut self, now: Instant) {
+        self.last_render = now;
+        self.input_pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_pacer_throttles_input() {
+        let start = Instant::now();
+        let mut pacer = RenderPacer::new(Duration::from_millis(100), false);
+        pacer.mark_rendered(start);
+        pacer.mark_input();
+
+        assert!(!pacer.should_render(start + Duration::from_millis(10)));
+        assert!(pacer.should_render(start + Duration::from_millis(100)));
+        assert_eq!(pacer.poll_timeout(start), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_adaptive_pacer_renders_input_immediately() {
+        let start = Instant::now();
+        let mut pacer = RenderPacer::new(Duration::from_millis(100), true);
+        pacer.mark_rendered(start);
+
+        assert!(!pacer.should_render(start + Duration::from_millis(10)));
+        assert_eq!(
+            pacer.poll_timeout(start + Duration::from_millis(30)),
+            Duration::from_millis(70)
+        );
+
+        pacer.mark_input();
+        assert!(pacer.should_render(start + Duration::from_millis(10)));
+
+        pacer.mark_rendered(start + Duration::from_millis(10));
+        assert!(!pacer.should_render(start + Duration::from_millis(20)));
+        assert_eq!(
+            pacer.poll_timeout(start + Duration::from_millis(500)),
+            MIN_POLL
+        );
+    }
+}
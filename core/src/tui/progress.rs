@@ -0,0 +1,66 @@
+pub const FILLED: &str = "█";
+pub const EMPTY: &str = "░";
+
+pub fn ratio(value: f64, max: f64) -> f64 {
+    if max <= 0.0 || !value.is_finite() || !max.is_finite() {
+        return 0.0;
+    }
+
+    (value / max).clamp(0.0, 1.0)
+}
+
+pub fn filled_width(ratio: f64, width: usize) -> usize {
+    if !ratio.is_finite() {
+        return 0;
+    }
+
+    ((ratio.clamp(0.0, 1.0) * width as f64) as usize).min(width)
+}
+
+pub fn bar(ratio: f64, width: usize) -> String {
+    let filled = filled_width(ratio, width);
+    format!("{}{}", FILLED.repeat(filled), EMPTY.repeat(width - filled))
+}
+
+pub fn value_bar(value: f64, max: f64, width: usize) -> String {
+    bar(ratio(value, max), width)
+}
+
+pub fn dual_bar(ratio: f64, width: usize) -> (String, String) {
+    let first = filled_width(ratio, width);
+    (FILLED.repeat(first), FILLED.repeat(width - first))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(s: &str) -> (usize, usize) {
+        (s.matches(FILLED).count(), s.matches(EMPTY).count())
+    }
+
+    #[test]
+    fn test_bar_boundary_ratios() {
+        assert_eq!(counts(&bar(0.0, 10)), (0, 10));
+        assert_eq!(counts(&bar(0.5, 10)), (5, 5));
+        assert_eq!(counts(&bar(1.0, 10)), (10, 0));
+        assert_eq!(counts(&bar(1.7, 10)), (10, 0));
+        assert_eq!(counts(&bar(-0.3, 10)), (0, 10));
+        assert_eq!(counts(&bar(f64::NAN, 10)), (0, 10));
+    }
+
+    #[test]
+    fn test_value_bar_handles_zero_max() {
+        assert_eq!(counts(&value_bar(5.0, 0.0, 8)), (0, 8));
+        assert_eq!(counts(&value_bar(3000.0, 2000.0, 8)), (8, 0));
+    }
+
+    #[test]
+    fn test_dual_bar_boundary_ratios() {
+        let widths = |(a, b): (String, String)| (counts(&a).0, counts(&b).0);
+        assert_eq!(widths(dual_bar(0.0, 10)), (0, 10));
+        assert_eq!(widths(dual_bar(0.5, 10)), (5, 5));
+        assert_eq!(widths(dual_bar(1.0, 10)), (10, 0));
+        assert_eq!(widths(dual_bar(2.0, 10)), (10, 0));
+    }
+}
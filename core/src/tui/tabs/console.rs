@@ -1,20 +1,68 @@
-use crate::parser::entry::{LogEntry, LogLevel};
+use crate::parser::entry::{LogEntry, LogLevel, StreamKind};
 use crate::parser::filters::LogFilter;
-use ratatui::layout::Alignment;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::widgets::block::Title;
 use ratatui::widgets::BorderType;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use std::ops::Range;
+
+pub const PREVIOUS_BUILD_TAG: &str = "previous-build";
+pub const PREVIOUS_BUILD_DIVIDER_TAG: &str = "previous-build-divider";
 
 pub struct ConsoleTab<'a> {
     log_entries: &'a [LogEntry],
     filter: Option<&'a Box<dyn LogFilter>>,
     search_pattern: Option<&'a str>,
+    relative_numbers: bool,
+    highlight_stderr: bool,
+    follow: bool,
+    unseen: usize,
+    minimap: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimapCell {
+    pub level: Option<LogLevel>,
+    pub in_view: bool,
+}
+
+pub fn minimap_cells(
+    entries: &[&LogEntry],
+    height: usize,
+    view: Range<usize>,
+) -> Vec<MinimapCell> {
+    let total = entries.len();
+    (0..height)
+        .map(|row| {
+            let start = row * total / height;
+            let end = ((row + 1) * total / height).max(start + 1).min(total);
+            let level = entries
+                .get(start..end)
+                .unwrap_or_default()
+                .iter()
+                .map(|entry| entry.level)
+                .filter(|level| *level >= LogLevel::Warning)
+                .max()
+                .map(|level| level.min(LogLevel::Error));
+            MinimapCell {
+                level,
+                in_view: start < total && start < view.end && end > view.start,
+            }
+        })
+        .collect()
+}
+
+pub fn gutter_number(index: usize, selected: Option<usize>, relative: bool) -> usize {
+    match selected {
+        Some(selected) if relative && index != selected => index.abs_diff(selected),
+        _ => index + 1,
+    }
 }
 
 impl<'a> ConsoleTab<'a> {
@@ -27,9 +75,35 @@ impl<'a> ConsoleTab<'a> {
             log_entries,
             filter,
             search_pattern,
+            relative_numbers: false,
+            highlight_stderr: false,
+            follow: true,
+            unseen: 0,
+            minimap: false,
         }
     }
 
+    pub fn with_minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+
+    pub fn with_stderr_highlight(mut self, highlight_stderr: bool) -> Self {
+        self.highlight_stderr = highlight_stderr;
+        self
+    }
+
+    pub fn with_follow(mut self, follow: bool, unseen: usize) -> Self {
+        self.follow = follow;
+        self.unseen = unseen;
+        self
+    }
+
+    pub fn with_relative_numbers(mut self, relative_numbers: bool) -> Self {
+        self.relative_numbers = relative_numbers;
+        self
+    }
+
     fn get_filtered_entries(&self) -> Vec<&LogEntry> {
         match self.filter {
             Some(filter) => self.log_entries.iter().filter(|e| filter.matches(e)).collect(),
@@ -37,6 +111,31 @@ impl<'a> ConsoleTab<'a> {
         }
     }
 
+    fn is_match(&self, entry: &LogEntry) -> bool {
+        self.search_pattern.is_some_and(|pattern| {
+            entry.message.contains(pattern) || entry.raw_line.contains(pattern)
+        })
+    }
+
+    fn search_status(&self, entries: &[&LogEntry], selected: Option<usize>) -> Option<String> {
+        self.search_pattern?;
+
+        let matches: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.is_match(entry))
+            .map(|(index, _)| index)
+            .collect();
+
+        let current = selected.and_then(|selected| matches.iter().position(|&i| i == selected));
+        Some(match (current, matches.len()) {
+            (_, 0) => "no matches".to_string(),
+            (Some(position), total) => format!("match {} of {}", position + 1, total),
+            (None, 1) => "1 match".to_string(),
+            (None, total) => format!("{} matches", total),
+        })
+    }
+
     fn log_level_color(level: LogLevel) -> Color {
         match level {
             LogLevel::Debug => Color::DarkGray,
@@ -47,9 +146,32 @@ impl<'a> ConsoleTab<'a> {
         }
     }
 
-    fn create_list_item(&self, entry: &'a LogEntry, index: usize, line_number_width: usize) -> ListItem<'a> {
-        let color = Self::log_level_color(entry.level);
-        let line_number = index + 1;
+    fn create_list_item(
+        &self,
+        entry: &'a LogEntry,
+        index: usize,
+        selected: Option<usize>,
+        line_number_width: usize,
+    ) -> ListItem<'a> {
+        if entry.has_tag(PREVIOUS_BUILD_DIVIDER_TAG) {
+            return ListItem::new(Line::from(Span::styled(
+                &entry.raw_line,
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let is_previous = entry.has_tag(PREVIOUS_BUILD_TAG);
+        let is_stderr = self.highlight_stderr && entry.stream == StreamKind::Stderr;
+        let color = if is_previous {
+            Color::DarkGray
+        } else if is_stderr && entry.level < LogLevel::Warning {
+            Color::LightMagenta
+        } else {
+            Self::log_level_color(entry.level)
+        };
+        let line_number = gutter_number(index, selected, self.relative_numbers);
         let timestamp = entry.timestamp.format("%H:%M:%S");
 
         let mut content = vec![
@@ -71,30 +193,62 @@ impl<'a> ConsoleTab<'a> {
             content.push(Span::styled(&entry.raw_line, Style::default().fg(color)));
         }
 
-        let line = match self.search_pattern {
-            Some(pattern) if entry.message.contains(pattern) || entry.raw_line.contains(pattern) => {
-                Line::from(content).patch_style(Style::default().add_modifier(Modifier::REVERSED))
-            }
-            _ => Line::from(content),
-        };
+        if entry.repeat_count > 1 {
+            content.push(Span::styled(
+                format!(" (x{})", entry.repeat_count),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        let mut line = Line::from(content);
+        if self.is_match(entry) {
+            line = if selected == Some(index) {
+                line.patch_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                line.patch_style(Style::default().add_modifier(Modifier::REVERSED | Modifier::DIM))
+            };
+        }
+
+        if is_previous {
+            line = line.patch_style(Style::default().add_modifier(Modifier::DIM));
+        }
 
         ListItem::new(line)
     }
 
-    fn build_title(&self) -> String {
-        let keybindings = "[j/k: Line | Ctrl+U/D: Half | Ctrl+F/B: Page | gg/G: Top/Bot | zz/zt/zb: View | n/N: Search]";
+    fn follow_badge(&self) -> String {
+        match (self.follow, self.unseen) {
+            (true, _) => "[FOLLOW]".to_string(),
+            (false, 0) => "[PAUSED]".to_string(),
+            (false, unseen) => format!("[PAUSED +{} new]", unseen),
+        }
+    }
+
+    fn build_title(&self, search_status: Option<&str>) -> String {
+        let keybindings = "[j/k: Line | Ctrl+U/D: Half | Ctrl+F/B: Page | gg/G: Top/Bot | zz/zt/zb: View | n/N: Search | F: Follow]";
+        let badge = match search_status {
+            Some(status) => format!("{} [{}]", self.follow_badge(), status),
+            None => self.follow_badge(),
+        };
         match self.filter {
-            Some(filter) => format!(" Console ({}) {} ", filter.description(), keybindings),
-            None => format!(" Console {} ", keybindings),
+            Some(filter) => {
+                format!(" Console ({}) {} {} ", filter.description(), badge, keybindings)
+            }
+            None => format!(" Console {} {} ", badge, keybindings),
         }
     }
 
-    fn create_block(&self) -> Block<'_> {
+    fn create_block(&self, search_status: Option<&str>) -> Block<'_> {
         Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Cyan))
-            .title(Title::from(self.build_title()).alignment(Alignment::Center))
+            .title(Title::from(self.build_title(search_status)).alignment(Alignment::Center))
     }
 
     fn render_empty(&self, frame: &mut Frame, area: Rect, state: &mut ListState) {
@@ -108,6 +262,30 @@ impl<'a> ConsoleTab<'a> {
         frame.render_stateful_widget(list, area, state);
     }
 
+    fn render_minimap(&self, frame: &mut Frame, area: Rect, entries: &[&LogEntry], offset: usize) {
+        let height = area.height.saturating_sub(2) as usize;
+        let view = offset..offset + height;
+        let lines: Vec<Line> = minimap_cells(entries, height, view)
+            .into_iter()
+            .map(|cell| {
+                let (symbol, color) = match cell.level {
+                    Some(LogLevel::Error) => ("▐", Color::Red),
+                    Some(_) => ("▐", Color::Yellow),
+                    None => ("│", Color::DarkGray),
+                };
+                let style = if cell.in_view {
+                    Style::default().fg(color).bg(Color::Gray)
+                } else {
+                    Style::default().fg(color)
+                };
+                Line::from(Span::styled(symbol, style))
+            })
+            .collect();
+
+        let inner = Rect::new(area.x, area.y + 1, area.width, height as u16);
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
     pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut ListState) {
         let filtered_entries = self.get_filtered_entries();
 
@@ -117,18 +295,129 @@ impl<'a> ConsoleTab<'a> {
         }
 
         let line_number_width = filtered_entries.len().to_string().len().max(3);
+        let selected = state.selected();
 
         let items: Vec<ListItem> = filtered_entries
             .iter()
             .enumerate()
-            .map(|(index, entry)| self.create_list_item(entry, index, line_number_width))
+            .map(|(index, entry)| self.create_list_item(entry, index, selected, line_number_width))
             .collect();
 
+        let search_status = self.search_status(&filtered_entries, selected);
+        let selected_is_match = selected
+            .and_then(|index| filtered_entries.get(index))
+            .is_some_and(|entry| self.is_match(entry));
+        let highlight_style = if selected_is_match {
+            Style::default()
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED)
+        };
+
         let list = List::new(items)
-            .block(self.create_block())
-            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .block(self.create_block(search_status.as_deref()))
+            .highlight_style(highlight_style)
             .highlight_symbol(">> ");
 
-        frame.render_stateful_widget(list, area, state);
+        if !self.minimap {
+            frame.render_stateful_widget(list, area, state);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        frame.render_stateful_widget(list, chunks[0], state);
+        self.render_minimap(frame, chunks[1], &filtered_entries, state.offset());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absolute_gutter_number() {
+        assert_eq!(gutter_number(0, Some(5), false), 1);
+        assert_eq!(gutter_number(9, None, false), 10);
+    }
+
+    #[test]
+    fn test_relative_gutter_number() {
+        assert_eq!(gutter_number(5, Some(5), true), 6);
+        assert_eq!(gutter_number(2, Some(5), true), 3);
+        assert_eq!(gutter_number(8, Some(5), true), 3);
+        assert_eq!(gutter_number(6, Some(5), true), 1);
+    }
+
+    #[test]
+    fn test_relative_gutter_without_selection() {
+        assert_eq!(gutter_number(3, None, true), 4);
+    }
+
+    #[test]
+    fn test_follow_badge_shows_unseen_lines() {
+        let tab = ConsoleTab::new(&[], None, None);
+        assert!(tab.build_title(None).starts_with(" Console [FOLLOW] "));
+
+        let tab = ConsoleTab::new(&[], None, None).with_follow(false, 0);
+        assert_eq!(tab.follow_badge(), "[PAUSED]");
+
+        let tab = ConsoleTab::new(&[], None, None).with_follow(false, 12);
+        assert_eq!(tab.follow_badge(), "[PAUSED +12 new]");
+    }
+
+    #[test]
+    fn test_search_status_counts_matches() {
+        let entries: Vec<LogEntry> = ["boom", "ok", "boom again", "boom"]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let component = crate::parser::entry::LogComponent::Other("app".to_string());
+                LogEntry::new(LogLevel::Info, line.to_string(), line.to_string(), component, i)
+            })
+            .collect();
+        let refs: Vec<&LogEntry> = entries.iter().collect();
+
+        let tab = ConsoleTab::new(&entries, None, None);
+        assert_eq!(tab.search_status(&refs, Some(0)), None);
+
+        let tab = ConsoleTab::new(&entries, None, Some("boom"));
+        assert_eq!(tab.search_status(&refs, Some(2)).unwrap(), "match 2 of 3");
+        assert_eq!(tab.search_status(&refs, Some(1)).unwrap(), "3 matches");
+        assert!(tab.build_title(Some("match 2 of 3")).contains("[match 2 of 3]"));
+
+        let tab = ConsoleTab::new(&entries, None, Some("missing"));
+        assert_eq!(tab.search_status(&refs, None).unwrap(), "no matches");
+    }
+
+    #[test]
+    fn test_minimap_places_ticks_proportionally() {
+        let mut levels = [LogLevel::Info; 20];
+        levels[1] = LogLevel::Warning;
+        levels[3] = LogLevel::Fatal;
+        levels[18] = LogLevel::Warning;
+        let entries: Vec<LogEntry> = levels
+            .iter()
+            .enumerate()
+            .map(|(i, level)| {
+                let component = crate::parser::entry::LogComponent::Other("app".to_string());
+                LogEntry::new(*level, String::new(), String::new(), component, i)
+            })
+            .collect();
+        let refs: Vec<&LogEntry> = entries.iter().collect();
+
+        let cells = minimap_cells(&refs, 5, 8..12);
+        let levels: Vec<_> = cells.iter().map(|cell| cell.level).collect();
+        assert_eq!(
+            levels,
+            vec![Some(LogLevel::Error), None, None, None, Some(LogLevel::Warning)]
+        );
+        let in_view: Vec<_> = cells.iter().map(|cell| cell.in_view).collect();
+        assert_eq!(in_view, vec![false, false, true, false, false]);
+
+        let cells = minimap_cells(&refs[..2], 4, 0..4);
+        assert_eq!(cells[2].level, Some(LogLevel::Warning));
+        assert!(cells.iter().all(|cell| cell.in_view));
     }
 }
@@ -1,71 +1,405 @@
-use crate::history::{BuildHistoryEntry, ExecutionHistoryEntry};
+use crate::history::{diff_steps, matches_query, BuildHistoryEntry, ExecutionHistoryEntry};
 use ratatui::{
-    layout::{Alignment, Constraint, Rect},
-    style::{Color, Style},
-    widgets::{Block, BorderType, Borders, Cell, Row, Table},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Borders, Cell, Paragraph, Row, Sparkline, Table, TableState,
+    },
     Frame,
 };
 
 pub struct HistoryTab<'a> {
     history: &'a [BuildHistoryEntry],
+    marked: &'a [usize],
+    trend_builds: usize,
+    trend_include_failed: bool,
+    query: Option<&'a str>,
+}
+
+pub fn history_rows(history: &[BuildHistoryEntry], query: Option<&str>) -> Vec<usize> {
+    (0..history.len())
+        .rev()
+        .filter(|&idx| query.map_or(true, |q| matches_query(&history[idx], q)))
+        .collect()
+}
+
+pub fn duration_trend(
+    history: &[BuildHistoryEntry],
+    n: usize,
+    include_failed: bool,
+) -> Vec<f64> {
+    let mut durations: Vec<f64> = history
+        .iter()
+        .rev()
+        .filter(|entry| include_failed || entry.success)
+        .take(n)
+        .map(|entry| entry.duration)
+        .collect();
+    durations.reverse();
+    durations
+}
+
+pub fn trend_color(durations: &[f64]) -> Color {
+    let Some(&latest) = durations.last() else {
+        return Color::DarkGray;
+    };
+
+    let mut sorted = durations.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    let median = if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    };
+
+    if latest > median * 1.25 {
+        Color::Red
+    } else if latest <= median * 1.10 {
+        Color::Green
+    } else {
+        Color::Yellow
+    }
 }
 
 impl<'a> HistoryTab<'a> {
     pub fn new(history: &'a [BuildHistoryEntry]) -> Self {
-        Self { history }
+        Self {
+            history,
+            marked: &[],
+            trend_builds: 30,
+            trend_include_failed: false,
+            query: None,
+        }
+    }
+
+    pub fn with_query(mut self, query: Option<&'a str>) -> Self {
+        self.query = query;
+        self
+    }
+
+    pub fn with_marked(mut self, marked: &'a [usize]) -> Self {
+        self.marked = marked;
+        self
+    }
+
+    pub fn with_trend(mut self, builds: usize, include_failed: bool) -> Self {
+        self.trend_builds = builds;
+        self.trend_include_failed = include_failed;
+        self
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(area);
+
+        self.render_trend(frame, chunks[0]);
+        let area = chunks[1];
+
+        let diff_pair = match self.marked {
+            [a, b] => self.history.get(*a.min(b)).zip(self.history.get(*a.max(b))),
+            _ => None,
+        };
+
+        if let Some((before, after)) = diff_pair {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(area);
+
+            self.render_table(frame, chunks[0], state);
+            self.render_diff(frame, chunks[1], before, after);
+        } else {
+            self.render_table(frame, area, state);
+        }
+    }
+
+    fn render_trend(&self, frame: &mut Frame, area: Rect) {
+        let durations = duration_trend(self.history, self.trend_builds, self.trend_include_failed);
+        let color = trend_color(&durations);
+
+        let scope = if self.trend_include_failed {
+            "all builds"
+        } else {
+            "successful builds"
+        };
+        let title = match durations.last() {
+            Some(latest) => format!(
+                " Duration Trend: last {} {} (latest {:.1}s) [f: Toggle failed] ",
+                durations.len(),
+                scope,
+                latest
+            ),
+            None => format!(" Duration Trend: no {} [f: Toggle failed] ", scope),
+        };
+
+        let data: Vec<u64> = durations.iter().map(|d| (d * 1000.0) as u64).collect();
+
+        let sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(
+                        ratatui::widgets::block::Title::from(title).alignment(Alignment::Center),
+                    ),
+            )
+            .data(&data)
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+
+        frame.render_widget(sparkline, area);
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    fn render_table(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
         let header_cells = [
+            "",
             "Timestamp",
             "Preset",
             "Duration",
+            "Kind",
             "Status",
-            "Errors",
-            "Warnings",
+            "Err",
+            "Warn",
+            "Commit",
+            "Branch",
         ]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
         let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-        let rows = self.history.iter().rev().take(20).map(|entry| {
+        let visible_rows = history_rows(self.history, self.query);
+
+        let rows = visible_rows.iter().map(|&idx| {
+            let entry = &self.history[idx];
             let status_color = if entry.success {
                 Color::Green
             } else {
                 Color::Red
             };
             let status_text = if entry.success { "✓ OK" } else { "✗ FAIL" };
+            let mark = match self.marked.iter().position(|&m| m == idx) {
+                Some(0) => "A",
+                Some(_) => "B",
+                None => "",
+            };
+            let note = if entry.note.is_some() { "*" } else { "" };
 
             Row::new(vec![
-                Cell::from(entry.timestamp.format("%Y-%m-%d %H:%M").to_string()),
+                Cell::from(format!("{}{}", mark, note)).style(Style::default().fg(Color::Magenta)),
+                Cell::from(entry.timestamp.format("%m-%d %H:%M").to_string()),
                 Cell::from(entry.preset.clone()),
                 Cell::from(format!("{:.1}s", entry.duration)),
+                Cell::from(entry.kind_label()).style(Style::default().fg(Color::DarkGray)),
                 Cell::from(status_text).style(Style::default().fg(status_color)),
                 Cell::from(entry.error_count.to_string()),
                 Cell::from(entry.warning_count.to_string()),
+                Cell::from(entry.short_commit().unwrap_or("-").to_string())
+                    .style(Style::default().fg(Color::Cyan)),
+                Cell::from(entry.git_branch.clone().unwrap_or_else(|| "-".to_string()))
+                    .style(Style::default().fg(Color::DarkGray)),
             ])
         });
 
         let widths = [
-            Constraint::Length(16),
-            Constraint::Length(12),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(10),
+            Constraint::Length(2),
+            Constraint::Length(11),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(8),
+            Constraint::Min(6),
+        ];
+
+        let title = match self.query {
+            Some(query) => format!(
+                " Build History ({}/{}) /{} [j/k: Select | d: Mark for diff | :nf: Clear] ",
+                visible_rows.len(),
+                self.history.len(),
+                query
+            ),
+            None => format!(
+                " Build History ({}) [j/k: Select | d: Mark for diff | /: Search] ",
+                self.history.len()
+            ),
+        };
+
+        let selected_note = state
+            .selected()
+            .and_then(|row| visible_rows.get(row))
+            .and_then(|&idx| self.history.get(idx))
+            .and_then(|entry| entry.note.as_deref());
+
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(ratatui::widgets::block::Title::from(title).alignment(Alignment::Center));
+
+        if let Some(note) = selected_note {
+            block = block.title(
+                ratatui::widgets::block::Title::from(Span::styled(
+                    format!(" * {} ", note),
+                    Style::default().fg(Color::Magenta),
+                ))
+                .position(ratatui::widgets::block::Position::Bottom)
+                .alignment(Alignment::Left),
+            );
+        }
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(table, area, state);
+    }
+
+    fn render_diff(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        before: &BuildHistoryEntry,
+        after: &BuildHistoryEntry,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)])
+            .split(area);
+
+        let lines = vec![
+            Self::entry_line("A", before),
+            Self::entry_line("B", after),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Duration: ", Style::default().fg(Color::Yellow)),
+                Self::duration_delta_span(Some(after.duration - before.duration)),
+                Span::styled("  │ ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Errors: ", Style::default().fg(Color::Yellow)),
+                Self::count_delta_span(before.error_count, after.error_count),
+                Span::styled("  │ ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Warnings: ", Style::default().fg(Color::Yellow)),
+                Self::count_delta_span(before.warning_count, after.warning_count),
+            ]),
         ];
 
-        let title = format!(" Build History ({}) ", self.history.len());
+        let summary = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(
+                    ratatui::widgets::block::Title::from(" Diff A → B ")
+                        .alignment(Alignment::Center),
+                ),
+        );
+
+        frame.render_widget(summary, chunks[0]);
+
+        let header_cells = ["Step", "A", "B", "Δ"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let format_duration = |d: Option<f64>| {
+            d.map(|d| format!("{:.2}s", d))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        let rows = diff_steps(before, after).into_iter().map(|diff| {
+            Row::new(vec![
+                Cell::from(diff.description.clone()),
+                Cell::from(format_duration(diff.before)),
+                Cell::from(format_duration(diff.after)),
+                Cell::from(Line::from(Self::duration_delta_span(diff.delta()))),
+            ])
+        });
+
+        let widths = [
+            Constraint::Percentage(40),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ];
 
         let table = Table::new(rows, widths).header(header).block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(ratatui::widgets::block::Title::from(title).alignment(Alignment::Center)),
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(
+                    ratatui::widgets::block::Title::from(" Step Durations ")
+                        .alignment(Alignment::Center),
+                ),
         );
 
-        frame.render_widget(table, area);
+        frame.render_widget(table, chunks[1]);
+    }
+
+    fn entry_line(label: &str, entry: &BuildHistoryEntry) -> Line<'static> {
+        let commit = entry.short_commit().unwrap_or("-").to_string();
+        let branch = entry.git_branch.clone().unwrap_or_else(|| "-".to_string());
+
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", label),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "{} | {} | {:.1}s | ",
+                entry.timestamp.format("%Y-%m-%d %H:%M"),
+                entry.preset,
+                entry.duration
+            )),
+            Span::styled(commit, Style::default().fg(Color::Cyan)),
+            Span::raw(" "),
+            Span::styled(format!("({})", branch), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                entry
+                    .note
+                    .as_ref()
+                    .map(|note| format!(" * {}", note))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::Magenta),
+            ),
+        ])
+    }
+
+    fn duration_delta_span(delta: Option<f64>) -> Span<'static> {
+        match delta {
+            Some(d) if d > 0.005 => {
+                Span::styled(format!("+{:.2}s", d), Style::default().fg(Color::Red))
+            }
+            Some(d) if d < -0.005 => {
+                Span::styled(format!("{:.2}s", d), Style::default().fg(Color::Green))
+            }
+            Some(_) => Span::styled("±0.00s", Style::default().fg(Color::DarkGray)),
+            None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+        }
+    }
+
+    fn count_delta_span(before: usize, after: usize) -> Span<'static> {
+        let text = format!(
+            "{} → {} ({:+})",
+            before,
+            after,
+            after as i64 - before as i64
+        );
+        let color = match after.cmp(&before) {
+            std::cmp::Ordering::Greater => Color::Red,
+            std::cmp::Ordering::Less => Color::Green,
+            std::cmp::Ordering::Equal => Color::DarkGray,
+        };
+        Span::styled(text, Style::default().fg(color))
     }
 
     pub fn render_exec_history(exec_history: &[ExecutionHistoryEntry], frame: &mut Frame, area: Rect) {
@@ -128,3 +462,38 @@ impl<'a> HistoryTab<'a> {
         frame.render_widget(table, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(duration: f64, success: bool) -> BuildHistoryEntry {
+        let mut entry = BuildHistoryEntry::new("debug".to_string());
+        entry.duration = duration;
+        entry.success = success;
+        entry
+    }
+
+    #[test]
+    fn test_duration_trend_skips_failed_builds() {
+        let history = vec![entry(10.0, true), entry(1.0, false), entry(12.0, true)];
+
+        assert_eq!(duration_trend(&history, 10, false), vec![10.0, 12.0]);
+        assert_eq!(duration_trend(&history, 10, true), vec![10.0, 1.0, 12.0]);
+    }
+
+    #[test]
+    fn test_duration_trend_keeps_latest_n_oldest_first() {
+        let history = vec![entry(1.0, true), entry(2.0, true), entry(3.0, true)];
+
+        assert_eq!(duration_trend(&history, 2, false), vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_trend_color() {
+        assert_eq!(trend_color(&[10.0, 10.0, 10.5]), Color::Green);
+        assert_eq!(trend_color(&[10.0, 10.0, 12.0]), Color::Yellow);
+        assert_eq!(trend_color(&[10.0, 10.0, 13.0]), Color::Red);
+        assert_eq!(trend_color(&[]), Color::DarkGray);
+    }
+}
@@ -21,9 +21,36 @@ pub enum TabId {
     Performance = 2,
     Warnings = 3,
     History = 4,
+    Errors = 5,
 }
 
 impl TabId {
+    pub const ALL: [TabId; 6] = [
+        TabId::Console,
+        TabId::Summary,
+        TabId::Performance,
+        TabId::Warnings,
+        TabId::History,
+        TabId::Errors,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TabId::Console => "Console",
+            TabId::Summary => "Summary",
+            TabId::Performance => "Performance",
+            TabId::Warnings => "Warnings",
+            TabId::History => "History",
+            TabId::Errors => "Errors",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|tab| tab.name().eq_ignore_ascii_case(name.trim()))
+    }
+
     pub fn from_index(index: usize) -> Option<Self> {
         match index {
             0 => Some(TabId::Console),
@@ -31,6 +58,7 @@ impl TabId {
             2 => Some(TabId::Performance),
             3 => Some(TabId::Warnings),
             4 => Some(TabId::History),
+            5 => Some(TabId::Errors),
             _ => None,
         }
     }
@@ -40,12 +68,140 @@ impl TabId {
     }
 
     pub fn next(&self) -> Self {
-        let next_index = (self.index() + 1) % 5;
-        Self::from_index(next_index).unwrap()
+        TabSet::default().next(*self)
     }
 
     pub fn prev(&self) -> Self {
-        let prev_index = if self.index() == 0 { 4 } else { self.index() - 1 };
-        Self::from_index(prev_index).unwrap()
+        TabSet::default().prev(*self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabSet {
+    tabs: Vec<TabId>,
+}
+
+impl TabSet {
+    pub fn from_names(names: &[String]) -> Result<Self, String> {
+        let mut tabs = Vec::new();
+
+        for name in names {
+            let tab = TabId::from_name(name).ok_or_else(|| {
+                let valid: Vec<String> = TabId::ALL
+                    .iter()
+                    .map(|t| t.name().to_lowercase())
+                    .collect();
+                format!("Unknown tab '{}' (valid: {})", name, valid.join(", "))
+            })?;
+
+            if tabs.contains(&tab) {
+                return Err(format!("Tab '{}' listed more than once", name));
+            }
+            tabs.push(tab);
+        }
+
+        if tabs.is_empty() {
+            return Err("display.tabs must list at least one tab".to_string());
+        }
+
+        Ok(Self { tabs })
+    }
+
+    pub fn tabs(&self) -> &[TabId] {
+        &self.tabs
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn first(&self) -> TabId {
+        self.tabs[0]
+    }
+
+    pub fn contains(&self, tab: TabId) -> bool {
+        self.tabs.contains(&tab)
+    }
+
+    pub fn from_index(&self, index: usize) -> Option<TabId> {
+        self.tabs.get(index).copied()
+    }
+
+    pub fn position(&self, tab: TabId) -> Option<usize> {
+        self.tabs.iter().position(|t| *t == tab)
+    }
+
+    pub fn next(&self, tab: TabId) -> TabId {
+        match self.position(tab) {
+            Some(index) => self.tabs[(index + 1) % self.tabs.len()],
+            None => self.first(),
+        }
+    }
+
+    pub fn prev(&self, tab: TabId) -> TabId {
+        match self.position(tab) {
+            Some(index) => self.tabs[(index + self.tabs.len() - 1) % self.tabs.len()],
+            None => self.first(),
+        }
+    }
+}
+
+impl Default for TabSet {
+    fn default() -> Self {
+        Self {
+            tabs: TabId::ALL.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_default_tab_set_matches_tab_ids() {
+        let set = TabSet::default();
+        assert_eq!(set.len(), 6);
+        for tab in TabId::ALL {
+            assert_eq!(set.from_index(tab.index()), Some(tab));
+        }
+        assert_eq!(TabId::History.next(), TabId::Errors);
+        assert_eq!(TabId::Errors.next(), TabId::Console);
+        assert_eq!(TabId::Console.prev(), TabId::Errors);
+
+        let configured = TabSet::from_names(&crate::Config::default().display.tabs).unwrap();
+        assert_eq!(configured.tabs(), &TabId::ALL);
+    }
+
+    #[test]
+    fn test_custom_tab_set_navigation() {
+        let set = TabSet::from_names(&names(&["console", "Summary", "warnings"])).unwrap();
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.from_index(0), Some(TabId::Console));
+        assert_eq!(set.from_index(2), Some(TabId::Warnings));
+        assert_eq!(set.from_index(3), None);
+
+        assert_eq!(set.next(TabId::Summary), TabId::Warnings);
+        assert_eq!(set.next(TabId::Warnings), TabId::Console);
+        assert_eq!(set.prev(TabId::Console), TabId::Warnings);
+        assert_eq!(set.prev(TabId::Warnings), TabId::Summary);
+        assert_eq!(set.next(TabId::Performance), TabId::Console);
+    }
+
+    #[test]
+    fn test_tab_set_validation() {
+        let err = TabSet::from_names(&names(&["console", "charts"])).unwrap_err();
+        assert!(err.contains("charts"));
+        assert!(TabSet::from_names(&names(&["console", "Console"])).is_err());
+        assert!(TabSet::from_names(&[]).is_err());
     }
 }
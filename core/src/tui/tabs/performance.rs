@@ -1,5 +1,6 @@
 use crate::history::BuildStepResult;
-use crate::monitor::ResourceStats;
+use crate::monitor::{ResourceAdvisory, ResourceSample, ResourceStats};
+use crate::tui::progress;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
@@ -11,11 +12,201 @@ use ratatui::{
     Frame,
 };
 
+const TOP_PROCESSES: usize = 3;
+const TIMELINE_MAX_ROWS: usize = 10;
+const TIMELINE_LABEL_WIDTH: usize = 16;
+const MAX_ZOOM: u32 = 8;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResourceChart {
+    #[default]
+    Memory,
+    Threads,
+    DiskIo,
+}
+
+impl ResourceChart {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Memory => Self::Threads,
+            Self::Threads => Self::DiskIo,
+            Self::DiskIo => Self::Memory,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Threads => "threads",
+            Self::DiskIo => "disk I/O",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChartViewport {
+    zoom: u32,
+    pan: f64,
+}
+
+impl ChartViewport {
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom + 1).min(MAX_ZOOM);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = self.zoom.saturating_sub(1);
+        if self.zoom == 0 {
+            self.pan = 0.0;
+        }
+    }
+
+    pub fn pan(&mut self, forward: bool, span: f64) {
+        let (start, end) = self.window(span);
+        let width = end - start;
+        let end = if forward {
+            end + width / 4.0
+        } else {
+            end - width / 4.0
+        };
+        self.pan = (span - end).clamp(0.0, span - width);
+    }
+
+    pub fn window(&self, span: f64) -> (f64, f64) {
+        let width = span / f64::from(1u32 << self.zoom);
+        let end = (span - self.pan).clamp(width, span);
+        (end - width, end)
+    }
+
+    pub fn label(&self) -> String {
+        match (self.zoom, self.pan > 0.0) {
+            (0, _) => String::new(),
+            (zoom, false) => format!(" · {}x", 1u32 << zoom),
+            (zoom, true) => format!(" · {}x -{:.0}s", 1u32 << zoom, self.pan),
+        }
+    }
+}
+
 pub struct PerformanceTab<'a> {
     steps: &'a [BuildStepResult],
     total_duration: f64,
     build_complete: bool,
     resource_stats: ResourceStats,
+    slowest_steps: Option<usize>,
+    advisories: Vec<ResourceAdvisory>,
+    viewport: ChartViewport,
+    bottom_chart: ResourceChart,
+}
+
+pub fn top_slowest_steps(steps: &[BuildStepResult], n: usize) -> (Vec<&BuildStepResult>, usize) {
+    let mut sorted: Vec<&BuildStepResult> = steps.iter().collect();
+    sorted.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+
+    let rest = sorted.len().saturating_sub(n);
+    sorted.truncate(n);
+    (sorted, rest)
+}
+
+pub fn step_label(step: &BuildStepResult) -> String {
+    step.target
+        .clone()
+        .unwrap_or_else(|| step.description.clone())
+}
+
+pub fn timeline_bar(start: f64, duration: f64, span: f64, width: usize) -> String {
+    let lead = progress::filled_width(progress::ratio(start, span), width).min(width - 1);
+    let length =
+        progress::filled_width(progress::ratio(duration, span), width).clamp(1, width - lead);
+    format!("{}{}", " ".repeat(lead), progress::FILLED.repeat(length))
+}
+
+pub fn disk_throughput(
+    samples: &[ResourceSample],
+    bytes: impl Fn(&ResourceSample) -> u64,
+) -> Vec<(f64, f64)> {
+    let Some(first) = samples.first() else {
+        return Vec::new();
+    };
+
+    samples
+        .windows(2)
+        .filter_map(|pair| {
+            let interval = pair[1].timestamp - pair[0].timestamp;
+            if interval <= 0.0 {
+                return None;
+            }
+            let delta = bytes(&pair[1]).saturating_sub(bytes(&pair[0])) as f64;
+            Some((
+                pair[1].timestamp - first.timestamp,
+                delta / 1024.0 / 1024.0 / interval,
+            ))
+        })
+        .collect()
+}
+
+fn time_labels(start: f64, end: f64) -> Vec<Span<'static>> {
+    vec![
+        Span::raw(format!("{:.1}s", start)),
+        Span::raw(format!("{:.1}s", (start + end) / 2.0)),
+        Span::raw(format!("{:.1}s", end)),
+    ]
+}
+
+const STEP_TYPES: [&str; 4] = ["Configure", "Build", "Install", "Other"];
+
+fn step_type(description: &str) -> &'static str {
+    if description.starts_with("Configuring") || description.contains("CMake") {
+        "Configure"
+    } else if description.starts_with("Building") {
+        "Build"
+    } else if description.starts_with("Installing") {
+        "Install"
+    } else {
+        "Other"
+    }
+}
+
+/// Groups steps by type, keeping each target's steps together within a group.
+pub fn group_steps(steps: &[BuildStepResult]) -> Vec<(&'static str, Vec<&BuildStepResult>)> {
+    STEP_TYPES
+        .iter()
+        .filter_map(|&kind| {
+            let mut group: Vec<&BuildStepResult> = steps
+                .iter()
+                .filter(|step| step_type(&step.description) == kind)
+                .collect();
+            group.sort_by(|a, b| match (&a.target, &b.target) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            });
+            (!group.is_empty()).then_some((kind, group))
+        })
+        .collect()
+}
+
+pub fn y_axis_bounds(data: &[(f64, f64)], flat_padding: f64) -> (f64, f64) {
+    if data.is_empty() {
+        return (0.0, 1.0);
+    }
+
+    let y_min = data
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(f64::INFINITY, f64::min)
+        .max(0.0);
+    let y_max = data
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(0.0f64, f64::max)
+        .max(10.0);
+
+    let y_range = y_max - y_min;
+    let y_padding = if y_range < 1.0 {
+        flat_padding
+    } else {
+        y_range * 0.2
+    };
+    ((y_min - y_padding).max(0.0), y_max + y_padding)
 }
 
 impl<'a> PerformanceTab<'a> {
@@ -30,9 +221,33 @@ impl<'a> PerformanceTab<'a> {
             total_duration,
             build_complete,
             resource_stats,
+            slowest_steps: None,
+            advisories: Vec::new(),
+            viewport: ChartViewport::default(),
+            bottom_chart: ResourceChart::default(),
         }
     }
 
+    pub fn with_advisories(mut self, advisories: Vec<ResourceAdvisory>) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: ChartViewport) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn with_bottom_chart(mut self, bottom_chart: ResourceChart) -> Self {
+        self.bottom_chart = bottom_chart;
+        self
+    }
+
+    pub fn with_slowest_steps(mut self, slowest_steps: Option<usize>) -> Self {
+        self.slowest_steps = slowest_steps;
+        self
+    }
+
     pub fn new_runtime(
         total_duration: f64,
         exec_complete: bool,
@@ -43,6 +258,10 @@ impl<'a> PerformanceTab<'a> {
             total_duration,
             build_complete: exec_complete,
             resource_stats,
+            slowest_steps: None,
+            advisories: Vec::new(),
+            viewport: ChartViewport::default(),
+            bottom_chart: ResourceChart::default(),
         }
     }
 
@@ -50,19 +269,23 @@ impl<'a> PerformanceTab<'a> {
         if self.steps.is_empty() {
             self.render_resource_usage(frame, area);
         } else {
+            let timeline_height = self.steps.len().min(TIMELINE_MAX_ROWS) as u16 + 2;
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .constraints([
+                    Constraint::Percentage(35),
+                    Constraint::Length(timeline_height),
+                    Constraint::Min(0),
+                ])
                 .split(area);
 
             self.render_build_metrics(frame, chunks[0]);
-            self.render_resource_usage(frame, chunks[1]);
+            self.render_timeline(frame, chunks[1]);
+            self.render_resource_usage(frame, chunks[2]);
         }
     }
 
     fn render_build_metrics(&self, frame: &mut Frame, area: Rect) {
-        use std::collections::HashMap;
-
         let steps_total: f64 = self.steps.iter().map(|s| s.duration).sum();
         let total_errors: usize = self.steps.iter().map(|s| s.error_count).sum();
         let total_warnings: usize = self.steps.iter().map(|s| s.warning_count).sum();
@@ -77,22 +300,7 @@ impl<'a> PerformanceTab<'a> {
         let overhead = self.total_duration - steps_total;
         let mode_indicator = if self.build_complete { "" } else { " (Live)" };
 
-        let mut grouped_steps: HashMap<String, Vec<&BuildStepResult>> = HashMap::new();
-        for step in self.steps.iter() {
-            let step_type = self.extract_step_type(&step.description);
-            std::collections::hash_map::Entry::or_insert_with(
-                grouped_steps.entry(step_type),
-                Vec::new,
-            )
-            .push(step);
-        }
-
-        let step_order = ["Configure", "Build", "Install", "Other"];
-        let step_types: Vec<String> = step_order
-            .iter()
-            .filter(|&st| grouped_steps.contains_key(*st))
-            .map(|s| s.to_string())
-            .collect();
+        let grouped_steps = group_steps(self.steps);
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -173,7 +381,12 @@ impl<'a> PerformanceTab<'a> {
 
         frame.render_widget(summary, chunks[0]);
 
-        let step_count = step_types.len().max(1);
+        if let Some(n) = self.slowest_steps {
+            self.render_slowest_steps_panel(frame, chunks[1], n);
+            return;
+        }
+
+        let step_count = grouped_steps.len().max(1);
         let step_constraints: Vec<Constraint> = (0..step_count)
             .map(|_| Constraint::Ratio(1, step_count as u32))
             .collect();
@@ -183,22 +396,57 @@ impl<'a> PerformanceTab<'a> {
             .constraints(step_constraints)
             .split(chunks[1]);
 
-        for (idx, step_type) in step_types.iter().enumerate() {
-            let steps = grouped_steps.get(step_type).unwrap();
+        for (idx, (step_type, steps)) in grouped_steps.iter().enumerate() {
             self.render_step_type_panel(frame, step_chunks[idx], step_type, steps);
         }
     }
 
-    fn extract_step_type(&self, description: &str) -> String {
-        if description.starts_with("Configuring") || description.contains("CMake") {
-            "Configure".to_string()
-        } else if description.starts_with("Building") {
-            "Build".to_string()
-        } else if description.starts_with("Installing") {
-            "Install".to_string()
-        } else {
-            "Other".to_string()
-        }
+    fn render_timeline(&self, frame: &mut Frame, area: Rect) {
+        let span = self
+            .steps
+            .iter()
+            .map(|step| step.start_offset + step.duration)
+            .fold(self.total_duration, f64::max);
+        let bar_width = (area.width as usize)
+            .saturating_sub(TIMELINE_LABEL_WIDTH + 3)
+            .max(1);
+        let skipped = self.steps.len().saturating_sub(TIMELINE_MAX_ROWS);
+
+        let lines: Vec<Line> = self.steps[skipped..]
+            .iter()
+            .map(|step| {
+                let mut label = step_label(step);
+                label.truncate(TIMELINE_LABEL_WIDTH);
+                let color = if !step.success {
+                    Color::Red
+                } else if step.warning_count > 0 {
+                    Color::Yellow
+                } else {
+                    Color::Blue
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$} ", label, width = TIMELINE_LABEL_WIDTH),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        timeline_bar(step.start_offset, step.duration, span, bar_width),
+                        Style::default().fg(color),
+                    ),
+                ])
+            })
+            .collect();
+
+        let title = format!(" Timeline (0–{:.1}s) ", span);
+        let timeline = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(Title::from(title).alignment(Alignment::Center)),
+        );
+
+        frame.render_widget(timeline, area);
     }
 
     fn render_step_type_panel(
@@ -214,12 +462,12 @@ impl<'a> PerformanceTab<'a> {
         let mut lines = vec![];
 
         for step in steps.iter() {
-            let project_name = self.extract_project_name(&step.description);
+            let project_name = step_label(step);
 
             let bar = if max_duration > 0.0 {
-                self.render_progress_bar(step.duration, max_duration, bar_width)
+                progress::value_bar(step.duration, max_duration, bar_width)
             } else {
-                self.render_progress_bar(0.0, 1.0, bar_width)
+                progress::value_bar(0.0, 1.0, bar_width)
             };
 
             let status_icon = if step.success { "✓" } else { "✗" };
@@ -297,28 +545,83 @@ impl<'a> PerformanceTab<'a> {
         frame.render_widget(paragraph, area);
     }
 
-    fn extract_project_name(&self, description: &str) -> String {
-        if description.starts_with("Configuring ") {
-            description
-                .strip_prefix("Configuring ")
-                .unwrap_or(description)
-                .to_string()
-        } else if description.starts_with("Building ") {
-            description
-                .strip_prefix("Building ")
-                .unwrap_or(description)
-                .to_string()
-        } else if description.starts_with("Installing ") {
-            description
-                .strip_prefix("Installing ")
-                .unwrap_or(description)
-                .to_string()
-        } else {
-            description.to_string()
+    fn render_slowest_steps_panel(&self, frame: &mut Frame, area: Rect, n: usize) {
+        let (slowest, rest) = top_slowest_steps(self.steps, n);
+        let max_duration = slowest.first().map(|s| s.duration).unwrap_or(0.0);
+        let bar_width = (area.width.saturating_sub(50)).min(40) as usize;
+
+        let mut lines = vec![];
+
+        for step in &slowest {
+            let bar = if max_duration > 0.0 {
+                progress::value_bar(step.duration, max_duration, bar_width)
+            } else {
+                progress::value_bar(0.0, 1.0, bar_width)
+            };
+
+            let status_icon = if step.success { "✓" } else { "✗" };
+            let status_color = if step.success {
+                Color::Green
+            } else {
+                Color::Red
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(status_icon, Style::default().fg(status_color)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>8.2}s ", step.duration),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(bar, Style::default().fg(Color::Magenta)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("[{}] ", step_type(&step.description)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(step_label(step), Style::default().fg(Color::White)),
+            ]));
         }
+
+        if rest > 0 {
+            let rest_duration: f64 = self.steps.iter().map(|s| s.duration).sum::<f64>()
+                - slowest.iter().map(|s| s.duration).sum::<f64>();
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  {} faster steps ({:.2}s)", rest, rest_duration),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        let title = format!(" Slowest {} Steps [s: Group by type] ", slowest.len());
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(Title::from(title).alignment(Alignment::Center)),
+        );
+
+        frame.render_widget(paragraph, area);
     }
 
     fn render_resource_usage(&self, frame: &mut Frame, area: Rect) {
+        let area = if self.advisories.is_empty() {
+            area
+        } else {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(self.advisories.len() as u16 + 2),
+                    Constraint::Min(0),
+                ])
+                .split(area);
+
+            self.render_advisories(frame, chunks[0]);
+            chunks[1]
+        };
+
         if !self.resource_stats.samples.is_empty() {
             let main_chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -329,13 +632,40 @@ impl<'a> PerformanceTab<'a> {
                 ])
                 .split(area);
 
-            let horizontal_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(main_chunks[0]);
+            let horizontal_chunks = if self.resource_stats.processes.is_empty() {
+                Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(main_chunks[0])
+            } else {
+                let chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Percentage(35),
+                        Constraint::Percentage(35),
+                        Constraint::Percentage(30),
+                    ])
+                    .split(main_chunks[0]);
+                self.render_top_processes(frame, chunks[2]);
+                chunks
+            };
+
+            if self.resource_stats.gpu_available {
+                let cpu_gpu_chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(main_chunks[1]);
 
-            self.render_cpu_chart(frame, main_chunks[1]);
-            self.render_memory_chart(frame, main_chunks[2]);
+                self.render_cpu_chart(frame, cpu_gpu_chunks[0]);
+                self.render_gpu_chart(frame, cpu_gpu_chunks[1]);
+            } else {
+                self.render_cpu_chart(frame, main_chunks[1]);
+            }
+            match self.bottom_chart {
+                ResourceChart::Memory => self.render_memory_chart(frame, main_chunks[2]),
+                ResourceChart::Threads => self.render_threads_chart(frame, main_chunks[2]),
+                ResourceChart::DiskIo => self.render_disk_chart(frame, main_chunks[2]),
+            }
 
             self.render_left_metrics(frame, horizontal_chunks[0]);
             self.render_right_metrics(frame, horizontal_chunks[1]);
@@ -350,15 +680,36 @@ impl<'a> PerformanceTab<'a> {
         }
     }
 
+    fn render_advisories(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .advisories
+            .iter()
+            .map(|advisory| {
+                Line::from(vec![
+                    Span::styled(" ⚠ ", Style::default().fg(Color::Yellow)),
+                    Span::raw(advisory.to_string()),
+                ])
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(Title::from(" Advisories ").alignment(Alignment::Center)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
     fn render_left_metrics(&self, frame: &mut Frame, area: Rect) {
         let bar_width = (area.width.saturating_sub(10)).min(30) as usize;
 
-        let cpu_bar =
-            self.render_progress_bar(self.resource_stats.peak_cpu as f64, 100.0, bar_width);
-        let mem_bar =
-            self.render_progress_bar(self.resource_stats.peak_memory_mb, 2000.0, bar_width);
+        let cpu_bar = progress::value_bar(self.resource_stats.peak_cpu as f64, 100.0, bar_width);
+        let mem_bar = progress::value_bar(self.resource_stats.peak_memory_mb, 2000.0, bar_width);
         let thread_bar =
-            self.render_progress_bar(self.resource_stats.peak_threads as f64, 64.0, bar_width);
+            progress::value_bar(self.resource_stats.peak_threads as f64, 64.0, bar_width);
 
         let lines = vec![
             Line::from(vec![
@@ -476,8 +827,8 @@ impl<'a> PerformanceTab<'a> {
         } else {
             0.5
         };
-        let disk_bar = self.render_dual_progress_bar(read_ratio, bar_width);
-        let load_bar = self.render_progress_bar(self.resource_stats.load_avg_1min, 8.0, bar_width);
+        let disk_bar = progress::dual_bar(read_ratio, bar_width);
+        let load_bar = progress::value_bar(self.resource_stats.load_avg_1min, 8.0, bar_width);
 
         let mut lines = vec![
             Line::from(vec![
@@ -575,18 +926,72 @@ impl<'a> PerformanceTab<'a> {
         frame.render_widget(paragraph, area);
     }
 
+    fn render_top_processes(&self, frame: &mut Frame, area: Rect) {
+        let top = self.resource_stats.top_processes_by_memory(TOP_PROCESSES);
+        let max_memory = top.first().map(|(_, p)| p.peak_memory_mb).unwrap_or(0.0);
+        let bar_width = (area.width.saturating_sub(4)).min(30) as usize;
+
+        let mut lines = vec![];
+        for (pid, process) in &top {
+            lines.push(Line::from(vec![
+                Span::styled(process.name.clone(), Style::default().fg(Color::White)),
+                Span::styled(format!(" ({})", pid), Style::default().fg(Color::DarkGray)),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:.1} MB", process.memory_mb),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("  peak {:.1} MB", process.peak_memory_mb),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+            lines.push(Line::from(Span::styled(
+                progress::value_bar(process.peak_memory_mb, max_memory.max(1.0), bar_width),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(Title::from(" Top Processes (Memory) ").alignment(Alignment::Center)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn windowed(&self, points: impl Iterator<Item = (f64, f64)>) -> (Vec<(f64, f64)>, f64, f64) {
+        let samples = &self.resource_stats.samples;
+        let span = samples
+            .last()
+            .map_or(0.0, |s| s.timestamp - samples[0].timestamp);
+        let (x_min, x_max) = self.viewport.window(span);
+
+        let data = points
+            .filter(|(x, _)| (x_min..=x_max).contains(x))
+            .collect();
+        (data, x_min, x_max)
+    }
+
+    fn windowed_series(
+        &self,
+        value: impl Fn(&ResourceSample) -> f64,
+    ) -> (Vec<(f64, f64)>, f64, f64) {
+        let samples = &self.resource_stats.samples;
+        let start_time = samples[0].timestamp;
+        self.windowed(samples.iter().map(|s| (s.timestamp - start_time, value(s))))
+    }
+
     fn render_cpu_chart(&self, frame: &mut Frame, area: Rect) {
         if self.resource_stats.samples.is_empty() {
             return;
         }
 
-        let start_time = self.resource_stats.samples[0].timestamp;
-        let cpu_data: Vec<(f64, f64)> = self
-            .resource_stats
-            .samples
-            .iter()
-            .map(|s| (s.timestamp - start_time, s.cpu_usage as f64))
-            .collect();
+        let (cpu_data, x_min, x_max) = self.windowed_series(|s| s.cpu_usage as f64);
 
         let datasets = vec![Dataset::default()
             .name("CPU %")
@@ -595,29 +1000,8 @@ impl<'a> PerformanceTab<'a> {
             .style(Style::default().fg(Color::Green))
             .data(&cpu_data)];
 
-        let x_max = cpu_data.last().map(|(x, _)| *x).unwrap_or(0.0);
-
-        let y_min = cpu_data
-            .iter()
-            .map(|(_, y)| *y)
-            .fold(f64::INFINITY, f64::min)
-            .max(0.0);
-        let y_max = cpu_data
-            .iter()
-            .map(|(_, y)| *y)
-            .fold(0.0f64, f64::max)
-            .max(10.0);
-
-        let y_range = y_max - y_min;
-        let y_padding = if y_range < 1.0 { 5.0 } else { y_range * 0.2 };
-        let y_min_bound = (y_min - y_padding).max(0.0);
-        let y_max_bound = y_max + y_padding;
-
-        let x_labels = vec![
-            Span::raw("0s"),
-            Span::raw(format!("{:.1}s", x_max / 2.0)),
-            Span::raw(format!("{:.1}s", x_max)),
-        ];
+        let (y_min_bound, y_max_bound) = y_axis_bounds(&cpu_data, 5.0);
+        let title = format!(" CPU Usage (%){} [←/→ +/-] ", self.viewport.label());
 
         let y_labels = vec![
             Span::raw(format!("{:.1}", y_min_bound)),
@@ -631,14 +1015,14 @@ impl<'a> PerformanceTab<'a> {
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
                     .border_style(Style::default().fg(Color::Cyan))
-                    .title(Title::from(" CPU Usage (%) ").alignment(Alignment::Center)),
+                    .title(Title::from(title).alignment(Alignment::Center)),
             )
             .x_axis(
                 Axis::default()
                     .title("Time")
                     .style(Style::default().fg(Color::Gray))
-                    .bounds([0.0, x_max])
-                    .labels(x_labels),
+                    .bounds([x_min, x_max])
+                    .labels(time_labels(x_min, x_max)),
             )
             .y_axis(
                 Axis::default()
@@ -651,18 +1035,59 @@ impl<'a> PerformanceTab<'a> {
         frame.render_widget(chart, area);
     }
 
+    fn render_gpu_chart(&self, frame: &mut Frame, area: Rect) {
+        if self.resource_stats.samples.is_empty() {
+            return;
+        }
+
+        let (gpu_data, x_min, x_max) = self.windowed_series(|s| s.gpu_usage as f64);
+
+        let datasets = vec![Dataset::default()
+            .name("GPU %")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&gpu_data)];
+
+        let y_labels = vec![Span::raw("0"), Span::raw("50"), Span::raw("100")];
+
+        let title = format!(
+            " GPU Usage (%) · VRAM {:.0}MB (peak {:.0}MB) ",
+            self.resource_stats.vram_mb, self.resource_stats.peak_vram_mb
+        );
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(title).alignment(Alignment::Center)),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("Time")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([x_min, x_max])
+                    .labels(time_labels(x_min, x_max)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("%")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 100.0])
+                    .labels(y_labels),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
     fn render_memory_chart(&self, frame: &mut Frame, area: Rect) {
         if self.resource_stats.samples.is_empty() {
             return;
         }
 
-        let start_time = self.resource_stats.samples[0].timestamp;
-        let mem_data: Vec<(f64, f64)> = self
-            .resource_stats
-            .samples
-            .iter()
-            .map(|s| (s.timestamp - start_time, s.memory_mb))
-            .collect();
+        let (mem_data, x_min, x_max) = self.windowed_series(|s| s.memory_mb);
 
         let datasets = vec![Dataset::default()
             .name("Memory MB")
@@ -671,30 +1096,66 @@ impl<'a> PerformanceTab<'a> {
             .style(Style::default().fg(Color::Yellow))
             .data(&mem_data)];
 
-        let x_max = mem_data.last().map(|(x, _)| *x).unwrap_or(0.0);
+        let (y_min_bound, y_max_bound) = y_axis_bounds(&mem_data, 10.0);
+        let title = format!(
+            " Memory Usage (MB){} [T: {}] ",
+            self.viewport.label(),
+            self.bottom_chart.next().label()
+        );
 
-        let y_min = mem_data
-            .iter()
-            .map(|(_, y)| *y)
-            .fold(f64::INFINITY, f64::min)
-            .max(0.0);
-        let y_max = mem_data
-            .iter()
-            .map(|(_, y)| *y)
-            .fold(0.0f64, f64::max)
-            .max(10.0);
-
-        let y_range = y_max - y_min;
-        let y_padding = if y_range < 1.0 { 10.0 } else { y_range * 0.2 };
-        let y_min_bound = (y_min - y_padding).max(0.0);
-        let y_max_bound = y_max + y_padding;
-
-        let x_labels = vec![
-            Span::raw("0s"),
-            Span::raw(format!("{:.1}s", x_max / 2.0)),
-            Span::raw(format!("{:.1}s", x_max)),
+        let y_labels = vec![
+            Span::raw(format!("{:.0}", y_min_bound)),
+            Span::raw(format!("{:.0}", (y_min_bound + y_max_bound) / 2.0)),
+            Span::raw(format!("{:.0}", y_max_bound)),
         ];
 
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(title).alignment(Alignment::Center)),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("Time")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([x_min, x_max])
+                    .labels(time_labels(x_min, x_max)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("MB")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([y_min_bound, y_max_bound])
+                    .labels(y_labels),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
+    fn render_threads_chart(&self, frame: &mut Frame, area: Rect) {
+        if self.resource_stats.samples.is_empty() {
+            return;
+        }
+
+        let (thread_data, x_min, x_max) = self.windowed_series(|s| s.thread_count as f64);
+
+        let datasets = vec![Dataset::default()
+            .name("Threads")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&thread_data)];
+
+        let (y_min_bound, y_max_bound) = y_axis_bounds(&thread_data, 2.0);
+        let title = format!(
+            " Thread Count{} [T: {}] ",
+            self.viewport.label(),
+            self.bottom_chart.next().label()
+        );
+
         let y_labels = vec![
             Span::raw(format!("{:.0}", y_min_bound)),
             Span::raw(format!("{:.0}", (y_min_bound + y_max_bound) / 2.0)),
@@ -707,18 +1168,18 @@ impl<'a> PerformanceTab<'a> {
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
                     .border_style(Style::default().fg(Color::Cyan))
-                    .title(Title::from(" Memory Usage (MB) ").alignment(Alignment::Center)),
+                    .title(Title::from(title).alignment(Alignment::Center)),
             )
             .x_axis(
                 Axis::default()
                     .title("Time")
                     .style(Style::default().fg(Color::Gray))
-                    .bounds([0.0, x_max])
-                    .labels(x_labels),
+                    .bounds([x_min, x_max])
+                    .labels(time_labels(x_min, x_max)),
             )
             .y_axis(
                 Axis::default()
-                    .title("MB")
+                    .title("Threads")
                     .style(Style::default().fg(Color::Gray))
                     .bounds([y_min_bound, y_max_bound])
                     .labels(y_labels),
@@ -727,18 +1188,234 @@ impl<'a> PerformanceTab<'a> {
         frame.render_widget(chart, area);
     }
 
-    fn render_progress_bar(&self, value: f64, max: f64, width: usize) -> String {
-        let percentage = (value / max).min(1.0);
-        let filled = (percentage * width as f64) as usize;
-        let empty = width.saturating_sub(filled);
+    fn render_disk_chart(&self, frame: &mut Frame, area: Rect) {
+        if self.resource_stats.samples.is_empty() {
+            return;
+        }
+
+        let samples = &self.resource_stats.samples;
+        let (read_data, x_min, x_max) =
+            self.windowed(disk_throughput(samples, |s| s.disk_read_bytes).into_iter());
+        let (write_data, _, _) =
+            self.windowed(disk_throughput(samples, |s| s.disk_write_bytes).into_iter());
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Read MB/s")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Blue))
+                .data(&read_data),
+            Dataset::default()
+                .name("Write MB/s")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Magenta))
+                .data(&write_data),
+        ];
+
+        let all_points: Vec<(f64, f64)> = read_data.iter().chain(&write_data).copied().collect();
+        let (y_min_bound, y_max_bound) = y_axis_bounds(&all_points, 1.0);
+        let title = format!(
+            " Disk I/O (MB/s){} [T: {}] ",
+            self.viewport.label(),
+            self.bottom_chart.next().label()
+        );
 
-        format!("{}{}", "█".repeat(filled), "░".repeat(empty))
+        let y_labels = vec![
+            Span::raw(format!("{:.1}", y_min_bound)),
+            Span::raw(format!("{:.1}", (y_min_bound + y_max_bound) / 2.0)),
+            Span::raw(format!("{:.1}", y_max_bound)),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(title).alignment(Alignment::Center)),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("Time")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([x_min, x_max])
+                    .labels(time_labels(x_min, x_max)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("MB/s")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([y_min_bound, y_max_bound])
+                    .labels(y_labels),
+            );
+
+        frame.render_widget(chart, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(description: &str, duration: f64) -> BuildStepResult {
+        BuildStepResult {
+            description: description.to_string(),
+            duration,
+            success: true,
+            error_count: 0,
+            warning_count: 0,
+            commands: Vec::new(),
+            target: None,
+            start_offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_y_axis_bounds_pads_range() {
+        let data = [(0.0, 100.0), (1.0, 200.0), (2.0, 150.0)];
+        assert_eq!(y_axis_bounds(&data, 5.0), (80.0, 220.0));
+
+        let flat = [(0.0, 50.0), (1.0, 50.0)];
+        assert_eq!(y_axis_bounds(&flat, 5.0), (45.0, 55.0));
+
+        assert_eq!(y_axis_bounds(&[(0.0, 0.0)], 5.0), (0.0, 12.0));
+        assert_eq!(y_axis_bounds(&[], 5.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_top_slowest_steps_sorted_descending() {
+        let steps = vec![
+            step("Configuring CMake", 2.0),
+            step("Building engine", 30.0),
+            step("Building editor", 12.0),
+            step("Installing artifacts", 1.0),
+        ];
+
+        let (slowest, rest) = top_slowest_steps(&steps, 2);
+        let descriptions: Vec<&str> = slowest.iter().map(|s| s.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["Building engine", "Building editor"]);
+        assert_eq!(rest, 2);
+    }
+
+    #[test]
+    fn test_top_slowest_steps_n_larger_than_set() {
+        let steps = vec![step("Building engine", 3.0), step("Configuring CMake", 5.0)];
+
+        let (slowest, rest) = top_slowest_steps(&steps, 10);
+        assert_eq!(slowest.len(), 2);
+        assert_eq!(slowest[0].description, "Configuring CMake");
+        assert_eq!(rest, 0);
+    }
+
+    #[test]
+    fn test_step_label_prefers_target() {
+        let mut build = step("Building project", 1.0);
+        assert_eq!(step_label(&build), "Building project");
+
+        build.target = Some("editor".to_string());
+        assert_eq!(step_label(&build), "editor");
+    }
+
+    #[test]
+    fn test_group_steps_by_type_then_target() {
+        let targeted = |description: &str, target: &str| BuildStepResult {
+            target: Some(target.to_string()),
+            ..step(description, 1.0)
+        };
+        let steps = [
+            targeted("Building project", "game"),
+            step("Running tests", 1.0),
+            targeted("Configuring project", "editor"),
+            targeted("Building project", "editor"),
+            step("Building project", 1.0),
+        ];
+
+        let groups: Vec<(&str, Vec<Option<&str>>)> = group_steps(&steps)
+            .into_iter()
+            .map(|(kind, group)| {
+                let targets = group.iter().map(|s| s.target.as_deref()).collect();
+                (kind, targets)
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("Configure", vec![Some("editor")]),
+                ("Build", vec![Some("editor"), Some("game"), None]),
+                ("Other", vec![None]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timeline_bar_positions_by_offset() {
+        assert_eq!(timeline_bar(0.0, 5.0, 10.0, 10), "█████");
+        assert_eq!(timeline_bar(5.0, 5.0, 10.0, 10), "     █████");
+        assert_eq!(timeline_bar(10.0, 0.0, 10.0, 10), "         █");
+        assert_eq!(timeline_bar(2.0, 0.01, 10.0, 10), "  █");
+        assert_eq!(timeline_bar(0.0, 0.0, 0.0, 4), "█");
     }
 
-    fn render_dual_progress_bar(&self, ratio: f64, width: usize) -> (String, String) {
-        let first_width = (ratio * width as f64) as usize;
-        let second_width = width.saturating_sub(first_width);
+    #[test]
+    fn test_chart_viewport_pans_within_zoomed_window() {
+        let mut viewport = ChartViewport::default();
+        assert_eq!(viewport.window(100.0), (0.0, 100.0));
+        viewport.pan(false, 100.0);
+        assert_eq!(viewport.window(100.0), (0.0, 100.0));
+
+        viewport.zoom_in();
+        assert_eq!(viewport.window(100.0), (50.0, 100.0));
+        viewport.pan(false, 100.0);
+        assert_eq!(viewport.window(100.0), (37.5, 87.5));
+        assert_eq!(viewport.label(), " · 2x -12s");
+
+        for _ in 0..10 {
+            viewport.pan(false, 100.0);
+        }
+        assert_eq!(viewport.window(100.0), (0.0, 50.0));
+        viewport.pan(true, 100.0);
+        assert_eq!(viewport.window(100.0), (12.5, 62.5));
+
+        viewport.zoom_out();
+        assert_eq!(viewport.window(100.0), (0.0, 100.0));
+        assert_eq!(viewport.label(), "");
+    }
+
+    #[test]
+    fn test_disk_throughput_from_sample_deltas() {
+        const MB: u64 = 1024 * 1024;
+        let sample = |timestamp: f64, read: u64| ResourceSample {
+            timestamp,
+            cpu_usage: 0.0,
+            memory_mb: 0.0,
+            thread_count: 1,
+            disk_read_bytes: read,
+            disk_write_bytes: 0,
+            gpu_usage: 0.0,
+            vram_mb: 0.0,
+        };
+
+        assert!(disk_throughput(&[sample(1.0, MB)], |s| s.disk_read_bytes).is_empty());
+
+        let samples = [
+            sample(1.0, 0),
+            sample(1.5, 2 * MB),
+            sample(2.5, 6 * MB),
+            sample(2.5, 8 * MB),
+            sample(3.5, MB),
+        ];
+        assert_eq!(
+            disk_throughput(&samples, |s| s.disk_read_bytes),
+            vec![(0.5, 4.0), (1.5, 4.0), (2.5, 0.0)]
+        );
+    }
 
-        ("█".repeat(first_width), "█".repeat(second_width))
+    #[test]
+    fn test_top_slowest_steps_empty() {
+        let (slowest, rest) = top_slowest_steps(&[], 5);
+        assert!(slowest.is_empty());
+        assert_eq!(rest, 0);
     }
 }
@@ -1,32 +1,146 @@
-use crate::executor::{MetricHistory, MetricType, MetricVisualization};
+use crate::executor::{tag_label, MetricHistory, MetricType, MetricVisualization, SkippedStep};
 use crate::history::BuildStepResult;
+use crate::tui::progress;
+use crate::tui::tabs::performance::y_axis_bounds;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
+    text::Span,
     widgets::{
-        block::Title, Block, BorderType, Borders, Cell, Gauge, Paragraph, Row,
-        Sparkline, Table,
+        block::Title, Axis, BarChart, Block, BorderType, Borders, Cell, Chart, Dataset, Gauge,
+        GraphType, Paragraph, Row, Sparkline, Table, TableState,
     },
     Frame,
 };
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+const HISTOGRAM_MAX_BINS: usize = 12;
+const SHARE_BAR_WIDTH: usize = 8;
+
+const SERIES_COLORS: [Color; 8] = [
+    Color::Cyan,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Green,
+    Color::LightBlue,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightMagenta,
+];
+
+pub fn series_color(series_key: &str) -> Color {
+    let hash = series_key
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    SERIES_COLORS[(hash % SERIES_COLORS.len() as u64) as usize]
+}
+
+enum MetricRow<'m> {
+    Cells(&'m [&'m MetricHistory]),
+    Overlay(&'m [&'m MetricHistory]),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StepSort {
+    #[default]
+    Order,
+    Duration,
+    Errors,
+    Warnings,
+}
+
+impl StepSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Order => Self::Duration,
+            Self::Duration => Self::Errors,
+            Self::Errors => Self::Warnings,
+            Self::Warnings => Self::Order,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Order => "run order",
+            Self::Duration => "duration",
+            Self::Errors => "errors",
+            Self::Warnings => "warnings",
+        }
+    }
+
+    pub fn compare(self, a: &BuildStepResult, b: &BuildStepResult) -> Ordering {
+        match self {
+            Self::Order => Ordering::Equal,
+            Self::Duration => b.duration.total_cmp(&a.duration),
+            Self::Errors => b.error_count.cmp(&a.error_count),
+            Self::Warnings => b.warning_count.cmp(&a.warning_count),
+        }
+    }
+
+    pub fn sorted(self, steps: &[BuildStepResult]) -> Vec<&BuildStepResult> {
+        let mut sorted: Vec<&BuildStepResult> = steps.iter().collect();
+        sorted.sort_by(|a, b| self.compare(a, b));
+        sorted
+    }
+}
+
+pub fn duration_share(duration: f64, total: f64) -> String {
+    let ratio = progress::ratio(duration, total);
+    format!("{:>3.0}% {}", ratio * 100.0, progress::bar(ratio, SHARE_BAR_WIDTH))
+}
+
 pub struct SummaryTab<'a> {
     steps: &'a [BuildStepResult],
+    skipped: &'a [SkippedStep],
+    metric_groups: &'a [String],
+    sort: StepSort,
 }
 
 impl<'a> SummaryTab<'a> {
     pub fn new(steps: &'a [BuildStepResult]) -> Self {
-        Self { steps }
+        Self {
+            steps,
+            skipped: &[],
+            metric_groups: &[],
+            sort: StepSort::default(),
+        }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
-        let header_cells = ["Step", "Duration", "Status", "Errors", "Warnings"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
+    pub fn with_sort(mut self, sort: StepSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn with_metric_groups(mut self, metric_groups: &'a [String]) -> Self {
+        self.metric_groups = metric_groups;
+        self
+    }
+
+    pub fn with_skipped(mut self, skipped: &'a [SkippedStep]) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut TableState) {
+        let header_cells = [
+            "Step",
+            "Target",
+            "Duration",
+            "% of total",
+            "Status",
+            "Errors",
+            "Warnings",
+        ]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
         let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-        let rows = self.steps.iter().map(|step| {
+        let total_duration: f64 = self.steps.iter().map(|step| step.duration).sum();
+        let rows = self.sort.sorted(self.steps).into_iter().map(|step| {
             let status_color = if step.success {
                 Color::Green
             } else {
@@ -36,32 +150,70 @@ impl<'a> SummaryTab<'a> {
 
             Row::new(vec![
                 Cell::from(step.description.clone()),
+                Cell::from(step.target.clone().unwrap_or_else(|| "-".to_string()))
+                    .style(Style::default().fg(Color::DarkGray)),
                 Cell::from(format!("{:.2}s", step.duration)),
+                Cell::from(duration_share(step.duration, total_duration))
+                    .style(Style::default().fg(Color::Magenta)),
                 Cell::from(status_text).style(Style::default().fg(status_color)),
                 Cell::from(step.error_count.to_string()),
                 Cell::from(step.warning_count.to_string()),
             ])
         });
 
+        let skipped_rows = self.skipped.iter().map(|skipped| {
+            let style = Style::default().fg(Color::DarkGray);
+            Row::new(vec![
+                Cell::from(format!("{} ({})", skipped.description, skipped.reason)).style(style),
+                Cell::from("-").style(style),
+                Cell::from("-").style(style),
+                Cell::from("-").style(style),
+                Cell::from("⊘ SKIP").style(Style::default().fg(Color::Yellow)),
+                Cell::from("-").style(style),
+                Cell::from("-").style(style),
+            ])
+        });
+        let total_style = Style::default().add_modifier(Modifier::BOLD);
+        let passed = self.steps.iter().filter(|step| step.success).count();
+        let total_row = Row::new(vec![
+            Cell::from("Total"),
+            Cell::from(""),
+            Cell::from(format!("{:.2}s", total_duration)),
+            Cell::from(""),
+            Cell::from(format!("{}/{}", passed, self.steps.len())),
+            Cell::from(self.steps.iter().map(|s| s.error_count).sum::<usize>().to_string()),
+            Cell::from(self.steps.iter().map(|s| s.warning_count).sum::<usize>().to_string()),
+        ])
+        .style(total_style)
+        .top_margin(1);
+        let rows = rows
+            .chain(skipped_rows)
+            .chain((!self.steps.is_empty()).then_some(total_row));
+
         let widths = [
-            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Length(16),
             Constraint::Length(12),
+            Constraint::Length(14),
             Constraint::Length(10),
             Constraint::Length(10),
             Constraint::Length(10),
         ];
 
-        let title = " Summary ";
+        let title = format!(" Summary [o: Sort by {} | yy: Copy command] ", self.sort.label());
 
-        let table = Table::new(rows, widths).header(header).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(Title::from(title).alignment(Alignment::Center)),
-        );
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(title).alignment(Alignment::Center)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
 
-        frame.render_widget(table, area);
+        frame.render_stateful_widget(table, area, state);
     }
 
     pub fn render_metrics(
@@ -85,12 +237,27 @@ impl<'a> SummaryTab<'a> {
 
         let mut all_metrics: Vec<&MetricHistory> = metrics.values().collect();
         all_metrics.sort_by(|a, b| {
-            a.category.cmp(&b.category).then(a.key.cmp(&b.key))
+            a.category
+                .cmp(&b.category)
+                .then(a.key.cmp(&b.key))
+                .then(tag_label(&a.tags).cmp(&tag_label(&b.tags)))
         });
 
         let metrics_per_row = 3;
-        let total_metrics = all_metrics.len();
-        let row_count = (total_metrics + metrics_per_row - 1) / metrics_per_row;
+        let mut rows: Vec<MetricRow> = Vec::new();
+        let mut category_start = 0;
+        for i in 1..=all_metrics.len() {
+            if i == all_metrics.len() || all_metrics[i].category != all_metrics[i - 1].category {
+                let category = &all_metrics[category_start..i];
+                if self.metric_groups.contains(&category[0].category) {
+                    rows.push(MetricRow::Overlay(category));
+                } else {
+                    rows.extend(category.chunks(metrics_per_row).map(MetricRow::Cells));
+                }
+                category_start = i;
+            }
+        }
+        let row_count = rows.len();
 
         if row_count == 0 {
             return;
@@ -117,17 +284,90 @@ impl<'a> SummaryTab<'a> {
             .constraints(constraints)
             .split(area);
 
-        for (row, chunk) in row_chunks.iter().enumerate().take(row_count) {
-            let start_idx = row * metrics_per_row;
-            let end_idx = (start_idx + metrics_per_row).min(total_metrics);
-            let metrics_in_row = &all_metrics[start_idx..end_idx];
-
-            if !metrics_in_row.is_empty() {
-                self.render_metric_row(frame, *chunk, metrics_in_row);
+        for (row, chunk) in rows.iter().zip(row_chunks.iter()) {
+            match row {
+                MetricRow::Cells(metrics) => self.render_metric_row(frame, *chunk, metrics),
+                MetricRow::Overlay(series) => self.render_overlay_chart(frame, *chunk, series),
             }
         }
     }
 
+    fn render_overlay_chart(&self, frame: &mut Frame, area: Rect, series: &[&MetricHistory]) {
+        let Some(origin) = series.iter().filter_map(|m| m.timestamps.front()).min() else {
+            return;
+        };
+
+        let points: Vec<Vec<(f64, f64)>> = series
+            .iter()
+            .map(|metric| {
+                metric
+                    .timestamps
+                    .iter()
+                    .zip(&metric.values)
+                    .map(|(t, v)| (t.saturating_duration_since(*origin).as_secs_f64(), *v))
+                    .collect()
+            })
+            .collect();
+        let names: Vec<String> = series.iter().map(|m| m.series_label()).collect();
+
+        let title = format!(" {} ", series[0].category);
+
+        let datasets = series
+            .iter()
+            .zip(&names)
+            .zip(&points)
+            .map(|((metric, name), data)| {
+                let series_key = format!("{}:{}", metric.category, name);
+                Dataset::default()
+                    .name(name.clone())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(series_color(&series_key)))
+                    .data(data)
+            })
+            .collect();
+
+        let all_points = points.concat();
+        let x_max = all_points.iter().map(|(x, _)| *x).fold(0.0f64, f64::max);
+        let (y_min_bound, y_max_bound) = y_axis_bounds(&all_points, 5.0);
+
+        let x_labels = vec![
+            Span::raw("0s"),
+            Span::raw(format!("{:.1}s", x_max / 2.0)),
+            Span::raw(format!("{:.1}s", x_max)),
+        ];
+
+        let y_labels = vec![
+            Span::raw(format!("{:.1}", y_min_bound)),
+            Span::raw(format!("{:.1}", (y_min_bound + y_max_bound) / 2.0)),
+            Span::raw(format!("{:.1}", y_max_bound)),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Title::from(title).alignment(Alignment::Center)),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, x_max])
+                    .labels(x_labels),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([y_min_bound, y_max_bound])
+                    .labels(y_labels),
+            )
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+
+        frame.render_widget(chart, area);
+    }
+
     fn render_metric_row(
         &self,
         frame: &mut Frame,
@@ -185,6 +425,9 @@ impl<'a> SummaryTab<'a> {
             MetricVisualization::Table => {
                 self.render_simple_metric(frame, area, metric)
             }
+            MetricVisualization::Histogram => {
+                self.render_histogram_metric(frame, area, metric)
+            }
             MetricVisualization::Auto => {
                 match metric.metric_type {
                     MetricType::FPS => self.render_fps_metric(frame, area, metric),
@@ -224,7 +467,7 @@ impl<'a> SummaryTab<'a> {
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Green))
-            .title(format!(" {}: {} ", metric.category, metric.key));
+            .title(metric.title());
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -266,7 +509,11 @@ impl<'a> SummaryTab<'a> {
         metric: &MetricHistory,
     ) {
         let latest = metric.latest_value().unwrap_or(0.0);
-        let ratio = (latest / 100.0).clamp(0.0, 1.0);
+        let ratio = metric.ratio(latest);
+        let label = match metric.range {
+            Some((min, max)) => format!("{:.1} ({:.0}–{:.0})", latest, min, max),
+            None => format!("{:.1}%", latest),
+        };
 
         let gauge = Gauge::default()
             .block(
@@ -274,7 +521,7 @@ impl<'a> SummaryTab<'a> {
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
                     .border_style(Style::default().fg(Color::Cyan))
-                    .title(format!(" {}: {} ", metric.category, metric.key)),
+                    .title(metric.title()),
             )
             .gauge_style(
                 Style::default()
@@ -282,7 +529,7 @@ impl<'a> SummaryTab<'a> {
                     .add_modifier(Modifier::BOLD),
             )
             .ratio(ratio)
-            .label(format!("{:.1}%", latest));
+            .label(label);
 
         frame.render_widget(gauge, area);
     }
@@ -308,7 +555,7 @@ impl<'a> SummaryTab<'a> {
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Yellow))
-            .title(format!(" {}: {} ", metric.category, metric.key));
+            .title(metric.title());
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -365,7 +612,7 @@ impl<'a> SummaryTab<'a> {
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Cyan))
-            .title(format!(" {}: {} ", metric.category, metric.key));
+            .title(metric.title());
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -400,6 +647,50 @@ impl<'a> SummaryTab<'a> {
         }
     }
 
+    fn render_histogram_metric(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        metric: &MetricHistory,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(metric.title());
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 2 || inner.width < 2 {
+            return;
+        }
+
+        let bins = (inner.width as usize / 6).clamp(1, HISTOGRAM_MAX_BINS);
+        let buckets = metric.histogram(bins);
+        let labels: Vec<String> = buckets
+            .iter()
+            .map(|(lower, _)| format!("{:.1}", lower))
+            .collect();
+        let data: Vec<(&str, u64)> = labels
+            .iter()
+            .zip(&buckets)
+            .map(|(label, (_, count))| (label.as_str(), *count))
+            .collect();
+
+        let bar_width = ((inner.width as usize).saturating_sub(bins - 1) / bins).max(1) as u16;
+
+        let chart = BarChart::default()
+            .data(&data)
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(Color::Cyan))
+            .value_style(Style::default().fg(Color::Black).bg(Color::Cyan))
+            .label_style(Style::default().fg(Color::Gray));
+
+        frame.render_widget(chart, inner);
+    }
+
     fn render_simple_metric(
         &self,
         frame: &mut Frame,
@@ -418,7 +709,7 @@ impl<'a> SummaryTab<'a> {
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Cyan))
-            .title(format!(" {}: {} ", metric.category, metric.key));
+            .title(metric.title());
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -446,3 +737,66 @@ impl<'a> SummaryTab<'a> {
         frame.render_widget(paragraph, text_area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(description: &str, duration: f64, error_count: usize) -> BuildStepResult {
+        BuildStepResult {
+            description: description.to_string(),
+            duration,
+            success: error_count == 0,
+            error_count,
+            warning_count: 0,
+            commands: Vec::new(),
+            target: None,
+            start_offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_step_sort_is_stable_and_descending() {
+        let steps = vec![
+            step("Configure", 1.5, 0),
+            step("Build", 42.0, 2),
+            step("Install", 1.5, 0),
+            step("Test", 7.25, 2),
+        ];
+        let names = |sort: StepSort| -> Vec<&str> {
+            sort.sorted(&steps)
+                .iter()
+                .map(|step| step.description.as_str())
+                .collect()
+        };
+
+        assert_eq!(names(StepSort::Order), ["Configure", "Build", "Install", "Test"]);
+        assert_eq!(names(StepSort::Duration), ["Build", "Test", "Configure", "Install"]);
+        assert_eq!(names(StepSort::Errors), ["Build", "Test", "Configure", "Install"]);
+        assert_eq!(StepSort::Warnings.next(), StepSort::Order);
+        assert_eq!(steps[0].description, "Configure");
+    }
+
+    #[test]
+    fn test_duration_share_handles_zero_total() {
+        assert_eq!(duration_share(63.0, 90.0), " 70% █████░░░");
+        assert_eq!(duration_share(0.0, 0.0), "  0% ░░░░░░░░");
+    }
+
+    #[test]
+    fn test_series_color_is_deterministic() {
+        assert_eq!(series_color("frame:update_ms"), series_color("frame:update_ms"));
+
+        let keys = [
+            "frame:update_ms",
+            "frame:render_ms",
+            "frame:gpu_ms",
+            "frame:present_ms",
+        ];
+        let colors: std::collections::HashSet<Color> = keys
+            .iter()
+            .map(|key| series_color(key))
+            .collect();
+        assert!(colors.len() > 1);
+    }
+}
@@ -10,6 +10,8 @@ use std::path::PathBuf;
 
 pub struct WarningsTab<'a> {
     log_entries: &'a [LogEntry],
+    position: Option<(usize, usize)>,
+    errors_only: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -21,29 +23,79 @@ pub struct WarningLocation {
 
 impl<'a> WarningsTab<'a> {
     pub fn new(log_entries: &'a [LogEntry]) -> Self {
-        Self { log_entries }
+        Self {
+            log_entries,
+            position: None,
+            errors_only: false,
+        }
+    }
+
+    pub fn with_errors_only(mut self, errors_only: bool) -> Self {
+        self.errors_only = errors_only;
+        self
+    }
+
+    pub fn with_position(mut self, position: Option<(usize, usize)>) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn diagnostics(log_entries: &[LogEntry]) -> Vec<&LogEntry> {
+        let errors = log_entries
+            .iter()
+            .filter(|e| matches!(e.level, LogLevel::Error | LogLevel::Fatal));
+        let warnings = log_entries.iter().filter(|e| e.level == LogLevel::Warning);
+        errors.chain(warnings).collect()
+    }
+
+    pub fn errors(log_entries: &[LogEntry]) -> Vec<&LogEntry> {
+        let mut errors: Vec<&LogEntry> = log_entries
+            .iter()
+            .filter(|e| matches!(e.level, LogLevel::Error | LogLevel::Fatal))
+            .collect();
+        errors.sort_by(|a, b| {
+            b.level
+                .cmp(&a.level)
+                .then_with(|| a.file_path.is_none().cmp(&b.file_path.is_none()))
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        errors
+    }
+
+    fn entries(&self) -> Vec<&'a LogEntry> {
+        if self.errors_only {
+            Self::errors(self.log_entries)
+        } else {
+            Self::diagnostics(self.log_entries)
+        }
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect, state: &mut ListState) {
-        let warnings: Vec<&LogEntry> = self
-            .log_entries
+        let diagnostics = self.entries();
+        let warning_count = diagnostics
             .iter()
             .filter(|e| e.level == LogLevel::Warning)
-            .collect();
+            .count();
 
-        let items: Vec<ListItem> = warnings
+        let items: Vec<ListItem> = diagnostics
             .iter()
             .map(|entry| {
+                let color = if entry.level == LogLevel::Warning {
+                    Color::Yellow
+                } else {
+                    Color::Red
+                };
                 let content = if let Some(location) = entry.location_string() {
                     vec![
-                        Span::styled(&entry.message, Style::default().fg(Color::Yellow)),
+                        Span::styled(&entry.message, Style::default().fg(color)),
                         Span::raw(" "),
                         Span::styled(location, Style::default().fg(Color::Cyan)),
                     ]
                 } else {
                     vec![Span::styled(
                         &entry.raw_line,
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(color),
                     )]
                 };
 
@@ -51,7 +103,19 @@ impl<'a> WarningsTab<'a> {
             })
             .collect();
 
-        let title = format!(" Warnings ({}) [↑↓/jk: Scroll | Enter: Open File] ", warnings.len());
+        let position = match self.position {
+            Some((current, total)) => format!(" {}/{}", current, total),
+            None => String::new(),
+        };
+        let counts = if self.errors_only {
+            format!("Errors ({})", diagnostics.len())
+        } else {
+            format!("Errors ({}) Warnings ({})", diagnostics.len() - warning_count, warning_count)
+        };
+        let title = format!(
+            " {}{} [jk: Scroll | ]q/[q: Next/Prev | Enter/gf: Open] ",
+            counts, position
+        );
         let list = List::new(items)
             .block(
                 Block::default()
@@ -67,13 +131,9 @@ impl<'a> WarningsTab<'a> {
     }
 
     pub fn get_selected_location(&self, selected: usize) -> Option<WarningLocation> {
-        let warnings: Vec<&LogEntry> = self
-            .log_entries
-            .iter()
-            .filter(|e| e.level == LogLevel::Warning)
-            .collect();
+        let diagnostics = self.entries();
 
-        let entry = warnings.get(selected)?;
+        let entry = diagnostics.get(selected)?;
         self.parse_warning_location(entry)
     }
 
@@ -1,4 +1,5 @@
-use crate::parser::filters::{LevelFilter, LogFilter, PatternFilter};
+use crate::parser::entry::StreamKind;
+use crate::parser::filters::{LevelFilter, LogFilter, PatternFilter, StreamFilter};
 use crate::parser::entry::LogLevel;
 use super::keybinding_manager::PendingSequence;
 
@@ -59,6 +60,12 @@ impl VimCommandMode {
 
         let result = if cmd == "q" || cmd == "quit" {
             Some(CommandResult::Quit)
+        } else if cmd == "writeperf" || cmd.starts_with("writeperf ") {
+            let file = cmd.strip_prefix("writeperf ").map(|s| s.trim().to_string());
+            Some(CommandResult::WritePerf(file))
+        } else if cmd == "writetrace" || cmd.starts_with("writetrace ") {
+            let file = cmd.strip_prefix("writetrace ").map(|s| s.trim().to_string());
+            Some(CommandResult::WriteTrace(file))
         } else if cmd == "w" || cmd.starts_with("w ") {
             let file = cmd.strip_prefix("w ").map(|s| s.trim().to_string());
             Some(CommandResult::WriteLogs(file))
@@ -75,8 +82,45 @@ impl VimCommandMode {
             } else {
                 None
             }
-        } else if cmd == "nofilter" || cmd == "nf" {
+        } else if let Some(stream) = cmd.strip_prefix("filter stream=") {
+            StreamKind::from_name(stream)
+                .map(|stream| CommandResult::ApplyFilter(Box::new(StreamFilter::new(stream))))
+        } else if cmd == "errors" {
+            Some(CommandResult::ApplyFilter(Box::new(LevelFilter::new(LogLevel::Error))))
+        } else if cmd == "warnings" {
+            Some(CommandResult::ApplyFilter(Box::new(LevelFilter::only(LogLevel::Warning))))
+        } else if cmd == "noise" {
+            Some(CommandResult::ApplyFilter(Box::new(LevelFilter::new(LogLevel::Warning))))
+        } else if cmd == "nofilter" || cmd == "nf" || cmd == "all" {
             Some(CommandResult::ClearFilter)
+        } else if cmd == "set rnu" || cmd == "set relativenumber" {
+            Some(CommandResult::SetRelativeNumbers(true))
+        } else if cmd == "set nornu" || cmd == "set norelativenumber" {
+            Some(CommandResult::SetRelativeNumbers(false))
+        } else if cmd == "set rnu!" || cmd == "set relativenumber!" {
+            Some(CommandResult::ToggleRelativeNumbers)
+        } else if cmd == "preset" {
+            Some(CommandResult::OpenPresetPicker)
+        } else if let Some(name) = cmd.strip_prefix("preset ") {
+            Some(CommandResult::SetPreset(name.trim().to_string()))
+        } else if cmd == "note" {
+            Some(CommandResult::SetNote(None))
+        } else if let Some(text) = cmd.strip_prefix("note ") {
+            let text = text.trim();
+            Some(CommandResult::SetNote((!text.is_empty()).then(|| text.to_string())))
+        } else if cmd == "args" {
+            Some(CommandResult::SetExecArgs(Vec::new()))
+        } else if let Some(args) = cmd.strip_prefix("args ") {
+            Some(CommandResult::SetExecArgs(
+                args.split_whitespace().map(str::to_string).collect(),
+            ))
+        } else if let Some(category) = cmd.strip_prefix("group ") {
+            let category = category.trim();
+            (!category.is_empty()).then(|| CommandResult::ToggleMetricGroup(category.to_string()))
+        } else if cmd == "install" {
+            Some(CommandResult::InstallOnly)
+        } else if cmd == "clearold" {
+            Some(CommandResult::ClearPreviousLogs)
         } else if let Ok(line_number) = cmd.parse::<usize>() {
             Some(CommandResult::GotoLine(line_number))
         } else {
@@ -175,10 +219,21 @@ impl Default for VimCommandMode {
 pub enum CommandResult {
     Quit,
     WriteLogs(Option<String>),
+    WritePerf(Option<String>),
+    WriteTrace(Option<String>),
     ApplyFilter(Box<dyn LogFilter>),
     ClearFilter,
     Search(String, Box<dyn LogFilter>),
     GotoLine(usize),
+    ClearPreviousLogs,
+    SetRelativeNumbers(bool),
+    ToggleRelativeNumbers,
+    SetPreset(String),
+    OpenPresetPicker,
+    SetNote(Option<String>),
+    SetExecArgs(Vec<String>),
+    ToggleMetricGroup(String),
+    InstallOnly,
 }
 
 #[cfg(test)]
@@ -205,6 +260,165 @@ mod tests {
         assert!(matches!(result, Some(CommandResult::Quit)));
     }
 
+    #[test]
+    fn test_preset_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "preset release".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::SetPreset(ref name)) if name == "release"));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "preset".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::OpenPresetPicker)));
+    }
+
+    #[test]
+    fn test_note_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "note first build after toolchain upgrade".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(
+            result,
+            Some(CommandResult::SetNote(Some(ref text))) if text == "first build after toolchain upgrade"
+        ));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "note".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::SetNote(None))));
+    }
+
+    #[test]
+    fn test_args_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "args --scene  test.json".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(
+            result,
+            Some(CommandResult::SetExecArgs(args)) if args == vec!["--scene", "test.json"]
+        ));
+
+        vim_mode.input_buffer = "args".to_string();
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::SetExecArgs(args)) if args.is_empty()));
+    }
+
+    #[test]
+    fn test_group_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "group frame".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(
+            result,
+            Some(CommandResult::ToggleMetricGroup(ref category)) if category == "frame"
+        ));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "group  ".to_string();
+        assert!(vim_mode.execute_command().is_none());
+    }
+
+    #[test]
+    fn test_filter_stream_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "filter stream=stderr".to_string();
+
+        let Some(CommandResult::ApplyFilter(filter)) = vim_mode.execute_command() else {
+            panic!("Expected a stream filter");
+        };
+        assert_eq!(filter.description(), "stream: stderr");
+
+        vim_mode.input_buffer = "filter stream=tty".to_string();
+        assert!(vim_mode.execute_command().is_none());
+    }
+
+    #[test]
+    fn test_quick_filter_aliases() {
+        let mut vim_mode = VimCommandMode::new();
+        for (command, description) in [
+            ("errors", "level >= ERROR"),
+            ("warnings", "level = WARNING"),
+            ("noise", "level >= WARNING"),
+        ] {
+            vim_mode.mode = InputMode::Command;
+            vim_mode.input_buffer = command.to_string();
+            let Some(CommandResult::ApplyFilter(filter)) = vim_mode.execute_command() else {
+                panic!("Expected :{} to apply a filter", command);
+            };
+            assert_eq!(filter.description(), description);
+        }
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "all".to_string();
+        assert!(matches!(vim_mode.execute_command(), Some(CommandResult::ClearFilter)));
+    }
+
+    #[test]
+    fn test_writeperf_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "writeperf perf.csv".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(
+            result,
+            Some(CommandResult::WritePerf(Some(ref file))) if file == "perf.csv"
+        ));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "writeperf".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::WritePerf(None))));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "writetrace build.json".to_string();
+        assert!(matches!(
+            vim_mode.execute_command(),
+            Some(CommandResult::WriteTrace(Some(ref file))) if file == "build.json"
+        ));
+    }
+
+    #[test]
+    fn test_clearold_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "clearold".to_string();
+
+        let result = vim_mode.execute_command();
+        assert!(matches!(result, Some(CommandResult::ClearPreviousLogs)));
+    }
+
+    #[test]
+    fn test_set_relative_numbers_command() {
+        let mut vim_mode = VimCommandMode::new();
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "set rnu".to_string();
+        assert!(matches!(
+            vim_mode.execute_command(),
+            Some(CommandResult::SetRelativeNumbers(true))
+        ));
+
+        vim_mode.mode = InputMode::Command;
+        vim_mode.input_buffer = "set nornu".to_string();
+        assert!(matches!(
+            vim_mode.execute_command(),
+            Some(CommandResult::SetRelativeNumbers(false))
+        ));
+    }
+
     #[test]
     fn test_invalid_command() {
         let mut vim_mode = VimCommandMode::new();
@@ -0,0 +1,243 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use crate::config::WatchConfig;
+
+const SOURCE_EXTENSIONS: [&str; 7] = ["cpp", "cc", "cxx", "c", "h", "hpp", "cmake"];
+const EXCLUDED_DIRS: [&str; 3] = ["builds", "install", ".git"];
+
+pub struct WatchFilter {
+    roots: Vec<PathBuf>,
+    excluded: Vec<PathBuf>,
+    ignore: GlobSet,
+}
+
+impl WatchFilter {
+    pub fn new(roots: Vec<PathBuf>, config: &WatchConfig) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &config.ignore {
+            let glob = Glob::new(pattern)
+                .with_context(|| format!("Invalid watch.ignore pattern '{}'", pattern))?;
+            builder.add(glob);
+        }
+
+        Ok(Self {
+            roots,
+            excluded: Vec::new(),
+            ignore: builder.build()?,
+        })
+    }
+
+    pub fn with_excluded(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.excluded = dirs;
+        self
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        if !is_source_file(path) {
+            return false;
+        }
+
+        if self.excluded.iter().any(|dir| path.starts_with(dir)) {
+            return false;
+        }
+
+        let Some(relative) = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+        else {
+            return false;
+        };
+
+        let in_excluded_dir = relative.components().any(|component| match component {
+            Component::Normal(name) => EXCLUDED_DIRS.iter().any(|dir| name == *dir),
+            _ => false,
+        });
+
+        !in_excluded_dir && !self.ignore.is_match(relative)
+    }
+}
+
+fn is_source_file(path: &Path) -> bool {
+    if path
+        .file_name()
+        .is_some_and(|name| name == "CMakeLists.txt")
+    {
+        return true;
+    }
+
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+pub fn watch_roots(paths: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let path = path.canonicalize().unwrap_or(path);
+        if roots.iter().any(|root| path.starts_with(root)) {
+            continue;
+        }
+        roots.retain(|root| !root.starts_with(&path));
+        roots.push(path);
+    }
+    roots
+}
+
+pub async fn next_change(
+    rx: &mut mpsc::UnboundedReceiver<PathBuf>,
+    debounce: Duration,
+) -> Option<Vec<PathBuf>> {
+    let mut changed = vec![rx.recv().await?];
+
+    while let Ok(next) = tokio::time::timeout(debounce, rx.recv()).await {
+        match next {
+            Some(path) => {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+            None => break,
+        }
+    }
+
+    Some(changed)
+}
+
+pub struct SourceWatcher {
+    _watcher: RecommendedWatcher,
+    rx: mpsc::UnboundedReceiver<PathBuf>,
+    debounce: Duration,
+}
+
+impl SourceWatcher {
+    pub fn new(filter: WatchFilter, config: &WatchConfig) -> Result<Self> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let roots = filter.roots().to_vec();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    if filter.is_relevant(&path) {
+                        let _ = tx.send(path);
+                    }
+                }
+            })?;
+
+        for root in &roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {}", root.display()))?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+            debounce: config.debounce(),
+        })
+    }
+
+    pub async fn next_change(&mut self) -> Option<Vec<PathBuf>> {
+        next_change(&mut self.rx, self.debounce).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(ignore: &[&str]) -> WatchFilter {
+        let config = WatchConfig {
+            ignore: ignore.iter().map(|p| p.to_string()).collect(),
+            ..WatchConfig::default()
+        };
+        WatchFilter::new(vec![PathBuf::from("/work/engine")], &config).unwrap()
+    }
+
+    #[test]
+    fn test_filter_accepts_sources_and_cmake_files() {
+        let filter = filter(&[]);
+
+        assert!(filter.is_relevant(Path::new("/work/engine/src/main.cpp")));
+        assert!(filter.is_relevant(Path::new("/work/engine/include/engine.h")));
+        assert!(filter.is_relevant(Path::new("/work/engine/cmake/deps.cmake")));
+        assert!(filter.is_relevant(Path::new("/work/engine/CMakeLists.txt")));
+        assert!(!filter.is_relevant(Path::new("/work/engine/README.md")));
+        assert!(!filter.is_relevant(Path::new("/elsewhere/main.cpp")));
+    }
+
+    #[test]
+    fn test_filter_skips_build_and_install_dirs() {
+        let filter = filter(&[]).with_excluded(vec![PathBuf::from("/work/engine/out")]);
+
+        assert!(!filter.is_relevant(Path::new("/work/engine/builds/debug/CMakeFiles/a.cmake")));
+        assert!(!filter.is_relevant(Path::new("/work/engine/game/install/include/game.h")));
+        assert!(!filter.is_relevant(Path::new("/work/engine/out/build/cmake_install.cmake")));
+    }
+
+    #[test]
+    fn test_filter_applies_ignore_globs() {
+        let filter = filter(&["third_party/**", "*.gen.h"]);
+
+        assert!(!filter.is_relevant(Path::new("/work/engine/third_party/glm/glm.hpp")));
+        assert!(!filter.is_relevant(Path::new("/work/engine/src/shaders.gen.h")));
+        assert!(filter.is_relevant(Path::new("/work/engine/src/shaders.h")));
+    }
+
+    #[test]
+    fn test_filter_rejects_invalid_glob() {
+        let config = WatchConfig {
+            ignore: vec!["src/[".to_string()],
+            ..WatchConfig::default()
+        };
+        assert!(WatchFilter::new(vec![PathBuf::from("/work")], &config).is_err());
+    }
+
+    #[test]
+    fn test_watch_roots_drops_nested_paths() {
+        let roots = watch_roots(vec![
+            PathBuf::from("/work/engine/game"),
+            PathBuf::from("/work/engine"),
+            PathBuf::from("/work/tools"),
+            PathBuf::from("/work/engine/editor"),
+        ]);
+
+        assert_eq!(
+            roots,
+            vec![PathBuf::from("/work/engine"), PathBuf::from("/work/tools")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_change_debounces_bursts() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tx.send(PathBuf::from("a.cpp")).unwrap();
+        tx.send(PathBuf::from("b.h")).unwrap();
+        tx.send(PathBuf::from("a.cpp")).unwrap();
+
+        let changed = next_change(&mut rx, Duration::from_millis(20)).await;
+        assert_eq!(
+            changed,
+            Some(vec![PathBuf::from("a.cpp"), PathBuf::from("b.h")])
+        );
+
+        drop(tx);
+        assert_eq!(next_change(&mut rx, Duration::from_millis(20)).await, None);
+    }
+}
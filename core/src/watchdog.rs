@@ -0,0 +1,59 @@
+use std::future::Future;
+use std::time::Duration;
+
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogTimeout(pub Duration);
+
+impl std::fmt::Display for WatchdogTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ignis timed out after {}s (--timeout), aborting",
+            self.0.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for WatchdogTimeout {}
+
+pub async fn run_with_watchdog<F>(
+    work: F,
+    limit: Option<Duration>,
+) -> Result<F::Output, WatchdogTimeout>
+where
+    F: Future,
+{
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, work)
+            .await
+            .map_err(|_| WatchdogTimeout(limit)),
+        None => Ok(work.await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watchdog_fires_on_overlong_task() {
+        let work = async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            42
+        };
+
+        let result = run_with_watchdog(work, Some(Duration::from_millis(20))).await;
+        assert_eq!(result, Err(WatchdogTimeout(Duration::from_millis(20))));
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_passes_through_completed_task() {
+        let result = run_with_watchdog(async { 42 }, Some(Duration::from_secs(5))).await;
+        assert_eq!(result, Ok(42));
+
+        let result = run_with_watchdog(async { 7 }, None).await;
+        assert_eq!(result, Ok(7));
+    }
+}
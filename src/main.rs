@@ -1,24 +1,49 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 use ignis_core::logger::Logger;
-use ignis_core::parser::{CompilerOutputParser, LogLevel};
+use ignis_core::parser::{CompilerOutputParser, LogEntry, LogLevel};
 use ignis_core::runner::BuildContext;
-use ignis_core::{Builder, Cli, Config, ExecRunner, Stage, StageContext, StageRunner};
+use ignis_core::tui::ViewState;
+use ignis_core::{
+    Builder, Cli, Config, ExecRunner, ExecutableInfo, Stage, StageContext, StageRunner,
+};
+use std::path::Path;
 
 async fn execute_stages(
     builder: &Builder,
     stage_runner: &StageRunner,
     stages: Vec<Stage>,
+    previous_logs: Vec<LogEntry>,
+    view_state: ViewState,
+    scope: Option<&ExecutableInfo>,
+    watch_rx: Option<tokio::sync::watch::Receiver<u64>>,
 ) -> Result<ignis_core::tui::App> {
     let (mut app, log_tx, step_tx) = stage_runner.idle().await?;
-    let context = StageContext::new(builder.clone(), log_tx.clone(), step_tx.clone());
+    app.retain_previous_logs(previous_logs);
+    app.restore_view_state(view_state);
+    let watching = watch_rx.is_some();
+    if let Some(watch_rx) = watch_rx {
+        app = app.with_watch(watch_rx);
+    }
+    let mut context = StageContext::new(builder.clone(), log_tx.clone(), step_tx.clone());
+    if let Some(exec_info) = scope {
+        context = context.with_executable(exec_info.clone());
+    }
 
     let build_handle = {
         let stage_runner_clone = StageRunner::new(BuildContext::new(builder.clone()));
         tokio::spawn(async move {
-            stage_runner_clone
-                .execute_with_dependencies(stages, context)
-                .await
+            match stages.as_slice() {
+                [stage] => stage_runner_clone
+                    .execute_stage(*stage, &context)
+                    .await
+                    .map(|result| std::collections::HashMap::from([(*stage, result)])),
+                _ => {
+                    stage_runner_clone
+                        .execute_with_dependencies(stages, context)
+                        .await
+                }
+            }
         })
     };
 
@@ -27,6 +52,17 @@ async fn execute_stages(
         Ok::<_, anyhow::Error>(app)
     });
 
+    if watching {
+        let mut app = app_handle.await??;
+        if build_handle.is_finished() {
+            build_handle.await??;
+            app.finalize_build()?;
+        } else {
+            build_handle.abort();
+        }
+        return Ok(app);
+    }
+
     let _build_results = build_handle.await??;
     drop(log_tx);
     drop(step_tx);
@@ -37,11 +73,15 @@ async fn execute_stages(
     Ok(app)
 }
 
-async fn run_with_tui(builder: Builder) -> Result<()> {
+async fn run_with_tui(
+    mut builder: Builder,
+    mut scope: Option<ExecutableInfo>,
+    trace: Option<&Path>,
+) -> Result<()> {
     use ignis_core::tui::BuildAction;
 
     let mut exec_runner = ExecRunner::new();
-    let stage_runner = StageRunner::new(BuildContext::new(builder.clone()));
+    let mut stage_runner = StageRunner::new(BuildContext::new(builder.clone()));
 
     let (mut app, _log_tx, _step_tx) = stage_runner.idle().await?;
 
@@ -67,52 +107,326 @@ async fn run_with_tui(builder: Builder) -> Result<()> {
             continue;
         }
 
+        if let Some(preset) = app.take_preset_change() {
+            builder = builder.with_preset(preset);
+            stage_runner = StageRunner::new(BuildContext::new(builder.clone()));
+            if let Some(exec_info) = &scope {
+                scope = Some(builder.resolve_build_target(&exec_info.name)?);
+            }
+
+            let (mut new_app, _new_log_tx, _new_step_tx) = stage_runner.idle().await?;
+            new_app.open_build_menu();
+
+            let app_handle = tokio::spawn(async move {
+                new_app.run().await?;
+                Ok::<_, anyhow::Error>(new_app)
+            });
+
+            app = app_handle.await??;
+            continue;
+        }
+
         match app.get_build_action() {
             Some(BuildAction::Quit) | None => break,
-            Some(BuildAction::Rebuild) => {
-                let stages = vec![Stage::Configure, Stage::Build, Stage::Install];
-                app = execute_stages(&builder, &stage_runner, stages).await?;
+            Some(action @ (BuildAction::Rebuild | BuildAction::Install)) => {
+                let previous_logs = app.take_log_entries();
+                let view_state = app.snapshot_view_state();
+                let stages = builder.root().config.stages.filter_enabled(action.stages());
+                app = execute_stages(
+                    &builder,
+                    &stage_runner,
+                    stages,
+                    previous_logs,
+                    view_state,
+                    scope.as_ref(),
+                    None,
+                )
+                .await?;
             }
             Some(BuildAction::Clean) => {
-                execute_stages(&builder, &stage_runner, vec![Stage::Clean]).await?;
-                app = execute_stages(&builder, &stage_runner, vec![Stage::Configure, Stage::Build, Stage::Install]).await?;
+                let previous_logs = app.take_log_entries();
+                let view_state = app.snapshot_view_state();
+                execute_stages(
+                    &builder,
+                    &stage_runner,
+                    vec![Stage::Clean],
+                    Vec::new(),
+                    view_state.clone(),
+                    scope.as_ref(),
+                    None,
+                )
+                .await?;
+                let stages = builder
+                    .root()
+                    .config
+                    .stages
+                    .filter_enabled(BuildAction::Clean.stages());
+                app = execute_stages(
+                    &builder,
+                    &stage_runner,
+                    stages,
+                    previous_logs,
+                    view_state,
+                    scope.as_ref(),
+                    None,
+                )
+                .await?;
+            }
+        }
+    }
+
+    if let Some(path) = trace {
+        ignis_core::trace::write_trace(path, app.build_steps())?;
+    }
+
+    Ok(())
+}
+
+async fn run_watch(
+    builder: Builder,
+    scope: Option<ExecutableInfo>,
+    trace: Option<&Path>,
+) -> Result<()> {
+    use ignis_core::tui::BuildAction;
+    use ignis_core::watch::{watch_roots, SourceWatcher, WatchFilter};
+
+    let config = builder.root().config.watch.clone();
+    let roots = watch_roots(
+        std::iter::once(builder.root().path.clone())
+            .chain(builder.targets().iter().map(|target| target.path.clone())),
+    );
+    let excluded = builder
+        .targets()
+        .iter()
+        .flat_map(|target| {
+            let path = target.path.canonicalize().unwrap_or(target.path.clone());
+            let (build_dir, install_dir) = builder.get_dirs(path, builder.preset());
+            [build_dir, install_dir]
+        })
+        .collect();
+    let filter = WatchFilter::new(roots, &config)?.with_excluded(excluded);
+    let mut watcher = SourceWatcher::new(filter, &config)?;
+
+    let (change_tx, mut change_rx) = tokio::sync::watch::channel(0u64);
+    let forwarder = tokio::spawn(async move {
+        while watcher.next_change().await.is_some() {
+            change_tx.send_modify(|generation| *generation += 1);
+        }
+    });
+
+    let stage_runner = StageRunner::new(BuildContext::new(builder.clone()));
+    let mut action = BuildAction::Rebuild;
+    let mut previous_logs = Vec::new();
+    let mut view_state = ViewState::default();
+
+    loop {
+        change_rx.borrow_and_update();
+        let stages = builder.root().config.stages.filter_enabled(action.stages());
+        let mut app = execute_stages(
+            &builder,
+            &stage_runner,
+            stages,
+            previous_logs,
+            view_state,
+            scope.as_ref(),
+            Some(change_rx.clone()),
+        )
+        .await?;
+
+        if let Some(path) = trace {
+            ignis_core::trace::write_trace(path, app.build_steps())?;
+        }
+
+        match app.get_build_action() {
+            Some(next @ (BuildAction::Rebuild | BuildAction::Install)) => {
+                action = next;
+                previous_logs = app.take_log_entries();
+                view_state = app.snapshot_view_state();
             }
+            _ => break,
+        }
+    }
+
+    forwarder.abort();
+    Ok(())
+}
+
+async fn run_json_report(
+    builder: Builder,
+    scope: Option<ExecutableInfo>,
+    trace: Option<&Path>,
+) -> Result<()> {
+    let steps = match &scope {
+        Some(exec_info) => builder.generate_build_target_steps(exec_info),
+        None => builder.generate_build_all(),
+    };
+
+    let report = ignis_core::report::run_report(
+        steps,
+        builder.root().config.build.step_timeout(),
+        &builder.build_env(),
+    )
+    .await?;
+
+    println!("{}", report.to_json()?);
+    if let Some(path) = trace {
+        ignis_core::trace::write_trace(path, &report.steps)?;
+    }
+
+    if !report.success {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+async fn run_bench(
+    builder: &Builder,
+    target: &str,
+    duration: std::time::Duration,
+    format: ignis_core::cli::OutputFormat,
+) -> Result<()> {
+    let exec_info = builder.resolve_build_target(target)?;
+    let env = builder.root().config.exec.process_env();
+
+    if builder.dry_run() {
+        println!(
+            "Dry run: would run {} for {:.1}s",
+            exec_info.path.display(),
+            duration.as_secs_f64()
+        );
+        return Ok(());
+    }
+
+    let report = ignis_core::bench::run_bench(exec_info, duration, &env).await?;
+
+    match format {
+        ignis_core::cli::OutputFormat::Json => println!("{}", report.to_json()?),
+        ignis_core::cli::OutputFormat::Text => {
+            println!("{} ran for {:.1}s", report.program, report.duration);
+            print!("{}", report.render_table());
+            if let Some(reason) = &report.failure_reason {
+                println!("Program failed: {}", reason);
+            }
+        }
+    }
+
+    if !report.success() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn print_dry_run(
+    builder: &Builder,
+    scope: Option<&ExecutableInfo>,
+    format: ignis_core::cli::OutputFormat,
+) -> Result<()> {
+    use ignis_core::tui::BuildAction;
+
+    let stages = builder
+        .root()
+        .config
+        .stages
+        .filter_enabled(BuildAction::Rebuild.stages());
+    let plan = match scope {
+        Some(exec_info) => builder.plan_for(&stages, exec_info),
+        None => builder.plan(&stages),
+    }
+    .context("Failed to resolve stage dependencies")?;
+
+    if format == ignis_core::cli::OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(());
+    }
+
+    println!("Dry run for preset: {}", builder.preset());
+    for (index, layer) in plan.layers.iter().enumerate() {
+        let names: Vec<String> = layer.iter().map(|p| p.stage.to_string()).collect();
+        println!("\n[{}] {}", index + 1, names.join(", "));
+
+        for step in layer.iter().flat_map(|planned| &planned.steps) {
+            println!("{}\n  {}", step.description, step.invocation());
         }
     }
 
     Ok(())
 }
 
-async fn run_without_tui(builder: Builder) -> Result<()> {
-    let logger = std::sync::Arc::new(Logger::new());
+async fn run_without_tui(
+    builder: Builder,
+    scope: Option<ExecutableInfo>,
+    trace: Option<&Path>,
+) -> Result<()> {
+    let logger = std::sync::Arc::new(Logger::new_with_config(
+        &builder.root().config,
+        "build_log",
+    )?);
     let parser = std::sync::Arc::new(std::sync::Mutex::new(CompilerOutputParser::new()));
 
     logger.log(LogLevel::Info, "Starting build...");
 
-    let steps = builder.generate_build_all();
+    let step_timeout = builder.root().config.build.step_timeout();
+    let env = builder.build_env();
+
+    let steps = match &scope {
+        Some(exec_info) => builder.generate_build_target_steps(exec_info),
+        None => builder.generate_build_all(),
+    };
+
+    let build_start = std::time::Instant::now();
+    let mut step_results = Vec::new();
 
     for step in steps {
         logger.log(LogLevel::Info, &format!("Step: {}", step));
 
         let logger_clone = logger.clone();
         let parser_clone = parser.clone();
+        let start_offset = build_start.elapsed().as_secs_f64();
 
         let result = ignis_core::executor::execute_step(
-            step.commands,
+            step.commands.clone(),
             move |line| {
                 let entry = parser_clone.lock().unwrap().parse_line(&line);
                 logger_clone.log_entry(&entry);
             },
             None,
+            step_timeout,
+            &env,
         )
         .await?;
 
+        if result.timed_out {
+            if let Some(limit) = step_timeout {
+                logger.log_entry(&ignis_core::executor::timeout_entry(limit));
+            }
+        }
+
+        step_results.push(ignis_core::BuildStepResult {
+            description: step.description.clone(),
+            duration: result.duration,
+            success: result.success,
+            error_count: 0,
+            warning_count: 0,
+            commands: step.argv(),
+            target: step.target.clone(),
+            start_offset,
+        });
+
         if !result.success {
             logger.log(ignis_core::parser::entry::LogLevel::Error, "Build failed!");
+            if let Some(path) = trace {
+                ignis_core::trace::write_trace(path, &step_results)?;
+            }
             std::process::exit(1);
         }
     }
 
+    if let Some(path) = trace {
+        ignis_core::trace::write_trace(path, &step_results)?;
+    }
+
     logger.log(
         ignis_core::parser::entry::LogLevel::Info,
         "Build completed successfully!",
@@ -123,7 +437,8 @@ async fn run_without_tui(builder: Builder) -> Result<()> {
 
 fn list_presets(cli: &Cli) {
     let source_dir = cli.source_directory();
-    let presets = ignis_core::builder::detect_available_presets(&source_dir);
+    let config = Config::load_from_file(source_dir.join("ignis.toml")).unwrap_or_default();
+    let presets = ignis_core::builder::available_presets(&config, &source_dir);
 
     println!("Available presets:");
     for preset in presets {
@@ -131,7 +446,22 @@ fn list_presets(cli: &Cli) {
     }
 }
 
-fn show_history(root: &ignis_core::target::Target, count: Option<usize>) -> Result<()> {
+fn run_doctor(cli: &Cli) {
+    use ignis_core::doctor::{has_critical_failure, render_table, run_checks};
+
+    let checks = run_checks(&cli.source_directory());
+    print!("{}", render_table(&checks));
+
+    if has_critical_failure(&checks) {
+        std::process::exit(1);
+    }
+}
+
+fn show_history(
+    root: &ignis_core::target::Target,
+    count: Option<usize>,
+    filter: &ignis_core::history::HistoryFilter,
+) -> Result<()> {
     use ignis_core::history::BuildHistory;
 
     let logger = Logger::new();
@@ -139,14 +469,19 @@ fn show_history(root: &ignis_core::target::Target, count: Option<usize>) -> Resu
     let history = BuildHistory::new(storage_path, root.config.history.max_builds)
         .context("Failed to load history")?;
 
-    let entries = history.entries();
+    let entries = filter.apply(history.entries());
     let count = count.unwrap_or(10).min(entries.len());
 
-    if entries.is_empty() {
+    if history.entries().is_empty() {
         logger.log(LogLevel::Info, "No build history found.");
         return Ok(());
     }
 
+    if entries.is_empty() {
+        logger.log(LogLevel::Info, "No build history matches the given filters.");
+        return Ok(());
+    }
+
     logger.log(LogLevel::Info, &format!("Build History (last {} entries):", count));
     logger.log(LogLevel::Info, "");
 
@@ -155,13 +490,20 @@ fn show_history(root: &ignis_core::target::Target, count: Option<usize>) -> Resu
         logger.log(
             LogLevel::Info,
             &format!(
-                "{} {} | {} | {:.1}s | {} errors, {} warnings",
+                "{} {} | {} | {:.1}s | {} errors, {} warnings | {} ({}){}",
                 status,
                 entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
                 entry.preset,
                 entry.duration,
                 entry.error_count,
-                entry.warning_count
+                entry.warning_count,
+                entry.short_commit().unwrap_or("-"),
+                entry.git_branch.as_deref().unwrap_or("-"),
+                entry
+                    .note
+                    .as_ref()
+                    .map(|note| format!(" * {}", note))
+                    .unwrap_or_default()
             ),
         );
     }
@@ -214,32 +556,108 @@ fn clear_history(
     Ok(())
 }
 
-async fn clean_build(builder: Builder) -> Result<()> {
+fn confirm(prompt: &str) -> Result<bool> {
+    use std::io::Write;
+
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn clean_build(builder: Builder) -> Result<()> {
+    use ignis_core::clean;
+
     println!("Cleaning build for preset: {}", builder.preset());
 
     let (build_dir, install_dir) = builder.get_root_dirs();
+    let entries = clean::plan(&[build_dir, install_dir])?;
+    if entries.is_empty() {
+        println!("Nothing to clean.");
+        return Ok(());
+    }
 
-    let build_dir = build_dir.display().to_string();
-    let install_dir = install_dir.display().to_string();
+    for entry in &entries {
+        println!("  {}", entry.describe());
+    }
 
-    let command = builder.generate_clean_command(build_dir, install_dir);
+    let total = clean::format_size(clean::total_bytes(&entries));
+    if builder.dry_run() {
+        println!("Dry run: would free {}.", total);
+        return Ok(());
+    }
 
-    let result = ignis_core::executor::execute_step(
-        command,
-        |line| {
-            println!("{}", line);
-        },
-        None,
-    )
-    .await?;
+    let threshold = builder.root().config.build.confirm_clean_over_mb;
+    if clean::needs_confirmation(&entries, threshold)
+        && !confirm(&format!("Remove {}? [y/N] ", total))?
+    {
+        println!("Clean cancelled.");
+        return Ok(());
+    }
 
-    if result.success {
-        println!("Clean completed successfully.");
-    } else {
-        println!("Clean failed!");
+    clean::remove(&entries)?;
+    println!("Clean completed successfully, freed {}.", total);
+
+    Ok(())
+}
+
+async fn run_tests(builder: Builder) -> Result<()> {
+    let logger = std::sync::Arc::new(Logger::new_with_config(
+        &builder.root().config,
+        "test_log",
+    )?);
+    let parser = std::sync::Arc::new(std::sync::Mutex::new(CompilerOutputParser::new()));
+
+    let step_timeout = builder.root().config.build.step_timeout();
+    let env = builder.build_env();
+    let steps = builder.generate_test_all();
+
+    if steps.is_empty() {
+        println!(
+            "No CTest tests found for preset: {} (build the project first)",
+            builder.preset()
+        );
+        return Ok(());
+    }
+
+    if builder.dry_run() {
+        for step in &steps {
+            println!("{}\n  {}", step.description, step.invocation());
+        }
+        return Ok(());
+    }
+
+    let mut failed = false;
+
+    for step in steps {
+        logger.log(LogLevel::Info, &format!("Step: {}", step));
+
+        let logger_clone = logger.clone();
+        let parser_clone = parser.clone();
+
+        let result = ignis_core::executor::execute_step(
+            step.commands,
+            move |line| {
+                let entry = parser_clone.lock().unwrap().parse_line(&line);
+                logger_clone.log_entry(&entry);
+            },
+            None,
+            step_timeout,
+            &env,
+        )
+        .await?;
+
+        failed |= !result.success;
+    }
+
+    if failed {
+        logger.log(LogLevel::Error, "Tests failed!");
         std::process::exit(1);
     }
 
+    logger.log(LogLevel::Info, "All tests passed.");
+
     Ok(())
 }
 
@@ -256,6 +674,7 @@ fn init_config(cli: &Cli, name: Option<String>) -> Result<()> {
 
     let mut config = Config::default();
     config.build.name = name;
+    config.build.kind = Some("root".to_string());
 
     config
         .save_to_file(&config_path)
@@ -266,10 +685,27 @@ fn init_config(cli: &Cli, name: Option<String>) -> Result<()> {
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
+    use ignis_core::watchdog::{run_with_watchdog, TIMEOUT_EXIT_CODE};
+    use std::time::Duration;
+
     let cli = Cli::parse();
+    let limit = cli.timeout.map(Duration::from_secs);
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start tokio runtime")?;
 
+    match runtime.block_on(run_with_watchdog(run(cli), limit)) {
+        Ok(result) => result,
+        Err(timeout) => {
+            runtime.shutdown_timeout(Duration::from_secs(2));
+            ignis_core::tui::restore_terminal();
+            eprintln!("{}", timeout);
+            std::process::exit(TIMEOUT_EXIT_CODE);
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
     if let Some(command) = &cli.command {
         match command {
             ignis_core::cli::Commands::Init { name } => {
@@ -280,6 +716,10 @@ async fn main() -> Result<()> {
                 list_presets(&cli);
                 return Ok(());
             }
+            ignis_core::cli::Commands::Doctor => {
+                run_doctor(&cli);
+                return Ok(());
+            }
             _ => {}
         }
     }
@@ -290,8 +730,19 @@ async fn main() -> Result<()> {
         match command {
             ignis_core::cli::Commands::History { command } => {
                 match command {
-                    ignis_core::cli::HistoryCommands::Show { count } => {
-                        show_history(&root, *count)?;
+                    ignis_core::cli::HistoryCommands::Show {
+                        count,
+                        preset,
+                        failed_only,
+                        since,
+                    } => {
+                        let filter = ignis_core::history::HistoryFilter {
+                            preset: preset.clone(),
+                            failed_only: *failed_only,
+                            since: *since,
+                            query: None,
+                        };
+                        show_history(&root, *count, &filter)?;
                     }
                     ignis_core::cli::HistoryCommands::Clear { r#type } => {
                         clear_history(&root, *r#type)?;
@@ -303,23 +754,56 @@ async fn main() -> Result<()> {
         }
     }
 
-    let preset = cli.preset.as_deref().unwrap_or("debug");
-    let builder = Builder::new(root, preset.to_string()).with_targets(targets);
+    let preset = cli
+        .preset
+        .clone()
+        .unwrap_or_else(|| root.config.default_preset());
+    root.config.check_preset(&preset)?;
+    let builder = Builder::new(root, preset)
+        .with_targets(targets)
+        .with_dry_run(cli.dry_run);
 
     if let Some(command) = &cli.command {
         match command {
             ignis_core::cli::Commands::Clean { .. } => {
-                clean_build(builder).await?;
+                clean_build(builder)?;
+                return Ok(());
+            }
+            ignis_core::cli::Commands::Test { preset: Some(preset) } => {
+                builder.root().config.check_preset(preset)?;
+                run_tests(builder.with_preset(preset.clone())).await?;
+                return Ok(());
+            }
+            ignis_core::cli::Commands::Test { preset: None } => {
+                run_tests(builder).await?;
                 return Ok(());
             }
             _ => {}
         }
     }
 
-    if cli.no_tui {
-        run_without_tui(builder).await?;
+    if let Some(ignis_core::cli::Commands::Bench { target, duration }) = &cli.command {
+        run_bench(&builder, target, *duration, cli.format).await?;
+        return Ok(());
+    }
+
+    let scope = match &cli.target {
+        Some(name) => Some(builder.resolve_build_target(name)?),
+        None => None,
+    };
+
+    if let Some(ignis_core::cli::Commands::Watch) = &cli.command {
+        return run_watch(builder, scope, cli.trace.as_deref()).await;
+    }
+
+    if cli.dry_run && (cli.no_tui || cli.format == ignis_core::cli::OutputFormat::Json) {
+        print_dry_run(&builder, scope.as_ref(), cli.format)?;
+    } else if cli.format == ignis_core::cli::OutputFormat::Json {
+        run_json_report(builder, scope, cli.trace.as_deref()).await?;
+    } else if cli.no_tui {
+        run_without_tui(builder, scope, cli.trace.as_deref()).await?;
     } else {
-        run_with_tui(builder).await?;
+        run_with_tui(builder, scope, cli.trace.as_deref()).await?;
     }
 
     Ok(())